@@ -22,8 +22,8 @@ fn main() {
 
     println!("#### mpl ####");
     let start = Instant::now();
-    setup.setup();
-    setup.solve();
+    setup.setup().unwrap();
+    setup.solve().unwrap();
     let duration = start.elapsed();
     println!("Time elapsed in expensive_function() is: {duration:?}");
 }
@@ -1,105 +1,208 @@
 use crate::space::Point;
-use rand::{rngs::ThreadRng, Rng};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Topology of one axis of the configuration space. Decides how the limits of the boundaries apply to the axis.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Topology {
+    /// Unbounded real line. The limits only restrict the sampling.
+    Linear,
+    /// Interval between the limits. Default of all axes.
+    #[default]
+    Bounded,
+    /// Circle from the lower to the upper limit, e.g. a continuous revolute joint from -PI to PI.
+    /// Values wrap around into [lower, upper), and differences take the short way around.
+    Circular,
+}
+
+impl Topology {
+    /// Returns the value in the canonical form of the axis: clamped for bounded axes, wrapped for circular axes.
+    /// A circular axis without length is left unchanged.
+    pub fn enforce(&self, value: f64, lower: f64, upper: f64) -> f64 {
+        match self {
+            Topology::Bounded => value.clamp(lower, upper),
+            Topology::Circular if upper > lower => {
+                lower + (value - lower).rem_euclid(upper - lower)
+            }
+            _ => value,
+        }
+    }
+
+    /// Returns the signed difference from `from` to `to`. On circular axes it is the shorter way around.
+    pub fn difference(&self, from: f64, to: f64, lower: f64, upper: f64) -> f64 {
+        let period: f64 = upper - lower;
+        match self {
+            Topology::Circular if period > 0f64 => {
+                (to - from + 0.5 * period).rem_euclid(period) - 0.5 * period
+            }
+            _ => to - from,
+        }
+    }
+}
 
-/// Boundaries Limit the search space in 2D. Gives an upper and lower limit for the X- and Y-Coordinate.
+/// Boundaries limit the search space. Gives an upper and lower limit for every axis of the configuration space.
 /// Is implemented similar to a bounding box. That means as an upper / lower limit for the boundary axis.
-/// Only 2D.
+/// The dimension defaults to 2D, where the axes are called X and Y.
+///
+/// The boundaries own the random number generator that is used to sample the search space.
+/// It is seeded from the operating system, unless a seed is set with `set_seed`.
+///
+/// Every axis has a topology. All axes are bounded, unless it is changed with `set_topology`.
 #[derive(Debug, Clone)]
-pub struct Boundaries {
-    x_lower: f64,
-    x_upper: f64,
-    y_lower: f64,
-    y_upper: f64,
-    rand: ThreadRng,
+pub struct Boundaries<const N: usize = 2> {
+    lower: [f64; N],
+    upper: [f64; N],
+    topology: [Topology; N],
+    rand: StdRng,
 }
 
 impl Boundaries {
     pub fn get_x_lower(&self) -> f64 {
-        self.x_lower
+        self.lower[0]
     }
 
     pub fn set_x_lower(&mut self, value: f64) {
-        self.x_lower = value;
+        self.lower[0] = value;
     }
 
     pub fn get_x_upper(&self) -> f64 {
-        self.x_upper
+        self.upper[0]
     }
 
     pub fn set_x_upper(&mut self, value: f64) {
-        self.x_upper = value;
+        self.upper[0] = value;
     }
 
     pub fn get_y_lower(&self) -> f64 {
-        self.y_lower
+        self.lower[1]
     }
 
     pub fn set_y_lower(&mut self, value: f64) {
-        self.y_lower = value;
+        self.lower[1] = value;
     }
 
     pub fn get_y_upper(&self) -> f64 {
-        self.y_upper
+        self.upper[1]
     }
 
     pub fn set_y_upper(&mut self, value: f64) {
-        self.y_upper = value;
+        self.upper[1] = value;
     }
-}
 
-impl Boundaries {
     // Constructor for an Boundaries Object.
     pub fn new(x_lower: f64, x_upper: f64, y_lower: f64, y_upper: f64) -> Self {
-        let rand = rand::rng();
+        Boundaries::from_limits([x_lower, y_lower], [x_upper, y_upper])
+    }
+
+    /// Constructor for an Boundaries Object with a seeded random number generator.
+    pub fn new_seeded(x_lower: f64, x_upper: f64, y_lower: f64, y_upper: f64, seed: u64) -> Self {
+        let mut boundaries = Boundaries::new(x_lower, x_upper, y_lower, y_upper);
+        boundaries.set_seed(seed);
+        boundaries
+    }
+}
+
+impl<const N: usize> Boundaries<N> {
+    /// Constructor for Boundaries in any dimension. The limits are given per axis.
+    pub fn from_limits(lower: [f64; N], upper: [f64; N]) -> Self {
+        let rand = StdRng::from_os_rng();
         Boundaries {
-            x_lower,
-            x_upper,
-            y_lower,
-            y_upper,
+            lower,
+            upper,
+            topology: [Topology::default(); N],
             rand,
         }
     }
 
-    /// Checks if node is inside the boundaries.
+    /// Returns the lower limits of all axes.
+    pub fn get_lower(&self) -> [f64; N] {
+        self.lower
+    }
+
+    /// Returns the upper limits of all axes.
+    pub fn get_upper(&self) -> [f64; N] {
+        self.upper
+    }
+
+    /// Returns the topology of all axes.
+    pub fn get_topology(&self) -> [Topology; N] {
+        self.topology
+    }
+
+    /// Sets the topology of all axes, e.g. `Topology::Circular` for the axes of continuous revolute joints.
+    pub fn set_topology(&mut self, topology: [Topology; N]) {
+        self.topology = topology;
+    }
+
+    /// Reseeds the random number generator. The same seed always yields the same sequence of random configurations.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rand = StdRng::seed_from_u64(seed);
+    }
+
+    /// Returns the random number generator. Samplers draw from it, so a seed makes them reproducible as well.
+    pub fn get_rng(&mut self) -> &mut StdRng {
+        &mut self.rand
+    }
+
+    /// Checks if node is inside the boundaries. Only bounded axes are checked, every value of a linear or circular axis is inside.
     /// Returns
     ///  - true: Node is inside space
     ///  - false: Node is outside space
-    pub fn is_node_inside(&self, node: &Point) -> bool {
-        if node.get_x() < self.x_lower {
-            return false;
-        }
-
-        if node.get_x() > self.x_upper {
-            return false;
-        }
-
-        if node.get_y() < self.y_lower {
-            return false;
-        }
+    pub fn is_node_inside(&self, node: &Point<N>) -> bool {
+        node.get_coordinates()
+            .iter()
+            .zip(self.lower.iter().zip(self.upper.iter()))
+            .zip(self.topology.iter())
+            .all(|((coordinate, (lower, upper)), topology)| {
+                *topology != Topology::Bounded || (coordinate >= lower && coordinate <= upper)
+            })
+    }
 
-        if node.get_y() > self.y_upper {
-            return false;
+    /// Returns the node with every axis in its canonical form: clamped for bounded axes, wrapped for circular axes.
+    pub fn enforce_bounds(&self, node: &Point<N>) -> Point<N> {
+        let mut coordinates: [f64; N] = node.get_coordinates();
+        for (axis, coordinate) in coordinates.iter_mut().enumerate() {
+            *coordinate =
+                self.topology[axis].enforce(*coordinate, self.lower[axis], self.upper[axis]);
         }
+        Point::from_coordinates(coordinates)
+    }
 
-        true
+    /// Returns the Euclidean distance between the nodes, where circular axes take the short way around.
+    pub fn distance(&self, from: &Point<N>, to: &Point<N>) -> f64 {
+        let (from, to) = (from.get_coordinates(), to.get_coordinates());
+        (0..N)
+            .map(|axis| {
+                self.topology[axis]
+                    .difference(from[axis], to[axis], self.lower[axis], self.upper[axis])
+                    .powi(2)
+            })
+            .sum::<f64>()
+            .sqrt()
     }
 
     /// Generates a random node, which is inside the boundary limits.
+    /// The limits are inclusive, so boundaries without area always return the same node. Circular axes are wrapped, so the upper limit becomes the lower one.
     /// Return
     ///  - Point: Has random coordinates.
-    pub fn generate_random_configuration(&mut self) -> Point {
-        let x: f64 = self.rand.random_range(self.x_lower..self.x_upper);
-        let y: f64 = self.rand.random_range(self.y_lower..self.y_upper);
-        Point::new(x, y)
+    pub fn generate_random_configuration(&mut self) -> Point<N> {
+        let mut coordinates: [f64; N] = [f64::default(); N];
+        for (axis, coordinate) in coordinates.iter_mut().enumerate() {
+            *coordinate = self.rand.random_range(self.lower[axis]..=self.upper[axis]);
+            if self.topology[axis] == Topology::Circular {
+                *coordinate =
+                    self.topology[axis].enforce(*coordinate, self.lower[axis], self.upper[axis]);
+            }
+        }
+        Point::from_coordinates(coordinates)
     }
 }
 
 /// Implements the `Default` trait for the `Boundaries` struct.
 ///
-/// This trait provides a default constructor for creating a `Boundaries` object with default values for the lower and upper limits of the X and Y coordinates.
-impl Default for Boundaries {
+/// This trait provides a default constructor for creating a `Boundaries` object with default values for the lower and upper limits of all axes.
+impl<const N: usize> Default for Boundaries<N> {
     fn default() -> Self {
-        Boundaries::new(f64::default(), f64::MAX, f64::default(), f64::MAX)
+        Boundaries::from_limits([f64::default(); N], [f64::MAX; N])
     }
 }
 
@@ -111,10 +214,10 @@ mod tests {
         use crate::boundaries::Boundaries;
 
         let bounds: Boundaries = Boundaries::new(0f64, 1f64, 2f64, 3f64);
-        assert_eq!(0f64, bounds.x_lower);
-        assert_eq!(1f64, bounds.x_upper);
-        assert_eq!(2f64, bounds.y_lower);
-        assert_eq!(3f64, bounds.y_upper);
+        assert_eq!(0f64, bounds.get_x_lower());
+        assert_eq!(1f64, bounds.get_x_upper());
+        assert_eq!(2f64, bounds.get_y_lower());
+        assert_eq!(3f64, bounds.get_y_upper());
     }
 
     #[test]
@@ -153,10 +256,10 @@ mod tests {
         use crate::boundaries::Boundaries;
 
         let bounds: Boundaries = Boundaries::new(0f64, 1f64, 2f64, 3f64);
-        assert_eq!(0f64, bounds.x_lower);
-        assert_eq!(1f64, bounds.x_upper);
-        assert_eq!(2f64, bounds.y_lower);
-        assert_eq!(3f64, bounds.y_upper);
+        assert_eq!(0f64, bounds.get_x_lower());
+        assert_eq!(1f64, bounds.get_x_upper());
+        assert_eq!(2f64, bounds.get_y_lower());
+        assert_eq!(3f64, bounds.get_y_upper());
     }
 
     // Test that the function returns a Boundaries object with the minimum possible values for all limits.
@@ -165,10 +268,10 @@ mod tests {
         use crate::boundaries::Boundaries;
 
         let bounds: Boundaries = Boundaries::new(f64::MIN, f64::MIN, f64::MIN, f64::MIN);
-        assert_eq!(f64::MIN, bounds.x_lower);
-        assert_eq!(f64::MIN, bounds.x_upper);
-        assert_eq!(f64::MIN, bounds.y_lower);
-        assert_eq!(f64::MIN, bounds.y_upper);
+        assert_eq!(f64::MIN, bounds.get_x_lower());
+        assert_eq!(f64::MIN, bounds.get_x_upper());
+        assert_eq!(f64::MIN, bounds.get_y_lower());
+        assert_eq!(f64::MIN, bounds.get_y_upper());
     }
 
     // Test that the function returns a Boundaries object with the maximum possible values for all limits.
@@ -177,10 +280,10 @@ mod tests {
         use crate::boundaries::Boundaries;
 
         let bounds: Boundaries = Boundaries::new(f64::MAX, f64::MAX, f64::MAX, f64::MAX);
-        assert_eq!(f64::MAX, bounds.x_lower);
-        assert_eq!(f64::MAX, bounds.x_upper);
-        assert_eq!(f64::MAX, bounds.y_lower);
-        assert_eq!(f64::MAX, bounds.y_upper);
+        assert_eq!(f64::MAX, bounds.get_x_lower());
+        assert_eq!(f64::MAX, bounds.get_x_upper());
+        assert_eq!(f64::MAX, bounds.get_y_lower());
+        assert_eq!(f64::MAX, bounds.get_y_upper());
     }
 
     // Test the behavior of the 'test_boundaries_dummy_f64' function when given negative input values
@@ -189,10 +292,10 @@ mod tests {
         use crate::boundaries::Boundaries;
 
         let bounds: Boundaries = Boundaries::new(-1f64, -2f64, -3f64, -4f64);
-        assert_eq!(-1f64, bounds.x_lower);
-        assert_eq!(-2f64, bounds.x_upper);
-        assert_eq!(-3f64, bounds.y_lower);
-        assert_eq!(-4f64, bounds.y_upper);
+        assert_eq!(-1f64, bounds.get_x_lower());
+        assert_eq!(-2f64, bounds.get_x_upper());
+        assert_eq!(-3f64, bounds.get_y_lower());
+        assert_eq!(-4f64, bounds.get_y_upper());
     }
 
     // Test the behavior of the 'test_boundaries_dummy_f64' function when given non-integer input values
@@ -201,10 +304,10 @@ mod tests {
         use crate::boundaries::Boundaries;
 
         let bounds: Boundaries = Boundaries::new(0.5f64, 1.5f64, 2.5f64, 3.5f64);
-        assert_eq!(0.5f64, bounds.x_lower);
-        assert_eq!(1.5f64, bounds.x_upper);
-        assert_eq!(2.5f64, bounds.y_lower);
-        assert_eq!(3.5f64, bounds.y_upper);
+        assert_eq!(0.5f64, bounds.get_x_lower());
+        assert_eq!(1.5f64, bounds.get_x_upper());
+        assert_eq!(2.5f64, bounds.get_y_lower());
+        assert_eq!(3.5f64, bounds.get_y_upper());
     }
 
     // Test that the function returns the expected values when given input values that are not in sequential order.
@@ -213,9 +316,133 @@ mod tests {
         use crate::boundaries::Boundaries;
 
         let bounds: Boundaries = Boundaries::new(1f64, 0f64, 3f64, 2f64);
-        assert_eq!(1f64, bounds.x_lower);
-        assert_eq!(0f64, bounds.x_upper);
-        assert_eq!(3f64, bounds.y_lower);
-        assert_eq!(2f64, bounds.y_upper);
+        assert_eq!(1f64, bounds.get_x_lower());
+        assert_eq!(0f64, bounds.get_x_upper());
+        assert_eq!(3f64, bounds.get_y_lower());
+        assert_eq!(2f64, bounds.get_y_upper());
+    }
+
+    // Test that boundaries without area do not panic and always return the single valid node.
+    #[test]
+    fn test_generate_random_configuration_zero_area() {
+        use crate::boundaries::Boundaries;
+        use crate::space::Point;
+
+        let mut bounds: Boundaries = Boundaries::new(1f64, 1f64, 2f64, 2f64);
+        assert_eq!(
+            bounds.generate_random_configuration(),
+            Point::new(1f64, 2f64)
+        );
+    }
+
+    // Test that the same seed yields the same sequence of random configurations.
+    #[test]
+    fn test_same_seed_same_configurations() {
+        use crate::boundaries::Boundaries;
+
+        let mut bounds1: Boundaries = Boundaries::new_seeded(0f64, 3f64, 0f64, 3f64, 42);
+        let mut bounds2: Boundaries = Boundaries::new(0f64, 3f64, 0f64, 3f64);
+        bounds2.set_seed(42);
+        for _ in 0..100 {
+            assert_eq!(
+                bounds1.generate_random_configuration(),
+                bounds2.generate_random_configuration()
+            );
+        }
+    }
+
+    // Test that different seeds yield different random configurations.
+    #[test]
+    fn test_different_seed_different_configurations() {
+        use crate::boundaries::Boundaries;
+
+        let mut bounds1: Boundaries = Boundaries::new_seeded(0f64, 3f64, 0f64, 3f64, 1);
+        let mut bounds2: Boundaries = Boundaries::new_seeded(0f64, 3f64, 0f64, 3f64, 2);
+        assert_ne!(
+            bounds1.generate_random_configuration(),
+            bounds2.generate_random_configuration()
+        );
+    }
+
+    // Test that a clone continues with the same random sequence as the original.
+    #[test]
+    fn test_clone_keeps_random_state() {
+        use crate::boundaries::Boundaries;
+
+        let mut bounds1: Boundaries = Boundaries::new_seeded(0f64, 3f64, 0f64, 3f64, 7);
+        bounds1.generate_random_configuration();
+        let mut bounds2: Boundaries = bounds1.clone();
+        assert_eq!(
+            bounds1.generate_random_configuration(),
+            bounds2.generate_random_configuration()
+        );
+    }
+
+    // Test sampling and the inside check in 3D.
+    #[test]
+    fn test_boundaries_3d() {
+        use crate::boundaries::Boundaries;
+        use crate::space::Point;
+
+        let mut bounds: Boundaries<3> =
+            Boundaries::from_limits([0f64, 0f64, 0f64], [1f64, 2f64, 3f64]);
+        bounds.set_seed(42);
+        assert!(bounds.is_node_inside(&Point::from_coordinates([0.5, 1.5, 2.5])));
+        assert!(!bounds.is_node_inside(&Point::from_coordinates([0.5, 1.5, 3.5])));
+        for _ in 0..100 {
+            let node: Point<3> = bounds.generate_random_configuration();
+            assert!(bounds.is_node_inside(&node));
+        }
+        assert_eq!(bounds.get_upper(), [1f64, 2f64, 3f64]);
+    }
+
+    #[test]
+    fn test_topology() {
+        use crate::boundaries::Topology;
+        use std::f64::consts::PI;
+
+        assert_eq!(Topology::Linear.enforce(5.0, 0.0, 1.0), 5.0);
+        assert_eq!(Topology::Bounded.enforce(5.0, 0.0, 1.0), 1.0);
+        assert!((Topology::Circular.enforce(3.0 * PI, -PI, PI) - (-PI)).abs() < 1e-12);
+        assert_eq!(Topology::Circular.enforce(0.5, 1.0, 1.0), 0.5);
+        assert_eq!(Topology::Bounded.difference(3.0, -3.0, -PI, PI), -6.0);
+        assert!(
+            (Topology::Circular.difference(3.0, -3.0, -PI, PI) - (2.0 * PI - 6.0)).abs() < 1e-12
+        );
+        assert_eq!(Topology::default(), Topology::Bounded);
+    }
+
+    // Test that circular and linear axes accept every value, and circular axes wrap for sampling and distances.
+    #[test]
+    fn test_boundaries_with_topology() {
+        use crate::boundaries::{Boundaries, Topology};
+        use crate::space::Point;
+        use std::f64::consts::PI;
+
+        let mut bounds: Boundaries<3> = Boundaries::from_limits([0.0, 0.0, -PI], [1.0, 1.0, PI]);
+        bounds.set_topology([Topology::Linear, Topology::Bounded, Topology::Circular]);
+        bounds.set_seed(3);
+        assert_eq!(
+            bounds.get_topology(),
+            [Topology::Linear, Topology::Bounded, Topology::Circular]
+        );
+        assert!(bounds.is_node_inside(&Point::from_coordinates([5.0, 0.5, 7.0])));
+        assert!(!bounds.is_node_inside(&Point::from_coordinates([0.5, 1.5, 0.0])));
+
+        let enforced: [f64; 3] = bounds
+            .enforce_bounds(&Point::from_coordinates([5.0, 1.5, 7.0]))
+            .get_coordinates();
+        assert_eq!(enforced[..2], [5.0, 1.0]);
+        assert!((enforced[2] - (7.0 - 2.0 * PI)).abs() < 1e-12);
+
+        let distance: f64 = bounds.distance(
+            &Point::from_coordinates([0.0, 0.0, 3.0]),
+            &Point::from_coordinates([0.0, 0.0, -3.0]),
+        );
+        assert!((distance - (2.0 * PI - 6.0)).abs() < 1e-12);
+        for _ in 0..100 {
+            let theta: f64 = bounds.generate_random_configuration().get_coordinates()[2];
+            assert!((-PI..PI).contains(&theta));
+        }
     }
 }
@@ -0,0 +1,237 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::collision_checker::{CollisionChecker, MotionCheck};
+use crate::space::Point;
+
+/// Counters of a collision checker.
+///
+/// - node_checks, edge_checks: Number of calls, including the ones answered from the cache
+/// - node_hits, edge_hits: Number of calls answered from the cache
+/// - time: Time spent in the wrapped collision checker
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CollisionStatistics {
+    pub node_checks: usize,
+    pub node_hits: usize,
+    pub edge_checks: usize,
+    pub edge_hits: usize,
+    pub time: Duration,
+}
+
+/// Exact bit pattern of a point, so it can be used as key of a hash map.
+type PointKey<const N: usize> = [u64; N];
+
+/// Decorator that memoizes the results of the wrapped collision checker and counts the checks.
+/// Points are compared by their exact coordinates. Edges are assumed to be symmetric, so begin and end can be swapped.
+///
+/// The cache grows with every new node and edge. It can be emptied with `clear_cache`.
+pub struct CachingCollisionChecker<const N: usize = 2> {
+    collision_checker: Box<dyn CollisionChecker<N>>,
+    nodes: RefCell<HashMap<PointKey<N>, bool>>,
+    edges: RefCell<HashMap<(PointKey<N>, PointKey<N>), bool>>,
+    statistics: Cell<CollisionStatistics>,
+}
+
+impl<const N: usize> CachingCollisionChecker<N> {
+    pub fn new(collision_checker: Box<dyn CollisionChecker<N>>) -> Self {
+        CachingCollisionChecker {
+            collision_checker,
+            nodes: RefCell::new(HashMap::new()),
+            edges: RefCell::new(HashMap::new()),
+            statistics: Cell::new(CollisionStatistics::default()),
+        }
+    }
+
+    pub fn new_box(
+        collision_checker: Box<dyn CollisionChecker<N>>,
+    ) -> Box<dyn CollisionChecker<N>> {
+        Box::new(CachingCollisionChecker::new(collision_checker))
+    }
+
+    /// Forgets all cached results. Needed, if the obstacles of the wrapped collision checker change.
+    pub fn clear_cache(&self) {
+        self.nodes.borrow_mut().clear();
+        self.edges.borrow_mut().clear();
+    }
+
+    pub fn reset_statistics(&self) {
+        self.statistics.set(CollisionStatistics::default());
+    }
+
+    fn key(point: &Point<N>) -> PointKey<N> {
+        point.get_coordinates().map(f64::to_bits)
+    }
+
+    /// Looks up the key in the cache. On a miss the check is run, timed, and its result is stored.
+    fn cached<K: std::hash::Hash + Eq>(
+        &self,
+        cache: &RefCell<HashMap<K, bool>>,
+        key: K,
+        check: impl FnOnce() -> bool,
+    ) -> (bool, bool) {
+        if let Some(colliding) = cache.borrow().get(&key) {
+            return (*colliding, true);
+        }
+        let start_time: Instant = Instant::now();
+        let colliding: bool = check();
+        let mut statistics: CollisionStatistics = self.statistics.get();
+        statistics.time += start_time.elapsed();
+        self.statistics.set(statistics);
+        cache.borrow_mut().insert(key, colliding);
+        (colliding, false)
+    }
+}
+
+impl<const N: usize> CollisionChecker<N> for CachingCollisionChecker<N> {
+    /// Initializes the wrapped collision checker.
+    fn init(&self) -> bool {
+        self.collision_checker.init()
+    }
+
+    fn is_node_colliding(&self, node: &Point<N>) -> bool {
+        let (colliding, hit) = self.cached(&self.nodes, Self::key(node), || {
+            self.collision_checker.is_node_colliding(node)
+        });
+        let mut statistics: CollisionStatistics = self.statistics.get();
+        statistics.node_checks += 1;
+        statistics.node_hits += usize::from(hit);
+        self.statistics.set(statistics);
+        colliding
+    }
+
+    fn is_edge_colliding(&self, begin: &Point<N>, end: &Point<N>) -> bool {
+        let (a, b) = (Self::key(begin), Self::key(end));
+        let key: (PointKey<N>, PointKey<N>) = if a <= b { (a, b) } else { (b, a) };
+        let (colliding, hit) = self.cached(&self.edges, key, || {
+            self.collision_checker.is_edge_colliding(begin, end)
+        });
+        let mut statistics: CollisionStatistics = self.statistics.get();
+        statistics.edge_checks += 1;
+        statistics.edge_hits += usize::from(hit);
+        self.statistics.set(statistics);
+        colliding
+    }
+
+    /// Is not cached, because clearance queries are rarely repeated.
+    fn clearance(&self, node: &Point<N>) -> Option<f64> {
+        self.collision_checker.clearance(node)
+    }
+
+    fn get_statistics(&self) -> Option<CollisionStatistics> {
+        Some(self.statistics.get())
+    }
+
+    /// Is not cached, because the result depends on the order of the checks along the edge.
+    fn check_motion(&self, begin: &Point<N>, end: &Point<N>) -> Option<MotionCheck<N>> {
+        self.collision_checker.check_motion(begin, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    /// Obstacle at x > 1. Counts the calls that reach it.
+    struct CountingCollisionChecker {
+        calls: Rc<Cell<usize>>,
+    }
+
+    impl CollisionChecker for CountingCollisionChecker {
+        fn init(&self) -> bool {
+            true
+        }
+
+        fn is_node_colliding(&self, node: &Point) -> bool {
+            self.calls.set(self.calls.get() + 1);
+            node.get_x() > 1f64
+        }
+
+        fn is_edge_colliding(&self, begin: &Point, end: &Point) -> bool {
+            self.calls.set(self.calls.get() + 1);
+            begin.get_x() > 1f64 || end.get_x() > 1f64
+        }
+    }
+
+    fn create_cache() -> (CachingCollisionChecker, Rc<Cell<usize>>) {
+        let calls: Rc<Cell<usize>> = Rc::new(Cell::new(0usize));
+        let cc: Box<dyn CollisionChecker> = Box::new(CountingCollisionChecker {
+            calls: Rc::clone(&calls),
+        });
+        (CachingCollisionChecker::new(cc), calls)
+    }
+
+    #[test]
+    fn test_node_cache() {
+        let (cc, calls) = create_cache();
+        assert!(!cc.is_node_colliding(&Point::new(0.5, 0f64)));
+        assert!(!cc.is_node_colliding(&Point::new(0.5, 0f64)));
+        assert!(cc.is_node_colliding(&Point::new(1.5, 0f64)));
+        assert!(cc.is_node_colliding(&Point::new(1.5, 0f64)));
+        assert_eq!(calls.get(), 2);
+
+        let statistics: CollisionStatistics = cc.get_statistics().unwrap();
+        assert_eq!(statistics.node_checks, 4);
+        assert_eq!(statistics.node_hits, 2);
+        assert_eq!(statistics.edge_checks, 0);
+    }
+
+    // Test that an edge is answered from the cache in both directions.
+    #[test]
+    fn test_edge_cache() {
+        let (cc, calls) = create_cache();
+        let a: Point = Point::new(0f64, 0f64);
+        let b: Point = Point::new(0.5, 1f64);
+        let c: Point = Point::new(2f64, 1f64);
+        assert!(!cc.is_edge_colliding(&a, &b));
+        assert!(!cc.is_edge_colliding(&b, &a));
+        assert!(cc.is_edge_colliding(&b, &c));
+        assert!(cc.is_edge_colliding(&c, &b));
+        assert_eq!(calls.get(), 2);
+
+        let statistics: CollisionStatistics = cc.get_statistics().unwrap();
+        assert_eq!(statistics.edge_checks, 4);
+        assert_eq!(statistics.edge_hits, 2);
+    }
+
+    #[test]
+    fn test_clear_cache_and_reset() {
+        let (cc, calls) = create_cache();
+        let node: Point = Point::new(0.5, 0f64);
+        cc.is_node_colliding(&node);
+        cc.clear_cache();
+        cc.is_node_colliding(&node);
+        assert_eq!(calls.get(), 2);
+
+        cc.reset_statistics();
+        assert_eq!(cc.get_statistics(), Some(CollisionStatistics::default()));
+    }
+
+    #[test]
+    fn test_time_spent() {
+        struct SlowCollisionChecker {}
+
+        impl CollisionChecker for SlowCollisionChecker {
+            fn init(&self) -> bool {
+                true
+            }
+
+            fn is_node_colliding(&self, _node: &Point) -> bool {
+                std::thread::sleep(Duration::from_millis(5));
+                false
+            }
+
+            fn is_edge_colliding(&self, _begin: &Point, _end: &Point) -> bool {
+                false
+            }
+        }
+
+        let cc: CachingCollisionChecker =
+            CachingCollisionChecker::new(Box::new(SlowCollisionChecker {}));
+        cc.is_node_colliding(&Point::new(0f64, 0f64));
+        cc.is_node_colliding(&Point::new(0f64, 0f64));
+        let time: Duration = cc.get_statistics().unwrap().time;
+        assert!(time >= Duration::from_millis(5));
+    }
+}
@@ -0,0 +1,279 @@
+use std::cell::{Cell, RefCell};
+use std::time::Instant;
+
+use crate::collision_checker::CollisionChecker;
+use crate::space::Point;
+
+/// Number of checks after which the children are sorted by their measured cost again.
+const REORDER_INTERVAL: usize = 64;
+
+/// Weight of a new measurement in the moving average of the cost of a child.
+const COST_SMOOTHING: f64 = 0.1;
+
+/// Moving average of the time a child needs for a check, in seconds.
+#[derive(Debug, Default)]
+struct MeasuredCost {
+    average: Cell<f64>,
+    measurements: Cell<usize>,
+}
+
+impl MeasuredCost {
+    fn add(&self, seconds: f64) {
+        let average: f64 = match self.measurements.get() {
+            0 => seconds,
+            _ => self.average.get() + COST_SMOOTHING * (seconds - self.average.get()),
+        };
+        self.average.set(average);
+        self.measurements.set(self.measurements.get() + 1);
+    }
+}
+
+/// Order in which the children are checked, sorted by the measured cost of one kind of check.
+#[derive(Debug, Default)]
+struct CheckOrder {
+    order: RefCell<Vec<usize>>,
+    costs: Vec<MeasuredCost>,
+    checks: Cell<usize>,
+}
+
+impl CheckOrder {
+    fn push(&mut self) {
+        self.order.borrow_mut().push(self.costs.len());
+        self.costs.push(MeasuredCost::default());
+    }
+
+    /// Runs the check for the children from cheap to expensive and stops at the first collision.
+    /// Every executed check is timed. Children without measurement count as free, so they are measured first.
+    fn any(&self, check: impl Fn(usize) -> bool) -> bool {
+        self.checks.set(self.checks.get() + 1);
+        if self.checks.get().is_multiple_of(REORDER_INTERVAL) {
+            self.order.borrow_mut().sort_by(|a, b| {
+                self.costs[*a]
+                    .average
+                    .get()
+                    .total_cmp(&self.costs[*b].average.get())
+            });
+        }
+        self.order.borrow().iter().any(|child| {
+            let start_time: Instant = Instant::now();
+            let colliding: bool = check(*child);
+            self.costs[*child].add(start_time.elapsed().as_secs_f64());
+            colliding
+        })
+    }
+}
+
+/// Combines any number of collision checkers, e.g. a static map, a live occupancy grid and keep-out zones.
+/// A node or an edge is in collision, if it collides in any of the collision checkers.
+///
+/// The time of every check is measured. The collision checkers are sorted by their average cost regularly, separately for nodes and edges.
+/// So cheap checks run first and expensive ones are skipped, if a cheap one already found a collision.
+pub struct CompoundCollisionChecker<const N: usize = 2> {
+    collision_checkers: Vec<Box<dyn CollisionChecker<N>>>,
+    nodes: CheckOrder,
+    edges: CheckOrder,
+}
+
+impl<const N: usize> Default for CompoundCollisionChecker<N> {
+    fn default() -> Self {
+        CompoundCollisionChecker::new(Vec::new())
+    }
+}
+
+impl<const N: usize> CompoundCollisionChecker<N> {
+    pub fn new(collision_checkers: Vec<Box<dyn CollisionChecker<N>>>) -> Self {
+        let mut compound: CompoundCollisionChecker<N> = CompoundCollisionChecker {
+            collision_checkers: Vec::new(),
+            nodes: CheckOrder::default(),
+            edges: CheckOrder::default(),
+        };
+        for collision_checker in collision_checkers {
+            compound.add_collision_checker(collision_checker);
+        }
+        compound
+    }
+
+    pub fn new_box(
+        collision_checkers: Vec<Box<dyn CollisionChecker<N>>>,
+    ) -> Box<dyn CollisionChecker<N>> {
+        Box::new(CompoundCollisionChecker::new(collision_checkers))
+    }
+
+    pub fn add_collision_checker(&mut self, collision_checker: Box<dyn CollisionChecker<N>>) {
+        self.collision_checkers.push(collision_checker);
+        self.nodes.push();
+        self.edges.push();
+    }
+
+    /// Returns the number of combined collision checkers.
+    pub fn len(&self) -> usize {
+        self.collision_checkers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.collision_checkers.is_empty()
+    }
+}
+
+impl<const N: usize> CollisionChecker<N> for CompoundCollisionChecker<N> {
+    /// Initializes every collision checker, even if one of them fails.
+    /// Return
+    ///     true: All collision checkers were initialized successfully
+    fn init(&self) -> bool {
+        let failures: usize = self
+            .collision_checkers
+            .iter()
+            .filter(|collision_checker| !collision_checker.init())
+            .count();
+        failures == 0
+    }
+
+    fn is_node_colliding(&self, node: &Point<N>) -> bool {
+        self.nodes
+            .any(|child| self.collision_checkers[child].is_node_colliding(node))
+    }
+
+    fn is_edge_colliding(&self, begin: &Point<N>, end: &Point<N>) -> bool {
+        self.edges
+            .any(|child| self.collision_checkers[child].is_edge_colliding(begin, end))
+    }
+
+    /// Returns the smallest clearance of all collision checkers.
+    /// - None: At least one collision checker does not know its clearance
+    fn clearance(&self, node: &Point<N>) -> Option<f64> {
+        self.collision_checkers
+            .iter()
+            .try_fold(f64::INFINITY, |clearance, collision_checker| {
+                Some(clearance.min(collision_checker.clearance(node)?))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision_checker::{NaiveCollisionChecker, PolygonCollisionChecker};
+    use geo::polygon;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    type Counter = Rc<Cell<usize>>;
+
+    /// Obstacle at x > limit. Counts its checks and can be slowed down.
+    struct TestCollisionChecker {
+        limit: f64,
+        delay: Duration,
+        checks: Counter,
+        inits: Counter,
+    }
+
+    impl TestCollisionChecker {
+        fn new_box(limit: f64, delay: Duration) -> (Box<dyn CollisionChecker>, Counter, Counter) {
+            let checks: Counter = Rc::new(Cell::new(0usize));
+            let inits: Counter = Rc::new(Cell::new(0usize));
+            let cc: Box<dyn CollisionChecker> = Box::new(TestCollisionChecker {
+                limit,
+                delay,
+                checks: Rc::clone(&checks),
+                inits: Rc::clone(&inits),
+            });
+            (cc, checks, inits)
+        }
+    }
+
+    impl CollisionChecker for TestCollisionChecker {
+        fn init(&self) -> bool {
+            self.inits.set(self.inits.get() + 1);
+            self.limit > 0f64
+        }
+
+        fn is_node_colliding(&self, node: &Point) -> bool {
+            self.checks.set(self.checks.get() + 1);
+            std::thread::sleep(self.delay);
+            node.get_x() > self.limit
+        }
+
+        fn is_edge_colliding(&self, begin: &Point, end: &Point) -> bool {
+            self.is_node_colliding(begin) || self.is_node_colliding(end)
+        }
+    }
+
+    #[test]
+    fn test_or_semantics() {
+        let cc: CompoundCollisionChecker = CompoundCollisionChecker::new(vec![
+            NaiveCollisionChecker::new_box(),
+            PolygonCollisionChecker::new_box(vec![polygon![
+                (x: 1., y: 1.),
+                (x: 2., y: 1.),
+                (x: 2., y: 2.),
+                (x: 1., y: 2.)
+            ]]),
+            TestCollisionChecker::new_box(5f64, Duration::ZERO).0,
+        ]);
+        assert_eq!(cc.len(), 3);
+        assert!(cc.is_node_colliding(&Point::new(1.5, 1.5)));
+        assert!(cc.is_node_colliding(&Point::new(6f64, 0f64)));
+        assert!(!cc.is_node_colliding(&Point::new(3f64, 0f64)));
+        assert!(cc.is_edge_colliding(&Point::new(0f64, 0f64), &Point::new(3f64, 3f64)));
+        assert!(!cc.is_edge_colliding(&Point::new(0f64, 0f64), &Point::new(3f64, 0f64)));
+    }
+
+    #[test]
+    fn test_empty() {
+        let cc: CompoundCollisionChecker = CompoundCollisionChecker::default();
+        assert!(cc.is_empty());
+        assert!(cc.init());
+        assert!(!cc.is_node_colliding(&Point::new(0f64, 0f64)));
+        assert_eq!(cc.clearance(&Point::new(0f64, 0f64)), Some(f64::INFINITY));
+    }
+
+    // Test that every child is initialized, even after one failed.
+    #[test]
+    fn test_init_propagated() {
+        let (failing, _, failing_inits) = TestCollisionChecker::new_box(0f64, Duration::ZERO);
+        let (working, _, working_inits) = TestCollisionChecker::new_box(1f64, Duration::ZERO);
+        let cc: CompoundCollisionChecker = CompoundCollisionChecker::new(vec![failing, working]);
+        assert!(!cc.init());
+        assert_eq!(failing_inits.get(), 1);
+        assert_eq!(working_inits.get(), 1);
+    }
+
+    // Test that the cheap checker runs first after the costs were measured, and the expensive one is skipped on collisions.
+    #[test]
+    fn test_cheap_checks_first() {
+        let (slow, slow_checks, _) = TestCollisionChecker::new_box(1f64, Duration::from_millis(1));
+        let (fast, fast_checks, _) = TestCollisionChecker::new_box(1f64, Duration::ZERO);
+        let cc: CompoundCollisionChecker = CompoundCollisionChecker::new(vec![slow, fast]);
+
+        for _ in 0..REORDER_INTERVAL {
+            cc.is_node_colliding(&Point::new(0f64, 0f64));
+        }
+        assert_eq!(*cc.nodes.order.borrow(), vec![1, 0]);
+        assert_eq!(*cc.edges.order.borrow(), vec![0, 1]);
+
+        slow_checks.set(0);
+        fast_checks.set(0);
+        for _ in 0..10 {
+            assert!(cc.is_node_colliding(&Point::new(2f64, 0f64)));
+        }
+        assert_eq!(fast_checks.get(), 10);
+        assert_eq!(slow_checks.get(), 0);
+    }
+
+    #[test]
+    fn test_clearance() {
+        let square = PolygonCollisionChecker::new_box(vec![polygon![
+            (x: 1., y: 1.),
+            (x: 2., y: 1.),
+            (x: 2., y: 2.),
+            (x: 1., y: 2.)
+        ]]);
+        let cc: CompoundCollisionChecker =
+            CompoundCollisionChecker::new(vec![NaiveCollisionChecker::new_box(), square]);
+        assert_eq!(cc.clearance(&Point::new(0f64, 1.5)), Some(1f64));
+
+        let mut cc: CompoundCollisionChecker = cc;
+        cc.add_collision_checker(TestCollisionChecker::new_box(5f64, Duration::ZERO).0);
+        assert_eq!(cc.clearance(&Point::new(0f64, 1.5)), None);
+    }
+}
@@ -0,0 +1,415 @@
+use crate::boundaries::Boundaries;
+use crate::collision_checker::{CollisionChecker, OccupancyGridCollisionChecker};
+use crate::error::PlanningError;
+use crate::space::Point;
+
+/// Squared distance used instead of infinity inside the distance transform, so no infinities are subtracted.
+const FAR: f64 = 1e20;
+
+/// Signed distance field (SDF) on a grid. Every cell holds the distance from its center to the nearest obstacle boundary.
+/// It is positive in free cells and negative in occupied cells.
+/// The field is precomputed once with the Euclidean distance transform of Felzenszwalb and Huttenlocher.
+///
+/// Clearance queries interpolate bilinearly between the cell centers. Nodes and edges are checked against the cells.
+/// Everything outside of the grid is in collision.
+///
+/// # Source / Credits
+/// Felzenszwalb, P. F., Huttenlocher, D. P. (2012), "Distance Transforms of Sampled Functions"
+#[derive(Debug, Clone)]
+pub struct DistanceField {
+    width: usize,
+    height: usize,
+    resolution: f64,
+    origin: Point,
+    distances: Vec<f64>,
+}
+
+impl DistanceField {
+    /// Samples the collision checker at the center of every cell within the boundaries and builds the field from it.
+    /// Works with every collision checker, like polygon maps or footprints.
+    ///
+    /// # Errors
+    /// Returns `PlanningError::InvalidMap`, if the resolution is not positive or the boundaries do not contain a single cell.
+    pub fn from_collision_checker(
+        collision_checker: &dyn CollisionChecker,
+        boundaries: &Boundaries,
+        resolution: f64,
+    ) -> Result<Self, PlanningError> {
+        if resolution <= 0f64 || !resolution.is_finite() {
+            return Err(PlanningError::InvalidMap {
+                message: format!("resolution {resolution} must be positive"),
+            });
+        }
+        // The tolerance avoids an additional cell, if the extent is a multiple of the resolution with rounding errors.
+        let cells = |extent: f64| (extent / resolution - 1e-9).ceil();
+        let width: f64 = cells(boundaries.get_x_upper() - boundaries.get_x_lower());
+        let height: f64 = cells(boundaries.get_y_upper() - boundaries.get_y_lower());
+        if !(width >= 1f64 && height >= 1f64) {
+            return Err(PlanningError::InvalidMap {
+                message: "boundaries do not contain a single cell".to_string(),
+            });
+        }
+        let (width, height) = (width as usize, height as usize);
+        let origin: Point = Point::new(boundaries.get_x_lower(), boundaries.get_y_lower());
+
+        let mut occupied: Vec<bool> = Vec::with_capacity(width * height);
+        for row in 0..height {
+            for column in 0..width {
+                let center: Point = Point::new(
+                    origin.get_x() + (column as f64 + 0.5) * resolution,
+                    origin.get_y() + (row as f64 + 0.5) * resolution,
+                );
+                occupied.push(collision_checker.is_node_colliding(&center));
+            }
+        }
+        Ok(DistanceField::from_cells(
+            width, height, resolution, origin, &occupied,
+        ))
+    }
+
+    /// Builds the field with the same cells as the occupancy grid.
+    pub fn from_occupancy_grid(grid: &OccupancyGridCollisionChecker) -> Self {
+        DistanceField::from_collision_checker(grid, &grid.get_boundaries(), grid.get_resolution())
+            .expect("an occupancy grid has a positive resolution and at least one cell")
+    }
+
+    /// Returns the boundaries that cover the whole field.
+    pub fn get_boundaries(&self) -> Boundaries {
+        Boundaries::new(
+            self.origin.get_x(),
+            self.origin.get_x() + self.width as f64 * self.resolution,
+            self.origin.get_y(),
+            self.origin.get_y() + self.height as f64 * self.resolution,
+        )
+    }
+
+    pub fn get_resolution(&self) -> f64 {
+        self.resolution
+    }
+
+    /// Returns the signed distance at the point, interpolated bilinearly between the four closest cell centers.
+    /// - None: The point is outside of the field
+    pub fn get_distance(&self, point: &Point) -> Option<f64> {
+        let x: f64 = (point.get_x() - self.origin.get_x()) / self.resolution;
+        let y: f64 = (point.get_y() - self.origin.get_y()) / self.resolution;
+        if !(x >= 0f64 && y >= 0f64 && x <= self.width as f64 && y <= self.height as f64) {
+            return None;
+        }
+        // Position relative to the cell centers. Clamped, so the border cells are extended to the edge of the field.
+        let x: f64 = (x - 0.5).clamp(0f64, (self.width - 1) as f64);
+        let y: f64 = (y - 0.5).clamp(0f64, (self.height - 1) as f64);
+        let column: usize = (x.floor() as usize).min(self.width.saturating_sub(2));
+        let row: usize = (y.floor() as usize).min(self.height.saturating_sub(2));
+        let next_column: usize = (column + 1).min(self.width - 1);
+        let next_row: usize = (row + 1).min(self.height - 1);
+        let (tx, ty) = (x - column as f64, y - row as f64);
+
+        let lower_left: f64 = self.distance_of_cell(column, row);
+        if !lower_left.is_finite() {
+            // Either all cells are free or all are occupied, so the field is constant.
+            return Some(lower_left);
+        }
+        let lower: f64 = lower_left * (1f64 - tx) + self.distance_of_cell(next_column, row) * tx;
+        let upper: f64 = self.distance_of_cell(column, next_row) * (1f64 - tx)
+            + self.distance_of_cell(next_column, next_row) * tx;
+        Some(lower * (1f64 - ty) + upper * ty)
+    }
+
+    /// Builds the field from occupied cells, row by row from the bottom.
+    /// A free cell holds the distance to the nearest occupied cell center minus half a cell, an occupied cell the negative distance to the nearest free cell center plus half a cell.
+    /// So the boundary between two neighboring cells is at zero.
+    fn from_cells(
+        width: usize,
+        height: usize,
+        resolution: f64,
+        origin: Point,
+        occupied: &[bool],
+    ) -> Self {
+        let free: Vec<bool> = occupied.iter().map(|occupied| !occupied).collect();
+        let to_occupied: Vec<f64> = DistanceField::distance_transform(width, height, occupied);
+        let to_free: Vec<f64> = DistanceField::distance_transform(width, height, &free);
+        let distances: Vec<f64> = occupied
+            .iter()
+            .zip(to_occupied.iter().zip(to_free.iter()))
+            .map(|(occupied, (to_occupied, to_free))| {
+                if *occupied {
+                    -(to_free - 0.5) * resolution
+                } else {
+                    (to_occupied - 0.5) * resolution
+                }
+            })
+            .collect();
+        DistanceField {
+            width,
+            height,
+            resolution,
+            origin,
+            distances,
+        }
+    }
+
+    /// Returns the distance of every cell to the nearest target cell in cells. Infinite, if there is no target.
+    /// Transforms the rows first and the columns afterwards, which gives the exact Euclidean distance.
+    fn distance_transform(width: usize, height: usize, targets: &[bool]) -> Vec<f64> {
+        let mut squared: Vec<f64> = targets
+            .iter()
+            .map(|target| if *target { 0f64 } else { FAR })
+            .collect();
+        for row in squared.chunks_mut(width) {
+            let transformed: Vec<f64> = DistanceField::distance_transform_1d(row);
+            row.copy_from_slice(&transformed);
+        }
+        for column in 0..width {
+            let values: Vec<f64> = (0..height)
+                .map(|row| squared[row * width + column])
+                .collect();
+            for (row, value) in DistanceField::distance_transform_1d(&values)
+                .into_iter()
+                .enumerate()
+            {
+                squared[row * width + column] = value;
+            }
+        }
+        squared
+            .into_iter()
+            .map(|value| {
+                if value >= FAR / 2f64 {
+                    f64::INFINITY
+                } else {
+                    value.sqrt()
+                }
+            })
+            .collect()
+    }
+
+    /// Squared distance transform of a sampled function in one dimension, by the lower envelope of parabolas.
+    /// Returns for every index q the minimum of (q - p)^2 + f(p) over all indices p.
+    fn distance_transform_1d(f: &[f64]) -> Vec<f64> {
+        let n: usize = f.len();
+        let parabola = |p: usize| f[p] + (p * p) as f64;
+        // Vertices of the parabolas in the lower envelope and the borders between them.
+        let mut vertices: Vec<usize> = vec![0usize; n];
+        let mut borders: Vec<f64> = vec![0f64; n + 1];
+        let mut k: usize = 0;
+        borders[0] = f64::NEG_INFINITY;
+        borders[1] = f64::INFINITY;
+        for q in 1..n {
+            let mut border: f64;
+            loop {
+                let p: usize = vertices[k];
+                border = (parabola(q) - parabola(p)) / (2 * (q - p)) as f64;
+                if border > borders[k] || k == 0 {
+                    break;
+                }
+                k -= 1;
+            }
+            k += 1;
+            vertices[k] = q;
+            borders[k] = border;
+            borders[k + 1] = f64::INFINITY;
+        }
+
+        let mut k: usize = 0;
+        (0..n)
+            .map(|q| {
+                while borders[k + 1] < q as f64 {
+                    k += 1;
+                }
+                let p: usize = vertices[k];
+                let offset: f64 = q as f64 - p as f64;
+                offset * offset + f[p]
+            })
+            .collect()
+    }
+
+    fn cell_of(&self, point: &Point) -> (i64, i64) {
+        (
+            ((point.get_x() - self.origin.get_x()) / self.resolution).floor() as i64,
+            ((point.get_y() - self.origin.get_y()) / self.resolution).floor() as i64,
+        )
+    }
+
+    fn distance_of_cell(&self, column: usize, row: usize) -> f64 {
+        self.distances[row * self.width + column]
+    }
+
+    fn is_cell_colliding(&self, column: i64, row: i64) -> bool {
+        if column < 0 || row < 0 || column >= self.width as i64 || row >= self.height as i64 {
+            return true;
+        }
+        self.distance_of_cell(column as usize, row as usize) < 0f64
+    }
+}
+
+impl CollisionChecker for DistanceField {
+    /// Does nothing
+    /// Return
+    ///     true: always
+    fn init(&self) -> bool {
+        true
+    }
+
+    /// Checks states along the edge, at most half a cell apart.
+    /// Far from obstacles the clearance is used to skip states. The skip keeps a margin of two cells, so no occupied cell is skipped.
+    fn is_edge_colliding(&self, begin: &Point, end: &Point) -> bool {
+        let length: f64 = begin.euclidean_distance(end);
+        let mut travelled: f64 = 0f64;
+        loop {
+            let t: f64 = if length > 0f64 {
+                travelled / length
+            } else {
+                1f64
+            };
+            let state: Point = begin.interpolate(end, t.min(1f64));
+            if self.is_node_colliding(&state) {
+                return true;
+            }
+            if t >= 1f64 {
+                return false;
+            }
+            let clearance: f64 = self.get_distance(&state).unwrap_or(0f64);
+            travelled += f64::max(self.resolution / 2f64, clearance - 2f64 * self.resolution);
+        }
+    }
+
+    /// Looks up the cell that contains the node.
+    fn is_node_colliding(&self, node: &Point) -> bool {
+        let (column, row) = self.cell_of(node);
+        self.is_cell_colliding(column, row)
+    }
+
+    /// Returns the interpolated signed distance. None outside of the field.
+    fn clearance(&self, node: &Point) -> Option<f64> {
+        self.get_distance(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision_checker::PolygonCollisionChecker;
+    use geo::polygon;
+
+    /// Field of 10 x 10 cells with a size of 1. Only the cell (4, 4) is occupied.
+    fn single_obstacle() -> DistanceField {
+        let mut occupied: Vec<bool> = vec![false; 100];
+        occupied[44] = true;
+        DistanceField::from_cells(10, 10, 1f64, Point::new(0f64, 0f64), &occupied)
+    }
+
+    #[test]
+    fn test_distance_transform_1d() {
+        let f: Vec<f64> = vec![FAR, 0f64, FAR, FAR, FAR, 0f64, FAR];
+        assert_eq!(
+            DistanceField::distance_transform_1d(&f),
+            vec![1f64, 0f64, 1f64, 4f64, 1f64, 0f64, 1f64]
+        );
+        assert_eq!(DistanceField::distance_transform_1d(&[FAR]), vec![FAR]);
+    }
+
+    // Test that the transform is the exact Euclidean distance, compared with brute force.
+    #[test]
+    fn test_distance_transform() {
+        let (width, height) = (7usize, 5usize);
+        let targets: Vec<bool> = (0..width * height).map(|index| index % 11 == 3).collect();
+        let distances: Vec<f64> = DistanceField::distance_transform(width, height, &targets);
+        for row in 0..height {
+            for column in 0..width {
+                let expected: f64 = targets
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, target)| **target)
+                    .map(|(index, _)| {
+                        let dx: f64 = (index % width) as f64 - column as f64;
+                        let dy: f64 = (index / width) as f64 - row as f64;
+                        dx.hypot(dy)
+                    })
+                    .fold(f64::INFINITY, f64::min);
+                assert!((distances[row * width + column] - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_distances() {
+        let field: DistanceField = single_obstacle();
+        assert_eq!(field.get_distance(&Point::new(4.5, 4.5)), Some(-0.5));
+        assert_eq!(field.get_distance(&Point::new(7.5, 4.5)), Some(2.5));
+        assert_eq!(field.get_distance(&Point::new(4.5, 0.5)), Some(3.5));
+        // Boundary between the obstacle and its right neighbor.
+        assert_eq!(field.get_distance(&Point::new(5f64, 4.5)), Some(0f64));
+        assert_eq!(field.get_distance(&Point::new(10.5, 4.5)), None);
+        assert_eq!(field.clearance(&Point::new(-0.5, 4.5)), None);
+    }
+
+    #[test]
+    fn test_empty_field() {
+        let field: DistanceField =
+            DistanceField::from_cells(3, 2, 1f64, Point::new(0f64, 0f64), &[false; 6]);
+        assert_eq!(
+            field.get_distance(&Point::new(1f64, 1f64)),
+            Some(f64::INFINITY)
+        );
+        assert!(!field.is_edge_colliding(&Point::new(0.1, 0.1), &Point::new(2.9, 1.9)));
+    }
+
+    #[test]
+    fn test_collisions() {
+        let field: DistanceField = single_obstacle();
+        assert!(field.is_node_colliding(&Point::new(4.5, 4.5)));
+        assert!(!field.is_node_colliding(&Point::new(5.5, 4.5)));
+        assert!(field.is_node_colliding(&Point::new(-0.5, 4.5)));
+        assert!(field.is_edge_colliding(&Point::new(0.5, 4.2), &Point::new(9.5, 4.7)));
+        assert!(!field.is_edge_colliding(&Point::new(0.5, 5.5), &Point::new(9.5, 5.5)));
+        assert!(field.is_edge_colliding(&Point::new(0.5, 5.5), &Point::new(10.5, 5.5)));
+    }
+
+    #[test]
+    fn test_from_collision_checker() {
+        let cc: PolygonCollisionChecker = PolygonCollisionChecker::new(vec![polygon![
+            (x: 1., y: 1.),
+            (x: 2., y: 1.),
+            (x: 2., y: 2.),
+            (x: 1., y: 2.)
+        ]]);
+        let field: DistanceField = DistanceField::from_collision_checker(
+            &cc,
+            &Boundaries::new(0f64, 4f64, 0f64, 3f64),
+            0.1,
+        )
+        .unwrap();
+        let boundaries: Boundaries = field.get_boundaries();
+        assert!((boundaries.get_x_upper() - 4f64).abs() < 1e-9);
+        assert!((boundaries.get_y_upper() - 3f64).abs() < 1e-9);
+        for point in [
+            Point::new(3f64, 1.5),
+            Point::new(0.25, 0.25),
+            Point::new(1.5, 1.6),
+        ] {
+            let exact: f64 = cc.clearance(&point).unwrap();
+            let approximated: f64 = field.clearance(&point).unwrap();
+            assert!((exact - approximated).abs() < 0.1, "{exact} {approximated}");
+        }
+        assert!(matches!(
+            DistanceField::from_collision_checker(
+                &cc,
+                &Boundaries::new(0f64, 4f64, 0f64, 3f64),
+                0f64
+            ),
+            Err(PlanningError::InvalidMap { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_occupancy_grid() {
+        let mut occupancy: Vec<f64> = vec![0f64; 12];
+        occupancy[5] = 1f64;
+        let grid: OccupancyGridCollisionChecker =
+            OccupancyGridCollisionChecker::new(4, 3, 0.5, Point::new(1f64, 1f64), occupancy)
+                .unwrap();
+        let field: DistanceField = DistanceField::from_occupancy_grid(&grid);
+        assert_eq!(field.get_resolution(), 0.5);
+        assert!(field.is_node_colliding(&Point::new(1.75, 1.75)));
+        assert_eq!(field.clearance(&Point::new(2.75, 1.75)), Some(0.75));
+    }
+}
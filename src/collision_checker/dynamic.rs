@@ -0,0 +1,336 @@
+use std::cmp::Ordering;
+
+use geo::{Intersects, Line, Polygon};
+
+use crate::collision_checker::{CollisionChecker, NaiveCollisionChecker};
+use crate::error::PlanningError;
+use crate::space::{Point, TimedPoint};
+
+/// Largest rotation of an obstacle, that is treated as a single linear piece of motion, in radians.
+const ROTATION_STEP: f64 = 0.1;
+
+/// Pose of a dynamic obstacle at a point in time. The heading is in radians, counterclockwise from the x-axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe {
+    pub time: f64,
+    pub position: Point,
+    pub heading: f64,
+}
+
+impl Keyframe {
+    pub fn new(time: f64, position: Point, heading: f64) -> Self {
+        Keyframe {
+            time,
+            position,
+            heading,
+        }
+    }
+}
+
+/// Obstacle that moves along a known trajectory.
+/// The shape is given relative to the position of the obstacle and is rotated by its heading.
+/// Between two keyframes position and heading are interpolated linearly.
+/// Before the first and after the last keyframe the obstacle stands still.
+#[derive(Debug, Clone)]
+pub struct DynamicObstacle {
+    shape: Polygon<f64>,
+    keyframes: Vec<Keyframe>,
+}
+
+impl DynamicObstacle {
+    /// # Errors
+    /// Returns `PlanningError::InvalidTrajectory`, if there is no keyframe or the keyframes are not ordered by time.
+    pub fn new(shape: Polygon<f64>, keyframes: Vec<Keyframe>) -> Result<Self, PlanningError> {
+        if keyframes.is_empty() {
+            return Err(PlanningError::InvalidTrajectory {
+                message: "at least one keyframe is needed".to_string(),
+            });
+        }
+        if keyframes
+            .windows(2)
+            .any(|pair| pair[0].time.partial_cmp(&pair[1].time) != Some(Ordering::Less))
+        {
+            return Err(PlanningError::InvalidTrajectory {
+                message: "keyframes must be strictly ordered by time".to_string(),
+            });
+        }
+        Ok(DynamicObstacle { shape, keyframes })
+    }
+
+    /// Obstacle that does not move.
+    pub fn new_static(shape: Polygon<f64>, position: Point, heading: f64) -> Self {
+        DynamicObstacle {
+            shape,
+            keyframes: vec![Keyframe::new(0f64, position, heading)],
+        }
+    }
+
+    pub fn get_keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    /// Returns position and heading of the obstacle at the given time.
+    pub fn get_pose(&self, time: f64) -> (Point, f64) {
+        let next: usize = self
+            .keyframes
+            .partition_point(|keyframe| keyframe.time <= time);
+        if next == 0 {
+            let first: &Keyframe = &self.keyframes[0];
+            return (first.position, first.heading);
+        }
+        let previous: &Keyframe = &self.keyframes[next - 1];
+        let Some(next) = self.keyframes.get(next) else {
+            return (previous.position, previous.heading);
+        };
+        let t: f64 = (time - previous.time) / (next.time - previous.time);
+        (
+            previous.position.interpolate(&next.position, t),
+            previous.heading + (next.heading - previous.heading) * t,
+        )
+    }
+
+    /// Returns true, if the point is inside the obstacle at the given time.
+    pub fn is_colliding(&self, node: &TimedPoint) -> bool {
+        let local: Point = self.to_local(&node.get_point(), node.get_time());
+        self.shape
+            .intersects(&geo::Point::new(local.get_x(), local.get_y()))
+    }
+
+    /// Returns true, if a point moving linearly from begin to end hits the obstacle.
+    /// The time interval is split at every keyframe, so obstacle and point move linearly on each piece.
+    /// Seen from the obstacle the point moves on a straight line then, which is checked exactly against the shape.
+    /// While the obstacle rotates, the pieces are shortened to `ROTATION_STEP` and the motion seen from the obstacle is approximated by a line.
+    pub fn is_motion_colliding(&self, begin: &TimedPoint, end: &TimedPoint) -> bool {
+        let (begin_time, end_time) = (begin.get_time(), end.get_time());
+        let mut times: Vec<f64> = vec![begin_time];
+        times.extend(
+            self.keyframes
+                .iter()
+                .map(|keyframe| keyframe.time)
+                .filter(|time| *time > begin_time && *time < end_time),
+        );
+        times.push(end_time);
+
+        let position_at = |time: f64| -> Point {
+            if end_time > begin_time {
+                let t: f64 = (time - begin_time) / (end_time - begin_time);
+                begin.get_point().interpolate(&end.get_point(), t)
+            } else {
+                end.get_point()
+            }
+        };
+        times.windows(2).any(|interval| {
+            let rotation: f64 = (self.get_pose(interval[1]).1 - self.get_pose(interval[0]).1).abs();
+            let pieces: usize = ((rotation / ROTATION_STEP).ceil() as usize).max(1);
+            (0..pieces).any(|piece| {
+                let lower: f64 =
+                    interval[0] + (interval[1] - interval[0]) * piece as f64 / pieces as f64;
+                let upper: f64 =
+                    interval[0] + (interval[1] - interval[0]) * (piece + 1) as f64 / pieces as f64;
+                let a: Point = self.to_local(&position_at(lower), lower);
+                let b: Point = self.to_local(&position_at(upper), upper);
+                self.shape
+                    .intersects(&Line::new((a.get_x(), a.get_y()), (b.get_x(), b.get_y())))
+            })
+        })
+    }
+
+    /// Transforms the point into the frame of the obstacle at the given time.
+    fn to_local(&self, point: &Point, time: f64) -> Point {
+        let (position, heading) = self.get_pose(time);
+        let dx: f64 = point.get_x() - position.get_x();
+        let dy: f64 = point.get_y() - position.get_y();
+        let (sin, cos) = heading.sin_cos();
+        Point::new(cos * dx + sin * dy, -sin * dx + cos * dy)
+    }
+}
+
+/// Collision checker for a point robot among static obstacles and obstacles with known trajectories.
+/// Nodes and edges with time are checked with `is_timed_node_colliding` and `is_timed_edge_colliding`.
+/// A robot with a footprint can be handled by inflating the shapes of the obstacles, e.g. with `PolygonCollisionChecker::inflate`.
+///
+/// The plain `CollisionChecker` methods have no time and only check the static obstacles.
+pub struct DynamicCollisionChecker {
+    static_collision_checker: Box<dyn CollisionChecker>,
+    obstacles: Vec<DynamicObstacle>,
+}
+
+impl Default for DynamicCollisionChecker {
+    fn default() -> Self {
+        DynamicCollisionChecker::new(NaiveCollisionChecker::new_box(), Vec::new())
+    }
+}
+
+impl DynamicCollisionChecker {
+    pub fn new(
+        static_collision_checker: Box<dyn CollisionChecker>,
+        obstacles: Vec<DynamicObstacle>,
+    ) -> Self {
+        DynamicCollisionChecker {
+            static_collision_checker,
+            obstacles,
+        }
+    }
+
+    /// Replaces the collision checker for the static obstacles.
+    pub fn set_static_collision_checker(&mut self, collision_checker: Box<dyn CollisionChecker>) {
+        self.static_collision_checker = collision_checker;
+    }
+
+    pub fn add_obstacle(&mut self, obstacle: DynamicObstacle) {
+        self.obstacles.push(obstacle);
+    }
+
+    pub fn get_obstacles(&self) -> &[DynamicObstacle] {
+        &self.obstacles
+    }
+
+    /// Checks the node against the static obstacles and the dynamic obstacles at the time of the node.
+    pub fn is_timed_node_colliding(&self, node: &TimedPoint) -> bool {
+        self.static_collision_checker
+            .is_node_colliding(&node.get_point())
+            || self
+                .obstacles
+                .iter()
+                .any(|obstacle| obstacle.is_colliding(node))
+    }
+
+    /// Checks the edge against the static obstacles and the motion of the dynamic obstacles between the times of begin and end.
+    pub fn is_timed_edge_colliding(&self, begin: &TimedPoint, end: &TimedPoint) -> bool {
+        self.static_collision_checker
+            .is_edge_colliding(&begin.get_point(), &end.get_point())
+            || self
+                .obstacles
+                .iter()
+                .any(|obstacle| obstacle.is_motion_colliding(begin, end))
+    }
+}
+
+impl CollisionChecker for DynamicCollisionChecker {
+    /// Initializes the collision checker of the static obstacles.
+    fn init(&self) -> bool {
+        self.static_collision_checker.init()
+    }
+
+    /// Only checks the static obstacles.
+    fn is_node_colliding(&self, node: &Point) -> bool {
+        self.static_collision_checker.is_node_colliding(node)
+    }
+
+    /// Only checks the static obstacles.
+    fn is_edge_colliding(&self, begin: &Point, end: &Point) -> bool {
+        self.static_collision_checker.is_edge_colliding(begin, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::polygon;
+    use std::f64::consts::PI;
+
+    /// Square with an edge length of 1 around its position.
+    fn square() -> Polygon<f64> {
+        polygon![(x: -0.5, y: -0.5), (x: 0.5, y: -0.5), (x: 0.5, y: 0.5), (x: -0.5, y: 0.5)]
+    }
+
+    /// Square that drives along the x-axis from x = -5 at t = 0 to x = 5 at t = 10.
+    fn crossing_square() -> DynamicObstacle {
+        DynamicObstacle::new(
+            square(),
+            vec![
+                Keyframe::new(0f64, Point::new(-5f64, 0f64), 0f64),
+                Keyframe::new(10f64, Point::new(5f64, 0f64), 0f64),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn timed(x: f64, y: f64, time: f64) -> TimedPoint {
+        TimedPoint::new(Point::new(x, y), time)
+    }
+
+    #[test]
+    fn test_invalid_trajectory() {
+        assert!(matches!(
+            DynamicObstacle::new(square(), Vec::new()),
+            Err(PlanningError::InvalidTrajectory { .. })
+        ));
+        let keyframe: Keyframe = Keyframe::new(1f64, Point::new(0f64, 0f64), 0f64);
+        assert!(matches!(
+            DynamicObstacle::new(square(), vec![keyframe, keyframe]),
+            Err(PlanningError::InvalidTrajectory { .. })
+        ));
+    }
+
+    #[test]
+    fn test_get_pose() {
+        let obstacle: DynamicObstacle = DynamicObstacle::new(
+            square(),
+            vec![
+                Keyframe::new(1f64, Point::new(0f64, 0f64), 0f64),
+                Keyframe::new(3f64, Point::new(2f64, 4f64), PI),
+            ],
+        )
+        .unwrap();
+        assert_eq!(obstacle.get_pose(0f64), (Point::new(0f64, 0f64), 0f64));
+        assert_eq!(obstacle.get_pose(2f64), (Point::new(1f64, 2f64), PI / 2f64));
+        assert_eq!(obstacle.get_pose(5f64), (Point::new(2f64, 4f64), PI));
+    }
+
+    #[test]
+    fn test_timed_node() {
+        let cc: DynamicCollisionChecker =
+            DynamicCollisionChecker::new(NaiveCollisionChecker::new_box(), vec![crossing_square()]);
+        assert!(cc.is_timed_node_colliding(&timed(0f64, 0f64, 5f64)));
+        assert!(!cc.is_timed_node_colliding(&timed(0f64, 0f64, 2f64)));
+        assert!(cc.is_timed_node_colliding(&timed(5f64, 0.2, 20f64)));
+        assert!(!cc.is_node_colliding(&Point::new(0f64, 0f64)));
+    }
+
+    // Test that an edge is only in collision, if robot and obstacle are at the same place at the same time.
+    #[test]
+    fn test_timed_edge() {
+        let cc: DynamicCollisionChecker =
+            DynamicCollisionChecker::new(NaiveCollisionChecker::new_box(), vec![crossing_square()]);
+        assert!(cc.is_timed_edge_colliding(&timed(0f64, -2f64, 4f64), &timed(0f64, 2f64, 6f64)));
+        assert!(!cc.is_timed_edge_colliding(&timed(0f64, -2f64, 0f64), &timed(0f64, 2f64, 2f64)));
+        assert!(!cc.is_timed_edge_colliding(&timed(0f64, -2f64, 8f64), &timed(0f64, 2f64, 10f64)));
+        // Driving next to the obstacle with the same speed.
+        assert!(!cc.is_timed_edge_colliding(&timed(-5f64, 1f64, 0f64), &timed(5f64, 1f64, 10f64)));
+        // Waiting in the path of the obstacle, both ends are free.
+        assert!(cc.is_timed_edge_colliding(&timed(0f64, 0f64, 0f64), &timed(0f64, 0f64, 10f64)));
+    }
+
+    // Test that a rotating bar hits a robot between two keyframes, although both ends of the edge are free.
+    #[test]
+    fn test_rotating_obstacle() {
+        let bar: DynamicObstacle = DynamicObstacle::new(
+            polygon![(x: -2., y: -0.1), (x: 2., y: -0.1), (x: 2., y: 0.1), (x: -2., y: 0.1)],
+            vec![
+                Keyframe::new(0f64, Point::new(0f64, 0f64), PI / 2f64),
+                Keyframe::new(1f64, Point::new(0f64, 0f64), 3f64 * PI / 2f64),
+            ],
+        )
+        .unwrap();
+        let begin: TimedPoint = timed(1.5, 0f64, 0f64);
+        let end: TimedPoint = timed(1.5, 0f64, 1f64);
+        assert!(!bar.is_colliding(&begin));
+        assert!(!bar.is_colliding(&end));
+        assert!(bar.is_motion_colliding(&begin, &end));
+    }
+
+    #[test]
+    fn test_static_obstacles() {
+        let cc: DynamicCollisionChecker = DynamicCollisionChecker::new(
+            NaiveCollisionChecker::new_box(),
+            vec![DynamicObstacle::new_static(
+                square(),
+                Point::new(2f64, 2f64),
+                PI / 4f64,
+            )],
+        );
+        assert!(cc.is_timed_node_colliding(&timed(2f64, 2.6, 3f64)));
+        assert!(!cc.is_timed_node_colliding(&timed(2.6, 2.6, 3f64)));
+    }
+}
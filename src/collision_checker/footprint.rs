@@ -0,0 +1,395 @@
+use geo::{ConvexHull, Coord, MultiPoint, Polygon, Rotate, Translate};
+
+use crate::collision_checker::{CollisionChecker, PolygonCollisionChecker};
+use crate::space::Point;
+use crate::state_space::angle_difference;
+
+/// Largest rotation of the robot between two poses, whose convex hull is part of a swept area in SE(2).
+/// The hull misses at most `circumscribed_radius * (1 - cos(MAX_ROTATION_STEP / 2))` of the turning robot, i.e. about 0.03 %.
+const MAX_ROTATION_STEP: f64 = 0.05;
+
+/// Shape of the robot.
+///
+/// - Point: The robot has no extent
+/// - Disc: Circular robot with the given radius around its position
+/// - Polygon: Robot shape relative to its position. The robot looks along the positive x-axis.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Footprint {
+    Point,
+    Disc { radius: f64 },
+    Polygon { polygon: Polygon<f64> },
+}
+
+impl Footprint {
+    /// Returns the radius of the smallest disc around the position that contains the whole robot, in any orientation.
+    pub fn circumscribed_radius(&self) -> f64 {
+        match self {
+            Footprint::Point => 0f64,
+            Footprint::Disc { radius } => *radius,
+            Footprint::Polygon { polygon } => polygon
+                .exterior()
+                .coords()
+                .map(|coord| coord.x.hypot(coord.y))
+                .fold(0f64, f64::max),
+        }
+    }
+
+    /// Returns the area covered by a polygonal robot, which drives straight from begin to end and looks along the edge.
+    /// The area is the convex hull of the robot at both ends. It is exact for convex robots and conservative otherwise.
+    ///
+    /// Returns None for point and disc robots, which do not need a swept area.
+    pub fn swept(&self, begin: &Point, end: &Point) -> Option<Polygon<f64>> {
+        let Footprint::Polygon { polygon } = self else {
+            return None;
+        };
+        let heading: f64 = (end.get_y() - begin.get_y()).atan2(end.get_x() - begin.get_x());
+        let [begin, end] = [begin, end]
+            .map(|position| place(polygon, position.get_x(), position.get_y(), heading));
+        Some(convex_hull(&begin, &end))
+    }
+
+    /// Returns the polygon of the robot at the pose (x, y, theta). Theta is the heading of the robot.
+    ///
+    /// Returns None for point and disc robots, whose orientation does not matter.
+    pub fn placed(&self, pose: &Point<3>) -> Option<Polygon<f64>> {
+        let Footprint::Polygon { polygon } = self else {
+            return None;
+        };
+        let [x, y, theta] = pose.get_coordinates();
+        Some(place(polygon, x, y, theta))
+    }
+
+    /// Returns the areas covered by a polygonal robot, which moves straight from pose begin to pose end in (x, y, theta).
+    /// The robot turns the short way around, so the motion is split into steps of at most `MAX_ROTATION_STEP`, and each area is the convex hull of the robot at both ends of a step.
+    ///
+    /// Returns None for point and disc robots, which do not need a swept area.
+    pub fn swept_poses(&self, begin: &Point<3>, end: &Point<3>) -> Option<Vec<Polygon<f64>>> {
+        let Footprint::Polygon { .. } = self else {
+            return None;
+        };
+        let [x, y, heading]: [f64; 3] = begin.get_coordinates();
+        let [end_x, end_y, end_heading]: [f64; 3] = end.get_coordinates();
+        let rotation: f64 = angle_difference(heading, end_heading);
+        let steps: usize = ((rotation.abs() / MAX_ROTATION_STEP).ceil() as usize).max(1);
+        let poses: Vec<Polygon<f64>> = (0..=steps)
+            .map(|step| step as f64 / steps as f64)
+            .filter_map(|t| {
+                self.placed(&Point::from_coordinates([
+                    x + t * (end_x - x),
+                    y + t * (end_y - y),
+                    heading + t * rotation,
+                ]))
+            })
+            .collect();
+        Some(
+            poses
+                .windows(2)
+                .map(|pair| convex_hull(&pair[0], &pair[1]))
+                .collect(),
+        )
+    }
+}
+
+/// Rotates the robot shape by the heading and moves it to the position.
+fn place(polygon: &Polygon<f64>, x: f64, y: f64, heading: f64) -> Polygon<f64> {
+    polygon
+        .rotate_around_point(heading.to_degrees(), geo::Point::new(0f64, 0f64))
+        .translate(x, y)
+}
+
+/// Returns the convex hull of both polygons.
+fn convex_hull(a: &Polygon<f64>, b: &Polygon<f64>) -> Polygon<f64> {
+    let coords: Vec<Coord<f64>> = a
+        .exterior()
+        .coords()
+        .chain(b.exterior().coords())
+        .copied()
+        .collect();
+    MultiPoint::from(coords).convex_hull()
+}
+
+/// Collision checker for robots with a footprint among polygon obstacles.
+///
+/// - Disc robots: The obstacles are inflated by the radius once. Then the robot is checked like a point.
+/// - Polygonal robots: At nodes the robot may turn in place, so the circumscribed disc is checked.
+///   Along edges the robot looks in driving direction and the swept area is checked against the obstacles.
+///
+/// As `CollisionChecker<3>` it checks poses (x, y, theta), e.g. of `SE2StateSpace`.
+/// Then polygonal robots are rotated by theta at nodes, and the area swept between both poses is checked along edges.
+#[derive(Debug, Clone)]
+pub struct FootprintCollisionChecker {
+    footprint: Footprint,
+    obstacles: PolygonCollisionChecker,
+    inflated: PolygonCollisionChecker,
+}
+
+impl FootprintCollisionChecker {
+    pub fn new(footprint: Footprint, obstacles: PolygonCollisionChecker) -> Self {
+        let inflated: PolygonCollisionChecker = match footprint {
+            Footprint::Point => obstacles.clone(),
+            _ => obstacles.inflate(footprint.circumscribed_radius()),
+        };
+        FootprintCollisionChecker {
+            footprint,
+            obstacles,
+            inflated,
+        }
+    }
+
+    /// Returns the collision checker for positions (x, y) or poses (x, y, theta).
+    pub fn new_box<const N: usize>(
+        footprint: Footprint,
+        obstacles: PolygonCollisionChecker,
+    ) -> Box<dyn CollisionChecker<N>>
+    where
+        FootprintCollisionChecker: CollisionChecker<N>,
+    {
+        Box::new(FootprintCollisionChecker::new(footprint, obstacles))
+    }
+
+    pub fn get_footprint(&self) -> &Footprint {
+        &self.footprint
+    }
+}
+
+impl CollisionChecker for FootprintCollisionChecker {
+    /// Does nothing
+    /// Return
+    ///     true: always
+    fn init(&self) -> bool {
+        true
+    }
+
+    fn is_node_colliding(&self, node: &Point) -> bool {
+        self.inflated.is_node_colliding(node)
+    }
+
+    fn is_edge_colliding(&self, begin: &Point, end: &Point) -> bool {
+        match self.footprint.swept(begin, end) {
+            None => self.inflated.is_edge_colliding(begin, end),
+            Some(swept) => {
+                self.is_node_colliding(begin)
+                    || self.is_node_colliding(end)
+                    || self.obstacles.is_polygon_colliding(&swept)
+            }
+        }
+    }
+
+    /// Returns the clearance of the circumscribed disc. It is exact for disc robots and a lower bound for polygonal robots.
+    fn clearance(&self, node: &Point) -> Option<f64> {
+        self.obstacles
+            .clearance(node)
+            .map(|clearance| clearance - self.footprint.circumscribed_radius())
+    }
+}
+
+impl CollisionChecker<3> for FootprintCollisionChecker {
+    /// Does nothing
+    /// Return
+    ///     true: always
+    fn init(&self) -> bool {
+        true
+    }
+
+    fn is_node_colliding(&self, node: &Point<3>) -> bool {
+        match self.footprint.placed(node) {
+            None => self.inflated.is_node_colliding(&position(node)),
+            Some(placed) => self.obstacles.is_polygon_colliding(&placed),
+        }
+    }
+
+    fn is_edge_colliding(&self, begin: &Point<3>, end: &Point<3>) -> bool {
+        match self.footprint.swept_poses(begin, end) {
+            None => self
+                .inflated
+                .is_edge_colliding(&position(begin), &position(end)),
+            Some(swept) => swept
+                .iter()
+                .any(|area| self.obstacles.is_polygon_colliding(area)),
+        }
+    }
+
+    /// Returns the clearance of the circumscribed disc. It is exact for disc robots and a lower bound for polygonal robots.
+    fn clearance(&self, node: &Point<3>) -> Option<f64> {
+        CollisionChecker::<2>::clearance(self, &position(node))
+    }
+}
+
+/// Returns the position (x, y) of the pose (x, y, theta).
+fn position(pose: &Point<3>) -> Point {
+    let [x, y, _] = pose.get_coordinates();
+    Point::new(x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::polygon;
+
+    /// Single obstacle between (1, 1) and (2, 2).
+    fn obstacles() -> PolygonCollisionChecker {
+        PolygonCollisionChecker::new(vec![polygon![
+            (x: 1., y: 1.),
+            (x: 2., y: 1.),
+            (x: 2., y: 2.),
+            (x: 1., y: 2.)
+        ]])
+    }
+
+    /// Robot with a length of 1 and a width of 0.2.
+    fn rectangle() -> Footprint {
+        Footprint::Polygon {
+            polygon: polygon![(x: -0.5, y: -0.1), (x: 0.5, y: -0.1), (x: 0.5, y: 0.1), (x: -0.5, y: 0.1)],
+        }
+    }
+
+    #[test]
+    fn test_circumscribed_radius() {
+        assert_eq!(Footprint::Point.circumscribed_radius(), 0f64);
+        assert_eq!(Footprint::Disc { radius: 0.3 }.circumscribed_radius(), 0.3);
+        assert!((rectangle().circumscribed_radius() - 0.26f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_point_footprint() {
+        let cc: FootprintCollisionChecker =
+            FootprintCollisionChecker::new(Footprint::Point, obstacles());
+        assert!(!cc.is_node_colliding(&Point::new(0.9, 1.5)));
+        assert!(cc.is_node_colliding(&Point::new(1.1, 1.5)));
+        assert!(!cc.is_edge_colliding(&Point::new(0f64, 0.9), &Point::new(3f64, 0.9)));
+    }
+
+    #[test]
+    fn test_disc_footprint() {
+        let cc: FootprintCollisionChecker =
+            FootprintCollisionChecker::new(Footprint::Disc { radius: 0.5 }, obstacles());
+        assert!(cc.is_node_colliding(&Point::new(0.6, 1.5)));
+        assert!(!cc.is_node_colliding(&Point::new(0.4, 1.5)));
+        assert!(cc.is_node_colliding(&Point::new(2.3, 2.3)));
+        assert!(!cc.is_node_colliding(&Point::new(2.4, 2.4)));
+        // Passes the corner (2, 2) at a distance of about 0.35.
+        assert!(cc.is_edge_colliding(&Point::new(1.5, 3f64), &Point::new(3f64, 1.5)));
+        assert!(!cc.is_edge_colliding(&Point::new(0f64, 0.4), &Point::new(3f64, 0.4)));
+    }
+
+    // Test that a polygonal robot looks along the edge, so it fits through gaps that are too narrow for its circumscribed disc.
+    #[test]
+    fn test_polygon_footprint_edges() {
+        let cc: FootprintCollisionChecker =
+            FootprintCollisionChecker::new(rectangle(), obstacles());
+        let disc: FootprintCollisionChecker = FootprintCollisionChecker::new(
+            Footprint::Disc {
+                radius: rectangle().circumscribed_radius(),
+            },
+            obstacles(),
+        );
+
+        // Horizontal edge 0.4 below the obstacle. The robot is 0.1 wide to each side.
+        let begin: Point = Point::new(-1f64, 0.6);
+        let end: Point = Point::new(4f64, 0.6);
+        assert!(!cc.is_edge_colliding(&begin, &end));
+        assert!(disc.is_edge_colliding(&begin, &end));
+
+        // Vertical edge 0.4 left of the obstacle. Only a robot rotated by 90 degrees fits.
+        assert!(!cc.is_edge_colliding(&Point::new(0.6, -1f64), &Point::new(0.6, 4f64)));
+
+        // Horizontal edge 0.05 below the obstacle hits it with the side of the robot.
+        assert!(cc.is_edge_colliding(&Point::new(-1f64, 0.95), &Point::new(4f64, 0.95)));
+    }
+
+    // Test that the circumscribed disc is used at nodes, where the robot may turn in place.
+    #[test]
+    fn test_polygon_footprint_nodes() {
+        let cc: FootprintCollisionChecker =
+            FootprintCollisionChecker::new(rectangle(), obstacles());
+        assert!(cc.is_node_colliding(&Point::new(0.6, 1.5)));
+        assert!(!cc.is_node_colliding(&Point::new(0.4, 1.5)));
+        assert!(cc.is_edge_colliding(&Point::new(0.6, 1.5), &Point::new(0.6, 4f64)));
+    }
+
+    /// Two walls with a vertical corridor of width 0.4 between them, for 1 <= y <= 2.
+    fn corridor() -> PolygonCollisionChecker {
+        PolygonCollisionChecker::new(vec![
+            polygon![(x: 0., y: 1.), (x: 1.3, y: 1.), (x: 1.3, y: 2.), (x: 0., y: 2.)],
+            polygon![(x: 1.7, y: 1.), (x: 3., y: 1.), (x: 3., y: 2.), (x: 1.7, y: 2.)],
+        ])
+    }
+
+    // Test that only a rectangle that looks along the corridor fits through it.
+    #[test]
+    fn test_polygon_footprint_se2() {
+        use std::f64::consts::FRAC_PI_2;
+
+        let cc: FootprintCollisionChecker = FootprintCollisionChecker::new(rectangle(), corridor());
+        let pose = |x: f64, y: f64, theta: f64| Point::from_coordinates([x, y, theta]);
+
+        assert!(!cc.is_node_colliding(&pose(1.5, 1.5, FRAC_PI_2)));
+        assert!(!cc.is_node_colliding(&pose(1.5, 1.5, -FRAC_PI_2)));
+        assert!(cc.is_node_colliding(&pose(1.5, 1.5, 0f64)));
+        assert!(!cc.is_edge_colliding(&pose(1.5, 0.4, FRAC_PI_2), &pose(1.5, 2.6, FRAC_PI_2)));
+        assert!(cc.is_edge_colliding(&pose(1.5, 0.4, 0f64), &pose(1.5, 2.6, 0f64)));
+
+        // Turning below the corridor is free, but closer to it the corners sweep into the walls, although both poses are free.
+        assert!(!cc.is_edge_colliding(&pose(1.5, 0.3, 0f64), &pose(1.5, 0.3, FRAC_PI_2)));
+        let begin: Point<3> = pose(1.5, 0.6, 0f64);
+        let end: Point<3> = pose(1.5, 0.6, FRAC_PI_2);
+        assert!(!cc.is_node_colliding(&begin));
+        assert!(!cc.is_node_colliding(&end));
+        assert!(cc.is_edge_colliding(&begin, &end));
+
+        // Point and disc robots ignore the heading.
+        let disc: FootprintCollisionChecker =
+            FootprintCollisionChecker::new(Footprint::Disc { radius: 0.15 }, corridor());
+        assert!(!disc.is_node_colliding(&pose(1.5, 1.5, 0f64)));
+        assert!(!disc.is_edge_colliding(&pose(1.5, 0.4, 0f64), &pose(1.5, 2.6, 1f64)));
+        assert_eq!(
+            disc.clearance(&pose(1.5, 1.5, 0f64)),
+            disc.clearance(&Point::new(1.5, 1.5))
+        );
+    }
+
+    #[test]
+    fn test_swept_poses() {
+        let origin: Point<3> = Point::from_coordinates([0f64, 0f64, 0f64]);
+        assert_eq!(Footprint::Point.swept_poses(&origin, &origin), None);
+        assert_eq!(rectangle().swept_poses(&origin, &origin).unwrap().len(), 1);
+
+        // A half turn in place covers the disc of the robot.
+        let turned: Point<3> = Point::from_coordinates([0f64, 0f64, std::f64::consts::PI]);
+        let swept: Vec<Polygon<f64>> = rectangle().swept_poses(&origin, &turned).unwrap();
+        assert_eq!(swept.len(), 63);
+        let rect = geo::BoundingRect::bounding_rect(&geo::MultiPolygon::new(swept)).unwrap();
+        let radius: f64 = rectangle().circumscribed_radius();
+        assert!(rect.max().y > 0.99 * radius);
+        assert!(rect.min().y < -0.99 * radius);
+
+        // Turning across the seam at PI takes the short way around.
+        let begin: Point<3> = Point::from_coordinates([0f64, 0f64, 3.1]);
+        let end: Point<3> = Point::from_coordinates([0f64, 0f64, -3.1]);
+        assert_eq!(rectangle().swept_poses(&begin, &end).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_clearance() {
+        let cc: FootprintCollisionChecker =
+            FootprintCollisionChecker::new(Footprint::Disc { radius: 0.5 }, obstacles());
+        assert_eq!(cc.clearance(&Point::new(0f64, 1.5)), Some(0.5));
+        assert_eq!(cc.clearance(&Point::new(0.75, 1.5)), Some(-0.25));
+    }
+
+    #[test]
+    fn test_swept_area() {
+        assert_eq!(
+            Footprint::Point.swept(&Point::new(0f64, 0f64), &Point::new(1f64, 0f64)),
+            None
+        );
+        let swept: Polygon<f64> = rectangle()
+            .swept(&Point::new(0f64, 0f64), &Point::new(0f64, 2f64))
+            .unwrap();
+        let rect = geo::BoundingRect::bounding_rect(&swept).unwrap();
+        assert!((rect.min().x + 0.1).abs() < 1e-9);
+        assert!((rect.max().x - 0.1).abs() < 1e-9);
+        assert!((rect.min().y + 0.5).abs() < 1e-9);
+        assert!((rect.max().y - 2.5).abs() < 1e-9);
+    }
+}
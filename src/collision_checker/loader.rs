@@ -0,0 +1,255 @@
+//! Loaders for obstacle maps. Every loader returns a `PolygonCollisionChecker` with all areas of the map as obstacles,
+//! and `Boundaries` that are the bounding box of the map.
+
+use std::fs;
+
+use geo::{BoundingRect, Geometry, GeometryCollection, Polygon, Rect};
+use geojson::GeoJson;
+use wkt::TryFromWkt;
+
+use crate::boundaries::Boundaries;
+use crate::collision_checker::PolygonCollisionChecker;
+use crate::error::PlanningError;
+
+/// Parses a single WKT geometry. Supported are POLYGON, MULTIPOLYGON and GEOMETRYCOLLECTION of those.
+///
+/// # Errors
+/// - `PlanningError::InvalidWkt`: The text is not valid WKT
+/// - `PlanningError::UnsupportedGeometry`, `PlanningError::InvalidGeometry`, `PlanningError::EmptyMap`: See `collect_polygons`
+pub fn load_wkt(text: &str) -> Result<(PolygonCollisionChecker, Boundaries), PlanningError> {
+    let geometry: Geometry<f64> =
+        Geometry::try_from_wkt_str(text.trim()).map_err(|error| PlanningError::InvalidWkt {
+            message: error.to_string(),
+        })?;
+    build(geometry)
+}
+
+/// Reads the file and parses it with `load_wkt`.
+pub fn load_wkt_file(path: &str) -> Result<(PolygonCollisionChecker, Boundaries), PlanningError> {
+    load_wkt(&fs::read_to_string(path)?)
+}
+
+/// Parses GeoJSON. Usually a FeatureCollection, but a single Feature or Geometry works as well.
+/// Features without geometry are skipped.
+///
+/// # Errors
+/// - `PlanningError::InvalidGeoJson`: The text is not valid GeoJSON
+/// - `PlanningError::UnsupportedGeometry`, `PlanningError::InvalidGeometry`, `PlanningError::EmptyMap`: See `collect_polygons`
+pub fn load_geojson(text: &str) -> Result<(PolygonCollisionChecker, Boundaries), PlanningError> {
+    let invalid = |error: geojson::Error| PlanningError::InvalidGeoJson {
+        message: error.to_string(),
+    };
+    let geojson: GeoJson = text.parse().map_err(invalid)?;
+    let collection: GeometryCollection<f64> =
+        GeometryCollection::try_from(&geojson).map_err(invalid)?;
+    build(Geometry::GeometryCollection(collection))
+}
+
+/// Reads the file and parses it with `load_geojson`.
+pub fn load_geojson_file(
+    path: &str,
+) -> Result<(PolygonCollisionChecker, Boundaries), PlanningError> {
+    load_geojson(&fs::read_to_string(path)?)
+}
+
+/// Creates the collision checker and the boundaries from all polygons of the geometry.
+fn build(geometry: Geometry<f64>) -> Result<(PolygonCollisionChecker, Boundaries), PlanningError> {
+    let mut polygons: Vec<Polygon<f64>> = Vec::new();
+    collect_polygons(geometry, &mut polygons)?;
+    let rect: Rect<f64> = GeometryCollection::from(polygons.clone())
+        .bounding_rect()
+        .ok_or(PlanningError::EmptyMap)?;
+    let boundaries: Boundaries =
+        Boundaries::new(rect.min().x, rect.max().x, rect.min().y, rect.max().y);
+    Ok((PolygonCollisionChecker::new(polygons), boundaries))
+}
+
+/// Flattens the geometry into polygons. Rectangles and triangles are converted into polygons.
+///
+/// # Errors
+/// - `PlanningError::UnsupportedGeometry`: The geometry contains points or lines
+/// - `PlanningError::InvalidGeometry`: A polygon has less than three vertices or non-finite coordinates
+fn collect_polygons(
+    geometry: Geometry<f64>,
+    polygons: &mut Vec<Polygon<f64>>,
+) -> Result<(), PlanningError> {
+    match geometry {
+        Geometry::Polygon(polygon) => polygons.push(validate(polygon)?),
+        Geometry::MultiPolygon(multi_polygon) => {
+            for polygon in multi_polygon {
+                polygons.push(validate(polygon)?);
+            }
+        }
+        Geometry::Rect(rect) => polygons.push(validate(rect.to_polygon())?),
+        Geometry::Triangle(triangle) => polygons.push(validate(triangle.to_polygon())?),
+        Geometry::GeometryCollection(collection) => {
+            for geometry in collection {
+                collect_polygons(geometry, polygons)?;
+            }
+        }
+        Geometry::Point(_) => return Err(PlanningError::UnsupportedGeometry { geometry: "POINT" }),
+        Geometry::MultiPoint(_) => {
+            return Err(PlanningError::UnsupportedGeometry {
+                geometry: "MULTIPOINT",
+            })
+        }
+        Geometry::Line(_) | Geometry::LineString(_) => {
+            return Err(PlanningError::UnsupportedGeometry {
+                geometry: "LINESTRING",
+            })
+        }
+        Geometry::MultiLineString(_) => {
+            return Err(PlanningError::UnsupportedGeometry {
+                geometry: "MULTILINESTRING",
+            })
+        }
+    }
+    Ok(())
+}
+
+/// Checks that every ring of the polygon encloses an area and has finite coordinates.
+fn validate(polygon: Polygon<f64>) -> Result<Polygon<f64>, PlanningError> {
+    for ring in std::iter::once(polygon.exterior()).chain(polygon.interiors()) {
+        // Rings are closed, so the first vertex is contained twice.
+        if ring.0.len() < 4 {
+            return Err(PlanningError::InvalidGeometry {
+                message: format!(
+                    "ring with {} vertices does not enclose an area",
+                    ring.0.len().saturating_sub(1)
+                ),
+            });
+        }
+        if ring
+            .0
+            .iter()
+            .any(|coord| !coord.x.is_finite() || !coord.y.is_finite())
+        {
+            return Err(PlanningError::InvalidGeometry {
+                message: "ring with non-finite coordinates".to_string(),
+            });
+        }
+    }
+    Ok(polygon)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision_checker::CollisionChecker;
+    use crate::space::Point;
+
+    #[test]
+    fn test_load_wkt_polygon() {
+        let (cc, bounds) =
+            load_wkt("POLYGON((0 0, 4 0, 4 3, 0 3, 0 0), (1 1, 2 1, 2 2, 1 2, 1 1))").unwrap();
+        assert!(cc.is_node_colliding(&Point::new(0.5, 0.5)));
+        assert!(!cc.is_node_colliding(&Point::new(1.5, 1.5)));
+        assert_eq!(bounds.get_x_lower(), 0f64);
+        assert_eq!(bounds.get_x_upper(), 4f64);
+        assert_eq!(bounds.get_y_lower(), 0f64);
+        assert_eq!(bounds.get_y_upper(), 3f64);
+    }
+
+    #[test]
+    fn test_load_wkt_multi_polygon_and_collection() {
+        let (cc, bounds) = load_wkt(
+            "GEOMETRYCOLLECTION(
+                MULTIPOLYGON(((0 0, 1 0, 1 1, 0 1, 0 0)), ((5 5, 6 5, 6 6, 5 6, 5 5))),
+                POLYGON((-1 2, 0 2, 0 3, -1 3, -1 2))
+            )",
+        )
+        .unwrap();
+        assert_eq!(cc.get_polygons().len(), 3);
+        assert!(cc.is_node_colliding(&Point::new(5.5, 5.5)));
+        assert!(cc.is_node_colliding(&Point::new(-0.5, 2.5)));
+        assert!(!cc.is_node_colliding(&Point::new(3f64, 3f64)));
+        assert_eq!(bounds.get_x_lower(), -1f64);
+        assert_eq!(bounds.get_y_upper(), 6f64);
+    }
+
+    #[test]
+    fn test_load_wkt_errors() {
+        assert!(matches!(
+            load_wkt("POLYGON((0 0, 1 0, 1 1"),
+            Err(PlanningError::InvalidWkt { .. })
+        ));
+        assert!(matches!(
+            load_wkt("LINESTRING(0 0, 1 1)"),
+            Err(PlanningError::UnsupportedGeometry {
+                geometry: "LINESTRING"
+            })
+        ));
+        assert!(matches!(
+            load_wkt("POLYGON((0 0, 1 1, 0 0))"),
+            Err(PlanningError::InvalidGeometry { .. })
+        ));
+        assert!(matches!(
+            load_wkt("GEOMETRYCOLLECTION EMPTY"),
+            Err(PlanningError::EmptyMap)
+        ));
+    }
+
+    #[test]
+    fn test_load_geojson_feature_collection() {
+        let text: &str = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "properties": { "name": "shelf" },
+                    "geometry": { "type": "Polygon", "coordinates": [[[1, 1], [2, 1], [2, 2], [1, 2], [1, 1]]] }
+                },
+                {
+                    "type": "Feature",
+                    "properties": { "name": "walls" },
+                    "geometry": {
+                        "type": "MultiPolygon",
+                        "coordinates": [
+                            [[[0, 0], [4, 0], [4, 0.1], [0, 0.1], [0, 0]]],
+                            [[[0, 3.9], [4, 3.9], [4, 4], [0, 4], [0, 3.9]]]
+                        ]
+                    }
+                },
+                { "type": "Feature", "properties": {}, "geometry": null }
+            ]
+        }"#;
+        let (cc, bounds) = load_geojson(text).unwrap();
+        assert_eq!(cc.get_polygons().len(), 3);
+        assert!(cc.is_node_colliding(&Point::new(1.5, 1.5)));
+        assert!(cc.is_node_colliding(&Point::new(2f64, 0.05)));
+        assert!(!cc.is_node_colliding(&Point::new(3f64, 2f64)));
+        assert_eq!(bounds.get_x_upper(), 4f64);
+        assert_eq!(bounds.get_y_upper(), 4f64);
+    }
+
+    #[test]
+    fn test_load_geojson_errors() {
+        assert!(matches!(
+            load_geojson("{ \"type\": \"FeatureCollection\" "),
+            Err(PlanningError::InvalidGeoJson { .. })
+        ));
+        assert!(matches!(
+            load_geojson(r#"{ "type": "Point", "coordinates": [1, 2] }"#),
+            Err(PlanningError::UnsupportedGeometry { geometry: "POINT" })
+        ));
+        assert!(matches!(
+            load_geojson(r#"{ "type": "FeatureCollection", "features": [] }"#),
+            Err(PlanningError::EmptyMap)
+        ));
+    }
+
+    #[test]
+    fn test_load_files() {
+        let path = std::env::temp_dir().join("mpl_test_load_files.wkt");
+        let path: &str = path.to_str().unwrap();
+        std::fs::write(path, "POLYGON((0 0, 1 0, 1 1, 0 1, 0 0))").unwrap();
+        let (cc, _) = load_wkt_file(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert!(cc.is_node_colliding(&Point::new(0.5, 0.5)));
+
+        assert!(matches!(
+            load_geojson_file("/non/existent/map.geojson"),
+            Err(PlanningError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::space::Point;
+use std::marker::PhantomData;
+
+mod caching;
+mod compound;
+mod distance_field;
+mod dynamic;
+mod footprint;
+pub mod loader;
+mod motion_validator;
+mod occupancy_grid;
+mod polygon;
+
+pub use caching::{CachingCollisionChecker, CollisionStatistics};
+pub use compound::CompoundCollisionChecker;
+pub use distance_field::DistanceField;
+pub use dynamic::{DynamicCollisionChecker, DynamicObstacle, Keyframe};
+pub use footprint::{Footprint, FootprintCollisionChecker};
+pub use motion_validator::{DiscreteMotionValidator, MotionCheck};
+pub use occupancy_grid::{OccupancyGridCollisionChecker, OccupancyGridConfig};
+pub use polygon::PolygonCollisionChecker;
+
+/// CollisionChecker to implement custom Collision checkers.
+/// The dimension of the checked points defaults to 2D. Collision checkers for other configuration spaces implement `CollisionChecker<N>`.
+pub trait CollisionChecker<const N: usize = 2> {
+    /// Is run only once and before any checks are done. Can be used to read a file or database.
+    fn init(&self) -> bool;
+
+    /// Returns:
+    /// - true: f64here is an collision
+    /// - false: f64here is no collision
+    fn is_node_colliding(&self, node: &Point<N>) -> bool;
+
+    /// Returns:
+    /// - true: f64here is an collision
+    /// - false: f64here is no collision
+    fn is_edge_colliding(&self, node: &Point<N>, end: &Point<N>) -> bool;
+
+    /// Returns the distance from the node to the nearest obstacle. Is optional, the default does not support it.
+    /// - None: The clearance is unknown
+    /// - Some(clearance): Positive in free space, zero or negative inside obstacles
+    fn clearance(&self, _node: &Point<N>) -> Option<f64> {
+        None
+    }
+
+    /// Returns the counters of the collision checks. Only collision checkers that count their checks support it.
+    fn get_statistics(&self) -> Option<CollisionStatistics> {
+        None
+    }
+
+    /// Checks the edge from begin to end in order and reports how far it got before the first collision.
+    /// Is optional, the default does not support it. Planners then treat an edge in collision as a whole.
+    fn check_motion(&self, _begin: &Point<N>, _end: &Point<N>) -> Option<MotionCheck<N>> {
+        None
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct NaiveCollisionChecker {
+    pub phantom: PhantomData<f64>,
+}
+
+/// Does not check any collisions and always returns no collision (false). Works in any dimension.
+impl NaiveCollisionChecker {
+    pub fn new_box<const N: usize>() -> Box<dyn CollisionChecker<N>> {
+        Box::new(NaiveCollisionChecker {
+            phantom: PhantomData,
+        })
+    }
+}
+
+impl<const N: usize> CollisionChecker<N> for NaiveCollisionChecker {
+    /// Does nothing
+    /// Return
+    ///     true: always
+    fn init(&self) -> bool {
+        true
+    }
+
+    /// Does nothing
+    /// Return
+    ///     false: always
+    fn is_edge_colliding(&self, _node: &Point<N>, _end: &Point<N>) -> bool {
+        false
+    }
+
+    /// Does nothing
+    /// Return
+    ///     false: always
+    fn is_node_colliding(&self, _node: &Point<N>) -> bool {
+        false
+    }
+
+    /// There are no obstacles
+    /// Return
+    ///     infinity: always
+    fn clearance(&self, _node: &Point<N>) -> Option<f64> {
+        Some(f64::INFINITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CollisionChecker, NaiveCollisionChecker};
+    use crate::space::Point;
+    use std::marker::PhantomData;
+
+    #[test]
+    fn test_naive_init() {
+        let cc: NaiveCollisionChecker = NaiveCollisionChecker {
+            phantom: PhantomData,
+        };
+        let result: bool = CollisionChecker::<2>::init(&cc);
+        assert!(result);
+    }
+
+    #[test]
+    fn test_naive_node() {
+        let cc: NaiveCollisionChecker = NaiveCollisionChecker {
+            phantom: PhantomData,
+        };
+        let p1: &Point = &Point::new(1.0, 2.0);
+        let p2: &Point = &Point::new(1.0, 2.0);
+
+        let result = cc.is_edge_colliding(p1, p2);
+        assert!(!result);
+    }
+
+    #[test]
+    fn test_naive_edge() {
+        let cc: NaiveCollisionChecker = NaiveCollisionChecker {
+            phantom: PhantomData,
+        };
+        let p1: &Point = &Point::new(1.0, 2.0);
+        let result: bool = cc.is_node_colliding(p1);
+        assert!(!result);
+    }
+
+    #[test]
+    fn test_naive_clearance() {
+        let cc: NaiveCollisionChecker = NaiveCollisionChecker {
+            phantom: PhantomData,
+        };
+        assert_eq!(cc.clearance(&Point::new(1.0, 2.0)), Some(f64::INFINITY));
+    }
+}
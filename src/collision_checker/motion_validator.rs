@@ -0,0 +1,233 @@
+use std::collections::VecDeque;
+
+use crate::collision_checker::{CollisionChecker, CollisionStatistics};
+use crate::space::Point;
+
+/// Result of checking a motion state by state.
+///
+/// - is_valid: No state along the motion is in collision
+/// - last_valid: The last state before the first collision. None, if the first state is in collision.
+/// - fraction: Part of the motion from 0 to 1 that was reached before the first collision
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotionCheck<const N: usize = 2> {
+    pub is_valid: bool,
+    pub last_valid: Option<Point<N>>,
+    pub fraction: f64,
+}
+
+/// Checks edges by checking discrete states along the edge with `is_node_colliding` of the wrapped collision checker.
+/// Works with every collision checker, even if it can not check segments exactly, like grids or simulators.
+///
+/// The states are at most `resolution` apart and include both ends of the edge.
+/// Obstacles that are thinner than the resolution may be missed.
+/// Works in any dimension, e.g. for the joint space of an arm.
+pub struct DiscreteMotionValidator<const N: usize = 2> {
+    collision_checker: Box<dyn CollisionChecker<N>>,
+    resolution: f64,
+}
+
+impl<const N: usize> DiscreteMotionValidator<N> {
+    pub fn new(collision_checker: Box<dyn CollisionChecker<N>>, resolution: f64) -> Self {
+        DiscreteMotionValidator {
+            collision_checker,
+            resolution,
+        }
+    }
+
+    pub fn new_box(
+        collision_checker: Box<dyn CollisionChecker<N>>,
+        resolution: f64,
+    ) -> Box<dyn CollisionChecker<N>> {
+        Box::new(DiscreteMotionValidator::new(collision_checker, resolution))
+    }
+
+    pub fn get_resolution(&self) -> f64 {
+        self.resolution
+    }
+
+    pub fn set_resolution(&mut self, resolution: f64) {
+        self.resolution = resolution;
+    }
+
+    /// Checks the states from begin to end in order and stops at the first collision.
+    /// Slower than `is_edge_colliding` for edges in collision, but reports how far the motion got.
+    /// RRT-Connect uses it through `CollisionChecker::check_motion` to extend towards a sample as far as possible.
+    pub fn check_motion(&self, begin: &Point<N>, end: &Point<N>) -> MotionCheck<N> {
+        let steps: usize = self.steps(begin, end);
+        let mut last_valid: Option<Point<N>> = None;
+        let mut fraction: f64 = 0f64;
+        for step in 0..=steps {
+            let t: f64 = step as f64 / steps as f64;
+            let state: Point<N> = begin.interpolate(end, t);
+            if self.collision_checker.is_node_colliding(&state) {
+                return MotionCheck {
+                    is_valid: false,
+                    last_valid,
+                    fraction,
+                };
+            }
+            last_valid = Some(state);
+            fraction = t;
+        }
+        MotionCheck {
+            is_valid: true,
+            last_valid,
+            fraction,
+        }
+    }
+
+    /// Number of intervals between the states of an edge. Is at least 1, so both ends are always checked.
+    fn steps(&self, begin: &Point<N>, end: &Point<N>) -> usize {
+        let steps: f64 = (begin.euclidean_distance(end) / self.resolution).ceil();
+        if steps.is_finite() && steps >= 1f64 {
+            steps as usize
+        } else {
+            1usize
+        }
+    }
+}
+
+/// Returns the step indices of an edge with the given number of intervals in bisection order.
+/// First both ends, then the middle, then the middles of both halves and so on.
+/// Collisions are found early, because consecutive checks are far apart.
+fn bisection_order(steps: usize) -> Vec<usize> {
+    let mut order: Vec<usize> = vec![0usize, steps];
+    let mut intervals: VecDeque<(usize, usize)> = VecDeque::from([(0usize, steps)]);
+    while let Some((lower, upper)) = intervals.pop_front() {
+        if upper - lower < 2 {
+            continue;
+        }
+        let middle: usize = (lower + upper) / 2;
+        order.push(middle);
+        intervals.push_back((lower, middle));
+        intervals.push_back((middle, upper));
+    }
+    order
+}
+
+impl<const N: usize> CollisionChecker<N> for DiscreteMotionValidator<N> {
+    /// Initializes the wrapped collision checker.
+    fn init(&self) -> bool {
+        self.collision_checker.init()
+    }
+
+    fn is_node_colliding(&self, node: &Point<N>) -> bool {
+        self.collision_checker.is_node_colliding(node)
+    }
+
+    fn clearance(&self, node: &Point<N>) -> Option<f64> {
+        self.collision_checker.clearance(node)
+    }
+
+    fn get_statistics(&self) -> Option<CollisionStatistics> {
+        self.collision_checker.get_statistics()
+    }
+
+    fn check_motion(&self, begin: &Point<N>, end: &Point<N>) -> Option<MotionCheck<N>> {
+        Some(DiscreteMotionValidator::check_motion(self, begin, end))
+    }
+
+    /// Checks the states along the edge in bisection order and stops at the first collision.
+    fn is_edge_colliding(&self, begin: &Point<N>, end: &Point<N>) -> bool {
+        let steps: usize = self.steps(begin, end);
+        bisection_order(steps).into_iter().any(|step| {
+            let state: Point<N> = begin.interpolate(end, step as f64 / steps as f64);
+            self.collision_checker.is_node_colliding(&state)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Only knows its nodes. A wall at 1 <= x <= 1.2. Counts the node checks.
+    struct WallCollisionChecker {
+        checks: Rc<Cell<usize>>,
+    }
+
+    impl CollisionChecker for WallCollisionChecker {
+        fn init(&self) -> bool {
+            true
+        }
+
+        fn is_node_colliding(&self, node: &Point) -> bool {
+            self.checks.set(self.checks.get() + 1);
+            (1f64..=1.2).contains(&node.get_x())
+        }
+
+        fn is_edge_colliding(&self, _begin: &Point, _end: &Point) -> bool {
+            panic!("edges are checked by the motion validator")
+        }
+    }
+
+    fn create_validator(resolution: f64) -> (DiscreteMotionValidator, Rc<Cell<usize>>) {
+        let checks: Rc<Cell<usize>> = Rc::new(Cell::new(0usize));
+        let cc: Box<dyn CollisionChecker> = Box::new(WallCollisionChecker {
+            checks: Rc::clone(&checks),
+        });
+        (DiscreteMotionValidator::new(cc, resolution), checks)
+    }
+
+    #[test]
+    fn test_bisection_order() {
+        assert_eq!(bisection_order(1), vec![0, 1]);
+        assert_eq!(bisection_order(8), vec![0, 8, 4, 2, 6, 1, 3, 5, 7]);
+        let mut order: Vec<usize> = bisection_order(13);
+        order.sort();
+        assert_eq!(order, (0..=13).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn test_edge_colliding() {
+        let (validator, _) = create_validator(0.1);
+        assert!(validator.is_edge_colliding(&Point::new(0f64, 0f64), &Point::new(2f64, 0f64)));
+        assert!(!validator.is_edge_colliding(&Point::new(0f64, 0f64), &Point::new(0.9, 1f64)));
+        assert!(!validator.is_edge_colliding(&Point::new(0.5, 0.5), &Point::new(0.5, 0.5)));
+    }
+
+    // Test that the resolution decides, whether a thin wall is found.
+    #[test]
+    fn test_resolution() {
+        let (mut validator, _) = create_validator(0.5);
+        let begin: Point = Point::new(0.4, 0f64);
+        let end: Point = Point::new(2.4, 0f64);
+        assert!(!validator.is_edge_colliding(&begin, &end));
+        validator.set_resolution(0.1);
+        assert_eq!(validator.get_resolution(), 0.1);
+        assert!(validator.is_edge_colliding(&begin, &end));
+    }
+
+    // Test that a wall in the middle of the edge is found with few checks.
+    #[test]
+    fn test_early_exit() {
+        let (validator, checks) = create_validator(0.01);
+        assert!(validator.is_edge_colliding(&Point::new(0f64, 0f64), &Point::new(2.2, 0f64)));
+        assert_eq!(checks.get(), 3);
+    }
+
+    #[test]
+    fn test_check_motion() {
+        let (validator, _) = create_validator(0.1);
+        let motion: MotionCheck =
+            validator.check_motion(&Point::new(0f64, 0f64), &Point::new(2f64, 0f64));
+        assert!(!motion.is_valid);
+        assert!((motion.fraction - 0.45).abs() < 1e-9);
+        let last_valid: Point = motion.last_valid.unwrap();
+        assert!((last_valid.get_x() - 0.9).abs() < 1e-9);
+
+        let motion: MotionCheck =
+            validator.check_motion(&Point::new(0f64, 0f64), &Point::new(0.5, 0f64));
+        assert!(motion.is_valid);
+        assert_eq!(motion.fraction, 1f64);
+        assert_eq!(motion.last_valid, Some(Point::new(0.5, 0f64)));
+
+        let motion: MotionCheck =
+            validator.check_motion(&Point::new(1.1, 0f64), &Point::new(0f64, 0f64));
+        assert!(!motion.is_valid);
+        assert_eq!(motion.fraction, 0f64);
+        assert_eq!(motion.last_valid, None);
+    }
+}
@@ -0,0 +1,813 @@
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+use crate::boundaries::Boundaries;
+use crate::collision_checker::CollisionChecker;
+use crate::error::PlanningError;
+use crate::space::Point;
+
+/// Thresholds to classify the occupancy of a cell. The defaults are the ones of the ROS map_server.
+///
+/// - occupied_threshold: Cells with an occupancy above are occupied
+/// - free_threshold: Cells with an occupancy below are free. All others are unknown.
+/// - unknown_is_occupied: Treat unknown cells, and everything outside of the grid, as obstacles
+#[derive(Debug, Copy, Clone)]
+pub struct OccupancyGridConfig {
+    pub occupied_threshold: f64,
+    pub free_threshold: f64,
+    pub unknown_is_occupied: bool,
+}
+
+impl Default for OccupancyGridConfig {
+    fn default() -> Self {
+        OccupancyGridConfig {
+            occupied_threshold: 0.65,
+            free_threshold: 0.196,
+            unknown_is_occupied: true,
+        }
+    }
+}
+
+/// Collision checker for raster maps, like the occupancy grids of SLAM.
+/// Every cell holds an occupancy probability in [0, 1]. Negative values mark a cell as unknown.
+/// Cells are stored row by row, starting with the row at the origin, which is the lower left corner of the map.
+///
+/// Nodes are checked by a single cell lookup. Edges are checked by a DDA ray traversal, which visits every cell the edge passes.
+#[derive(Debug, Clone)]
+pub struct OccupancyGridCollisionChecker {
+    width: usize,
+    height: usize,
+    resolution: f64,
+    origin: Point,
+    occupancy: Vec<f64>,
+    pub config: OccupancyGridConfig,
+}
+
+impl OccupancyGridCollisionChecker {
+    /// Creates a grid with `width` x `height` cells.
+    ///
+    /// # Arguments
+    /// - `resolution`: Edge length of a cell
+    /// - `origin`: Position of the lower left corner of the map
+    /// - `occupancy`: Occupancy of every cell, row by row from the bottom
+    ///
+    /// # Errors
+    /// Returns `PlanningError::InvalidMap`, if the number of cells does not match or the resolution is not positive.
+    pub fn new(
+        width: usize,
+        height: usize,
+        resolution: f64,
+        origin: Point,
+        occupancy: Vec<f64>,
+    ) -> Result<Self, PlanningError> {
+        if width.checked_mul(height) != Some(occupancy.len()) {
+            return Err(PlanningError::InvalidMap {
+                message: format!(
+                    "{} cells given for a grid of {width} x {height}",
+                    occupancy.len()
+                ),
+            });
+        }
+        if resolution <= 0f64 || !resolution.is_finite() {
+            return Err(PlanningError::InvalidMap {
+                message: format!("resolution {resolution} must be positive"),
+            });
+        }
+        Ok(OccupancyGridCollisionChecker {
+            width,
+            height,
+            resolution,
+            origin,
+            occupancy,
+            config: OccupancyGridConfig::default(),
+        })
+    }
+
+    /// Creates a grid from a binary (P5) or ASCII (P2) PGM image. Dark pixels are occupied, bright pixels are free.
+    pub fn from_pgm(bytes: &[u8], resolution: f64, origin: Point) -> Result<Self, PlanningError> {
+        let image: GrayImage = GrayImage::from_pgm(bytes)?;
+        image.into_grid(resolution, origin, false)
+    }
+
+    /// Creates a grid from a PNG image. Colors are converted to gray. Dark pixels are occupied, bright pixels are free.
+    pub fn from_png(bytes: &[u8], resolution: f64, origin: Point) -> Result<Self, PlanningError> {
+        let image: GrayImage = GrayImage::from_png(bytes)?;
+        image.into_grid(resolution, origin, false)
+    }
+
+    /// Reads a PGM or PNG image. The format is detected from the content of the file.
+    pub fn from_image_file(
+        path: &str,
+        resolution: f64,
+        origin: Point,
+    ) -> Result<Self, PlanningError> {
+        let image: GrayImage = GrayImage::from_bytes(&fs::read(path)?)?;
+        image.into_grid(resolution, origin, false)
+    }
+
+    /// Reads a map in the format of the ROS map_server: A YAML file that references a PGM or PNG image.
+    /// Supported keys are `image`, `resolution`, `origin`, `negate`, `occupied_thresh` and `free_thresh`.
+    /// A relative image path is relative to the YAML file. A rotated origin is not supported.
+    pub fn from_ros_yaml_file(path: &str) -> Result<Self, PlanningError> {
+        let yaml: String = fs::read_to_string(path)?;
+        let metadata: RosMapMetadata = RosMapMetadata::parse(&yaml)?;
+
+        let image_path = Path::new(path)
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(&metadata.image);
+        let image: GrayImage = GrayImage::from_bytes(&fs::read(image_path)?)?;
+        let mut grid = image.into_grid(metadata.resolution, metadata.origin, metadata.negate)?;
+        grid.config.occupied_threshold = metadata.occupied_threshold;
+        grid.config.free_threshold = metadata.free_threshold;
+        Ok(grid)
+    }
+
+    /// Returns the boundaries that cover the whole grid.
+    pub fn get_boundaries(&self) -> Boundaries {
+        Boundaries::new(
+            self.origin.get_x(),
+            self.origin.get_x() + self.width as f64 * self.resolution,
+            self.origin.get_y(),
+            self.origin.get_y() + self.height as f64 * self.resolution,
+        )
+    }
+
+    pub fn get_resolution(&self) -> f64 {
+        self.resolution
+    }
+
+    /// Returns the occupancy of the cell that contains the point.
+    /// - None: The point is outside of the grid or not finite
+    pub fn get_occupancy(&self, point: &Point) -> Option<f64> {
+        let (column, row) = self.cell_of(point)?;
+        self.occupancy_of_cell(column, row)
+    }
+
+    /// Returns the column and row of the cell that contains the point. Can be outside of the grid.
+    /// - None: A coordinate is NaN or infinite, so there is no cell
+    fn cell_of(&self, point: &Point) -> Option<(i64, i64)> {
+        if !point.get_x().is_finite() || !point.get_y().is_finite() {
+            return None;
+        }
+        Some((
+            ((point.get_x() - self.origin.get_x()) / self.resolution).floor() as i64,
+            ((point.get_y() - self.origin.get_y()) / self.resolution).floor() as i64,
+        ))
+    }
+
+    fn occupancy_of_cell(&self, column: i64, row: i64) -> Option<f64> {
+        if column < 0 || row < 0 || column >= self.width as i64 || row >= self.height as i64 {
+            return None;
+        }
+        Some(self.occupancy[row as usize * self.width + column as usize])
+    }
+
+    /// Clips the edge to the rectangle of the grid with the algorithm of Liang and Barsky.
+    /// Works on half of the coordinates, so that the difference of far apart points does not overflow.
+    /// A clipped end is placed exactly on the border of the grid that cut the edge.
+    /// - None: The edge does not touch the grid
+    fn clip_to_grid(&self, begin: &Point, end: &Point) -> Option<(Point, Point)> {
+        let start: [f64; 2] = begin.get_coordinates();
+        let stop: [f64; 2] = end.get_coordinates();
+        let lower: [f64; 2] = [self.origin.get_x(), self.origin.get_y()];
+        let upper: [f64; 2] = [
+            lower[0] + self.width as f64 * self.resolution,
+            lower[1] + self.height as f64 * self.resolution,
+        ];
+        let half_delta: [f64; 2] = [0, 1].map(|axis| stop[axis] / 2f64 - start[axis] / 2f64);
+
+        // Parameter t along the edge and the border of the grid that cut the edge there.
+        let mut clip_begin: (f64, Option<(usize, f64)>) = (0f64, None);
+        let mut clip_end: (f64, Option<(usize, f64)>) = (1f64, None);
+        for axis in 0..2 {
+            if half_delta[axis] == 0f64 {
+                if start[axis] < lower[axis] || start[axis] > upper[axis] {
+                    return None;
+                }
+                continue;
+            }
+            let (enter, leave): (f64, f64) = if half_delta[axis] > 0f64 {
+                (lower[axis], upper[axis])
+            } else {
+                (upper[axis], lower[axis])
+            };
+            let t_enter: f64 = (enter / 2f64 - start[axis] / 2f64) / half_delta[axis];
+            let t_leave: f64 = (leave / 2f64 - start[axis] / 2f64) / half_delta[axis];
+            if t_enter > clip_begin.0 {
+                clip_begin = (t_enter, Some((axis, enter)));
+            }
+            if t_leave < clip_end.0 {
+                clip_end = (t_leave, Some((axis, leave)));
+            }
+        }
+        if clip_begin.0 > clip_end.0 {
+            return None;
+        }
+        let clip = |(t, border): (f64, Option<(usize, f64)>)| -> Point {
+            let coordinate = |axis: usize| -> f64 {
+                match border {
+                    Some((border_axis, value)) if border_axis == axis => value,
+                    _ if t == 0f64 => start[axis],
+                    _ if t == 1f64 => stop[axis],
+                    _ => (start[axis] + t * half_delta[axis] + t * half_delta[axis])
+                        .clamp(lower[axis], upper[axis]),
+                }
+            };
+            Point::new(coordinate(0), coordinate(1))
+        };
+        Some((clip(clip_begin), clip(clip_end)))
+    }
+
+    fn is_cell_colliding(&self, column: i64, row: i64) -> bool {
+        match self.occupancy_of_cell(column, row) {
+            Some(occupancy) if occupancy > self.config.occupied_threshold => true,
+            Some(occupancy) if occupancy >= 0f64 && occupancy < self.config.free_threshold => false,
+            _ => self.config.unknown_is_occupied,
+        }
+    }
+}
+
+impl CollisionChecker for OccupancyGridCollisionChecker {
+    /// Does nothing
+    /// Return
+    ///     true: always
+    fn init(&self) -> bool {
+        true
+    }
+
+    /// Traverses all cells the edge passes with the DDA algorithm of Amanatides and Woo.
+    /// If the edge passes exactly through a corner, both neighboring cells are checked.
+    /// Edges with a coordinate that is not finite are always colliding.
+    /// Only the part of the edge inside of the grid is traversed, the rest is outside and unknown.
+    fn is_edge_colliding(&self, begin: &Point, end: &Point) -> bool {
+        let (Some(begin_cell), Some(end_cell)) = (self.cell_of(begin), self.cell_of(end)) else {
+            return true;
+        };
+        if self.config.unknown_is_occupied
+            && [begin_cell, end_cell]
+                .iter()
+                .any(|&(column, row)| self.occupancy_of_cell(column, row).is_none())
+        {
+            return true;
+        }
+        let Some((begin, end)) = self.clip_to_grid(begin, end) else {
+            return false;
+        };
+        let (begin, end): (&Point, &Point) = (&begin, &end);
+        let (Some((mut column, mut row)), Some((end_column, end_row))) =
+            (self.cell_of(begin), self.cell_of(end))
+        else {
+            return true;
+        };
+        let dx: f64 = end.get_x() - begin.get_x();
+        let dy: f64 = end.get_y() - begin.get_y();
+        let step_column: i64 = if dx > 0f64 { 1 } else { -1 };
+        let step_row: i64 = if dy > 0f64 { 1 } else { -1 };
+
+        // Parameter t in [0, 1] along the edge, at which the next cell border is crossed.
+        let next_border = |cell: i64, step: i64, origin: f64, start: f64, delta: f64| -> f64 {
+            if delta == 0f64 {
+                return f64::INFINITY;
+            }
+            let border: f64 = origin + (cell + i64::from(step > 0)) as f64 * self.resolution;
+            (border - start) / delta
+        };
+        let mut t_column: f64 =
+            next_border(column, step_column, self.origin.get_x(), begin.get_x(), dx);
+        let mut t_row: f64 = next_border(row, step_row, self.origin.get_y(), begin.get_y(), dy);
+        let t_delta_column: f64 = (self.resolution / dx).abs();
+        let t_delta_row: f64 = (self.resolution / dy).abs();
+
+        let Some(steps) = end_column
+            .abs_diff(column)
+            .checked_add(end_row.abs_diff(row))
+        else {
+            return true;
+        };
+        for _ in 0..=steps {
+            if self.is_cell_colliding(column, row) {
+                return true;
+            }
+            if column == end_column && row == end_row {
+                return false;
+            }
+            if t_column < t_row {
+                column += step_column;
+                t_column += t_delta_column;
+            } else if t_row < t_column {
+                row += step_row;
+                t_row += t_delta_row;
+            } else {
+                if self.is_cell_colliding(column + step_column, row)
+                    || self.is_cell_colliding(column, row + step_row)
+                {
+                    return true;
+                }
+                column += step_column;
+                row += step_row;
+                t_column += t_delta_column;
+                t_row += t_delta_row;
+            }
+        }
+        self.is_cell_colliding(end_column, end_row)
+    }
+
+    /// Looks up the cell that contains the node. Nodes with a coordinate that is not finite are always colliding.
+    fn is_node_colliding(&self, node: &Point) -> bool {
+        match self.cell_of(node) {
+            Some((column, row)) => self.is_cell_colliding(column, row),
+            None => true,
+        }
+    }
+}
+
+/// Gray image with the first row at the top, as stored in image files.
+struct GrayImage {
+    width: usize,
+    height: usize,
+    max_value: f64,
+    pixels: Vec<f64>,
+}
+
+impl GrayImage {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, PlanningError> {
+        if bytes.starts_with(b"P2") || bytes.starts_with(b"P5") {
+            GrayImage::from_pgm(bytes)
+        } else if bytes.starts_with(b"\x89PNG") {
+            GrayImage::from_png(bytes)
+        } else {
+            Err(PlanningError::InvalidMap {
+                message: "image is neither PGM nor PNG".to_string(),
+            })
+        }
+    }
+
+    fn from_pgm(bytes: &[u8]) -> Result<Self, PlanningError> {
+        let invalid = |message: &str| PlanningError::InvalidMap {
+            message: format!("invalid PGM: {message}"),
+        };
+
+        // The header consists of four tokens: magic number, width, height, and maximum value.
+        let mut position: usize = 0;
+        let mut header: Vec<&str> = Vec::new();
+        while header.len() < 4 {
+            while position < bytes.len() && bytes[position].is_ascii_whitespace() {
+                position += 1;
+            }
+            if position < bytes.len() && bytes[position] == b'#' {
+                while position < bytes.len() && bytes[position] != b'\n' {
+                    position += 1;
+                }
+                continue;
+            }
+            let start: usize = position;
+            while position < bytes.len() && !bytes[position].is_ascii_whitespace() {
+                position += 1;
+            }
+            if start == position {
+                return Err(invalid("incomplete header"));
+            }
+            header.push(
+                std::str::from_utf8(&bytes[start..position])
+                    .map_err(|_| invalid("header is not ASCII"))?,
+            );
+        }
+        let parse = |token: &str| {
+            token
+                .parse::<usize>()
+                .map_err(|_| invalid("header is not a number"))
+        };
+        let width: usize = parse(header[1])?;
+        let height: usize = parse(header[2])?;
+        let max_value: usize = parse(header[3])?;
+        if max_value == 0 || max_value > u16::MAX as usize {
+            return Err(invalid("maximum value out of range"));
+        }
+
+        let pixels: Vec<f64> = match header[0] {
+            "P5" => {
+                // Exactly one whitespace separates the header from the binary data.
+                let data: &[u8] = bytes.get(position + 1..).unwrap_or_default();
+                if max_value < 256 {
+                    data.iter().map(|value| *value as f64).collect()
+                } else {
+                    data.chunks_exact(2)
+                        .map(|value| u16::from_be_bytes([value[0], value[1]]) as f64)
+                        .collect()
+                }
+            }
+            "P2" => std::str::from_utf8(&bytes[position..])
+                .map_err(|_| invalid("data is not ASCII"))?
+                .split_ascii_whitespace()
+                .map(|token| parse(token).map(|value| value as f64))
+                .collect::<Result<Vec<f64>, PlanningError>>()?,
+            _ => return Err(invalid("only P2 and P5 are supported")),
+        };
+        let size: usize = width
+            .checked_mul(height)
+            .ok_or_else(|| invalid("image is too large"))?;
+        if pixels.len() < size {
+            return Err(invalid("not enough pixels"));
+        }
+
+        Ok(GrayImage {
+            width,
+            height,
+            max_value: max_value as f64,
+            pixels: pixels[..size].to_vec(),
+        })
+    }
+
+    fn from_png(bytes: &[u8]) -> Result<Self, PlanningError> {
+        let invalid = |error: png::DecodingError| PlanningError::InvalidMap {
+            message: format!("invalid PNG: {error}"),
+        };
+        let mut decoder = png::Decoder::new(Cursor::new(bytes));
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().map_err(invalid)?;
+        let mut buffer: Vec<u8> = vec![0; reader.output_buffer_size().unwrap_or_default()];
+        let info: png::OutputInfo = reader.next_frame(&mut buffer).map_err(invalid)?;
+
+        // Alpha is ignored. Colors are averaged to gray.
+        let (channels, color_channels): (usize, usize) = match info.color_type {
+            png::ColorType::Grayscale => (1, 1),
+            png::ColorType::GrayscaleAlpha => (2, 1),
+            png::ColorType::Rgb => (3, 3),
+            png::ColorType::Rgba => (4, 3),
+            png::ColorType::Indexed => {
+                return Err(PlanningError::InvalidMap {
+                    message: "invalid PNG: indexed colors were not expanded".to_string(),
+                })
+            }
+        };
+        let width: usize = info.width as usize;
+        let height: usize = info.height as usize;
+        let pixels: Vec<f64> = buffer
+            .chunks(info.line_size)
+            .take(height)
+            .flat_map(|line| {
+                line.chunks_exact(channels).take(width).map(|pixel| {
+                    pixel[..color_channels]
+                        .iter()
+                        .map(|value| *value as f64)
+                        .sum::<f64>()
+                        / color_channels as f64
+                })
+            })
+            .collect();
+
+        Ok(GrayImage {
+            width,
+            height,
+            max_value: 255f64,
+            pixels,
+        })
+    }
+
+    /// Converts the brightness into occupancy and flips the rows, so the first row is at the origin.
+    /// - negate: Bright pixels are occupied instead of free
+    fn into_grid(
+        self,
+        resolution: f64,
+        origin: Point,
+        negate: bool,
+    ) -> Result<OccupancyGridCollisionChecker, PlanningError> {
+        let occupancy: Vec<f64> = self
+            .pixels
+            .chunks(self.width.max(1))
+            .rev()
+            .flatten()
+            .map(|value| match negate {
+                true => value / self.max_value,
+                false => (self.max_value - value) / self.max_value,
+            })
+            .collect();
+        OccupancyGridCollisionChecker::new(self.width, self.height, resolution, origin, occupancy)
+    }
+}
+
+/// Content of the YAML file of a ROS map.
+struct RosMapMetadata {
+    image: String,
+    resolution: f64,
+    origin: Point,
+    negate: bool,
+    occupied_threshold: f64,
+    free_threshold: f64,
+}
+
+impl RosMapMetadata {
+    /// Parses the flat `key: value` structure of the map YAML. Nested YAML is not supported.
+    fn parse(yaml: &str) -> Result<Self, PlanningError> {
+        let invalid = |message: String| PlanningError::InvalidMap {
+            message: format!("invalid map YAML: {message}"),
+        };
+        let parse_number = |key: &str, value: &str| {
+            value
+                .parse::<f64>()
+                .map_err(|_| invalid(format!("{key} is not a number")))
+        };
+
+        let defaults: OccupancyGridConfig = OccupancyGridConfig::default();
+        let mut image: Option<String> = None;
+        let mut resolution: Option<f64> = None;
+        let mut origin: Point = Point::new(0f64, 0f64);
+        let mut negate: bool = false;
+        let mut occupied_threshold: f64 = defaults.occupied_threshold;
+        let mut free_threshold: f64 = defaults.free_threshold;
+
+        for line in yaml.lines() {
+            let line: &str = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value: &str = value.trim().trim_matches(|c| c == '"' || c == '\'');
+            match key.trim() {
+                "image" => image = Some(value.to_string()),
+                "resolution" => resolution = Some(parse_number("resolution", value)?),
+                "negate" => negate = matches!(value, "1" | "true" | "True"),
+                "occupied_thresh" => occupied_threshold = parse_number("occupied_thresh", value)?,
+                "free_thresh" => free_threshold = parse_number("free_thresh", value)?,
+                "origin" => {
+                    let values: Vec<f64> = value
+                        .trim_start_matches('[')
+                        .trim_end_matches(']')
+                        .split(',')
+                        .map(|token| parse_number("origin", token.trim()))
+                        .collect::<Result<Vec<f64>, PlanningError>>()?;
+                    if values.len() < 2 {
+                        return Err(invalid("origin needs x and y".to_string()));
+                    }
+                    if values.get(2).is_some_and(|yaw| *yaw != 0f64) {
+                        return Err(invalid("a rotated origin is not supported".to_string()));
+                    }
+                    origin = Point::new(values[0], values[1]);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(RosMapMetadata {
+            image: image.ok_or_else(|| invalid("image is missing".to_string()))?,
+            resolution: resolution.ok_or_else(|| invalid("resolution is missing".to_string()))?,
+            origin,
+            negate,
+            occupied_threshold,
+            free_threshold,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 4 x 3 map as seen in an image, top row first:
+    /// ```text
+    /// . # . ?
+    /// . # . .
+    /// . . . .
+    /// ```
+    /// Free is 254, occupied is 0, unknown is 205.
+    const PIXELS: [u8; 12] = [254, 0, 254, 205, 254, 0, 254, 254, 254, 254, 254, 254];
+
+    fn pgm_p5() -> Vec<u8> {
+        let mut bytes: Vec<u8> = b"P5\n# CREATOR: test\n4 3\n255\n".to_vec();
+        bytes.extend_from_slice(&PIXELS);
+        bytes
+    }
+
+    fn png() -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, 4, 3);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&PIXELS).unwrap();
+        writer.finish().unwrap();
+        bytes
+    }
+
+    /// Checks the map of `PIXELS` with a resolution of 0.5 and the origin at (-1, 0).
+    fn assert_map(cc: &OccupancyGridCollisionChecker) {
+        assert!(!cc.is_node_colliding(&Point::new(-0.75, 0.25)));
+        assert!(cc.is_node_colliding(&Point::new(-0.25, 1.25)));
+        assert!(cc.is_node_colliding(&Point::new(-0.25, 0.75)));
+        assert!(!cc.is_node_colliding(&Point::new(-0.25, 0.25)));
+        assert!(cc.is_node_colliding(&Point::new(0.75, 1.25)));
+        assert!(cc.is_node_colliding(&Point::new(-1.25, 0.25)));
+    }
+
+    #[test]
+    fn test_from_pgm() {
+        let cc = OccupancyGridCollisionChecker::from_pgm(&pgm_p5(), 0.5, Point::new(-1f64, 0f64))
+            .unwrap();
+        assert_map(&cc);
+
+        let ascii: String = format!(
+            "P2\n4 3\n255\n{}\n",
+            PIXELS.map(|value| value.to_string()).join(" ")
+        );
+        let cc =
+            OccupancyGridCollisionChecker::from_pgm(ascii.as_bytes(), 0.5, Point::new(-1f64, 0f64))
+                .unwrap();
+        assert_map(&cc);
+    }
+
+    #[test]
+    fn test_from_png() {
+        let cc =
+            OccupancyGridCollisionChecker::from_png(&png(), 0.5, Point::new(-1f64, 0f64)).unwrap();
+        assert_map(&cc);
+    }
+
+    #[test]
+    fn test_invalid_maps() {
+        assert!(matches!(
+            OccupancyGridCollisionChecker::from_pgm(b"P5\n4 3\n255\n\x00", 0.5, Point::default()),
+            Err(PlanningError::InvalidMap { .. })
+        ));
+        assert!(matches!(
+            OccupancyGridCollisionChecker::from_pgm(b"P6\n4 3\n255\n", 0.5, Point::default()),
+            Err(PlanningError::InvalidMap { .. })
+        ));
+        assert!(matches!(
+            OccupancyGridCollisionChecker::from_png(b"\x89PNG broken", 0.5, Point::default()),
+            Err(PlanningError::InvalidMap { .. })
+        ));
+        assert!(matches!(
+            OccupancyGridCollisionChecker::new(2, 2, 1f64, Point::default(), vec![0f64; 3]),
+            Err(PlanningError::InvalidMap { .. })
+        ));
+        assert!(matches!(
+            OccupancyGridCollisionChecker::new(2, 2, 0f64, Point::default(), vec![0f64; 4]),
+            Err(PlanningError::InvalidMap { .. })
+        ));
+    }
+
+    // Test that a header, whose number of pixels does not fit into usize, is rejected instead of overflowing.
+    #[test]
+    fn test_pgm_size_overflow() {
+        let header: String = format!("P5\n{} 3\n255\n\x00", usize::MAX);
+        assert!(matches!(
+            OccupancyGridCollisionChecker::from_pgm(header.as_bytes(), 0.5, Point::default()),
+            Err(PlanningError::InvalidMap { .. })
+        ));
+        assert!(matches!(
+            OccupancyGridCollisionChecker::new(
+                usize::MAX,
+                2,
+                1f64,
+                Point::default(),
+                vec![0f64; 2]
+            ),
+            Err(PlanningError::InvalidMap { .. })
+        ));
+    }
+
+    // Test that coordinates that are not finite are colliding instead of being mapped to a cell.
+    #[test]
+    fn test_non_finite_coordinates() {
+        let cc: OccupancyGridCollisionChecker =
+            OccupancyGridCollisionChecker::new(2, 2, 1f64, Point::default(), vec![0f64; 4])
+                .unwrap();
+        assert!(!cc.is_node_colliding(&Point::new(0.5, 0.5)));
+        for invalid in [
+            Point::new(f64::NAN, 0.5),
+            Point::new(0.5, f64::NAN),
+            Point::new(f64::INFINITY, 0.5),
+            Point::new(0.5, f64::NEG_INFINITY),
+        ] {
+            assert_eq!(cc.get_occupancy(&invalid), None);
+            assert!(cc.is_node_colliding(&invalid));
+            assert!(cc.is_edge_colliding(&Point::new(0.5, 0.5), &invalid));
+            assert!(cc.is_edge_colliding(&invalid, &Point::new(0.5, 0.5)));
+        }
+    }
+
+    // Test that far apart edges are clipped to the grid instead of overflowing or walking every cell.
+    #[test]
+    fn test_huge_edge() {
+        let mut occupancy: Vec<f64> = vec![0f64; 4];
+        let begin: Point = Point::new(-1e300, 0.5);
+        let end: Point = Point::new(1e300, 0.5);
+        let mut cc: OccupancyGridCollisionChecker =
+            OccupancyGridCollisionChecker::new(2, 2, 1f64, Point::default(), occupancy.clone())
+                .unwrap();
+        assert!(cc.is_edge_colliding(&begin, &end));
+        cc.config.unknown_is_occupied = false;
+        assert!(!cc.is_edge_colliding(&begin, &end));
+        assert!(!cc.is_edge_colliding(&Point::new(-1e300, 5f64), &Point::new(1e300, 5f64)));
+        assert!(!cc.is_edge_colliding(&Point::new(-1e300, -1e300), &Point::new(-1e299, 1e300)));
+
+        occupancy[1] = 1f64;
+        let mut cc: OccupancyGridCollisionChecker =
+            OccupancyGridCollisionChecker::new(2, 2, 1f64, Point::default(), occupancy).unwrap();
+        assert!(cc.is_edge_colliding(&begin, &end));
+        cc.config.unknown_is_occupied = false;
+        assert!(cc.is_edge_colliding(&begin, &end));
+        assert!(cc.is_edge_colliding(&Point::new(-1e300, -1e300), &Point::new(1e300, 1e300)));
+        assert!(!cc.is_edge_colliding(&Point::new(-1e300, 1.5), &Point::new(1e300, 1.5)));
+        assert!(cc.is_edge_colliding(&Point::new(f64::MAX, 0.5), &Point::new(f64::MIN, 0.5)));
+    }
+
+    // Test that the thresholds decide about occupied, free, and unknown cells.
+    #[test]
+    fn test_thresholds() {
+        let mut cc = OccupancyGridCollisionChecker::new(
+            4,
+            1,
+            1f64,
+            Point::new(0f64, 0f64),
+            vec![0.1, 0.5, 0.9, -1f64],
+        )
+        .unwrap();
+        assert!(!cc.is_node_colliding(&Point::new(0.5, 0.5)));
+        assert!(cc.is_node_colliding(&Point::new(1.5, 0.5)));
+        assert!(cc.is_node_colliding(&Point::new(2.5, 0.5)));
+        assert!(cc.is_node_colliding(&Point::new(3.5, 0.5)));
+        assert!(cc.is_node_colliding(&Point::new(4.5, 0.5)));
+
+        cc.config.unknown_is_occupied = false;
+        assert!(!cc.is_node_colliding(&Point::new(1.5, 0.5)));
+        assert!(cc.is_node_colliding(&Point::new(2.5, 0.5)));
+        assert!(!cc.is_node_colliding(&Point::new(3.5, 0.5)));
+        assert!(!cc.is_node_colliding(&Point::new(4.5, 0.5)));
+
+        cc.config.occupied_threshold = 0.4;
+        assert!(cc.is_node_colliding(&Point::new(1.5, 0.5)));
+    }
+
+    // Test the edge traversal against a dense sampling of the edge.
+    #[test]
+    fn test_edge_traversal() {
+        // Single occupied cell in the center of a 5 x 5 grid.
+        let mut occupancy: Vec<f64> = vec![0f64; 25];
+        occupancy[12] = 1f64;
+        let cc = OccupancyGridCollisionChecker::new(5, 5, 1f64, Point::new(0f64, 0f64), occupancy)
+            .unwrap();
+
+        assert!(cc.is_edge_colliding(&Point::new(0.5, 0.5), &Point::new(4.5, 4.5)));
+        assert!(cc.is_edge_colliding(&Point::new(4.5, 2.5), &Point::new(0.5, 2.5)));
+        assert!(cc.is_edge_colliding(&Point::new(2.5, 0.5), &Point::new(2.5, 4.5)));
+        assert!(!cc.is_edge_colliding(&Point::new(0.5, 0.5), &Point::new(4.5, 0.5)));
+        assert!(!cc.is_edge_colliding(&Point::new(0.5, 0.5), &Point::new(1.5, 4.5)));
+        assert!(!cc.is_edge_colliding(&Point::new(1.5, 1.5), &Point::new(1.5, 1.5)));
+        // Passes exactly through the corner (2, 2) of the occupied cell.
+        assert!(cc.is_edge_colliding(&Point::new(1.5, 2.5), &Point::new(2.5, 1.5)));
+        // Leaves the grid.
+        assert!(cc.is_edge_colliding(&Point::new(0.5, 0.5), &Point::new(-0.5, 0.5)));
+
+        for k in 0..100 {
+            let begin: Point = Point::new((k * 7 % 50) as f64 / 10.0, (k * 13 % 50) as f64 / 10.0);
+            let end: Point = Point::new((k * 17 % 50) as f64 / 10.0, (k * 3 % 50) as f64 / 10.0);
+            let sampled: bool = (0..=10000)
+                .any(|i| cc.is_node_colliding(&begin.interpolate(&end, i as f64 / 10000.0)));
+            if sampled {
+                assert!(cc.is_edge_colliding(&begin, &end));
+            }
+        }
+    }
+
+    #[test]
+    fn test_boundaries() {
+        let cc = OccupancyGridCollisionChecker::from_pgm(&pgm_p5(), 0.5, Point::new(-1f64, 0f64))
+            .unwrap();
+        let bounds: Boundaries = cc.get_boundaries();
+        assert_eq!(bounds.get_x_lower(), -1f64);
+        assert_eq!(bounds.get_x_upper(), 1f64);
+        assert_eq!(bounds.get_y_lower(), 0f64);
+        assert_eq!(bounds.get_y_upper(), 1.5);
+        assert_eq!(cc.get_resolution(), 0.5);
+        assert_eq!(cc.get_occupancy(&Point::new(5f64, 5f64)), None);
+    }
+
+    #[test]
+    fn test_from_ros_yaml_file() {
+        let directory = std::env::temp_dir().join("mpl_test_from_ros_yaml_file");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("map.pgm"), pgm_p5()).unwrap();
+        let yaml = directory.join("map.yaml");
+        fs::write(
+            &yaml,
+            "image: map.pgm\nresolution: 0.5\norigin: [-1.0, 0.0, 0.0]\nnegate: 0\noccupied_thresh: 0.65\nfree_thresh: 0.196 # comment\n",
+        )
+        .unwrap();
+        let cc = OccupancyGridCollisionChecker::from_ros_yaml_file(yaml.to_str().unwrap()).unwrap();
+        assert_map(&cc);
+
+        fs::write(
+            &yaml,
+            "image: map.pgm\nresolution: 0.5\norigin: [0.0, 0.0, 1.57]\n",
+        )
+        .unwrap();
+        let rotated = OccupancyGridCollisionChecker::from_ros_yaml_file(yaml.to_str().unwrap());
+        fs::write(&yaml, "image: map.pgm\n").unwrap();
+        let missing = OccupancyGridCollisionChecker::from_ros_yaml_file(yaml.to_str().unwrap());
+        fs::remove_dir_all(&directory).unwrap();
+        assert!(matches!(rotated, Err(PlanningError::InvalidMap { .. })));
+        assert!(matches!(missing, Err(PlanningError::InvalidMap { .. })));
+    }
+}
@@ -0,0 +1,328 @@
+use geo::algorithm::buffer::{Buffer, BufferStyle, LineJoin};
+use geo::{BoundingRect, Distance, Euclidean, Intersects, Line, MultiPolygon, Polygon};
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{RTree, AABB};
+
+use crate::collision_checker::CollisionChecker;
+use crate::space::Point;
+
+/// Angle between two vertices of the arcs that are created when obstacles are inflated.
+const INFLATION_ARC_STEP: f64 = 0.2;
+
+/// Bounding box of a polygon in the R-tree. The data is the index of the polygon.
+type IndexedRectangle = GeomWithData<Rectangle<[f64; 2]>, usize>;
+
+/// Collision checker for obstacles given as `geo` polygons. Holes of the polygons are free space.
+/// The bounding boxes of all polygons are stored in an R-tree, so only polygons close to a query are checked exactly.
+///
+/// Obstacles are closed sets. A node on the boundary of a polygon and an edge touching a polygon are in collision.
+#[derive(Debug, Clone, Default)]
+pub struct PolygonCollisionChecker {
+    polygons: Vec<Polygon<f64>>,
+    tree: RTree<IndexedRectangle>,
+}
+
+impl PolygonCollisionChecker {
+    /// Creates a collision checker with the given obstacles. The R-tree is bulk loaded.
+    pub fn new(polygons: Vec<Polygon<f64>>) -> Self {
+        let rectangles: Vec<IndexedRectangle> = polygons
+            .iter()
+            .enumerate()
+            .filter_map(|(index, polygon)| Self::bounding_rectangle(polygon, index))
+            .collect();
+        PolygonCollisionChecker {
+            polygons,
+            tree: RTree::bulk_load(rectangles),
+        }
+    }
+
+    pub fn new_box(polygons: Vec<Polygon<f64>>) -> Box<dyn CollisionChecker> {
+        Box::new(PolygonCollisionChecker::new(polygons))
+    }
+
+    /// Creates a collision checker with all polygons of the multipolygon as obstacles.
+    pub fn from_multi_polygon(multi_polygon: MultiPolygon<f64>) -> Self {
+        PolygonCollisionChecker::new(multi_polygon.0)
+    }
+
+    /// Adds a single obstacle.
+    pub fn add_polygon(&mut self, polygon: Polygon<f64>) {
+        if let Some(rectangle) = Self::bounding_rectangle(&polygon, self.polygons.len()) {
+            self.tree.insert(rectangle);
+        }
+        self.polygons.push(polygon);
+    }
+
+    /// Adds all polygons of the multipolygon as obstacles.
+    pub fn add_multi_polygon(&mut self, multi_polygon: MultiPolygon<f64>) {
+        for polygon in multi_polygon {
+            self.add_polygon(polygon);
+        }
+    }
+
+    /// Returns all obstacles.
+    pub fn get_polygons(&self) -> &[Polygon<f64>] {
+        &self.polygons
+    }
+
+    /// Returns a collision checker with all obstacles grown by the distance (Minkowski sum with a disc).
+    /// A point checked against the inflated obstacles collides, if a disc with this radius collides with the original obstacles.
+    /// The arcs of the inflated obstacles are approximated by vertices. They are placed slightly outwards, so the approximation is conservative.
+    pub fn inflate(&self, distance: f64) -> PolygonCollisionChecker {
+        let distance: f64 = distance / (INFLATION_ARC_STEP / 2f64).cos();
+        let style = || BufferStyle::new(distance).line_join(LineJoin::Round(INFLATION_ARC_STEP));
+        PolygonCollisionChecker::new(
+            self.polygons
+                .iter()
+                .flat_map(|polygon| polygon.buffer_with_style(style()))
+                .collect(),
+        )
+    }
+
+    /// Returns true, if the polygon touches or overlaps any obstacle.
+    pub fn is_polygon_colliding(&self, polygon: &Polygon<f64>) -> bool {
+        let Some(rect) = polygon.bounding_rect() else {
+            return false;
+        };
+        let envelope: AABB<[f64; 2]> =
+            AABB::from_corners([rect.min().x, rect.min().y], [rect.max().x, rect.max().y]);
+        self.tree
+            .locate_in_envelope_intersecting(&envelope)
+            .any(|rectangle| self.polygons[rectangle.data].intersects(polygon))
+    }
+
+    /// Returns the distance from the point to the closest ring of the polygon, including the rings of the holes.
+    fn distance_to_boundary(polygon: &Polygon<f64>, point: &geo::Point<f64>) -> f64 {
+        std::iter::once(polygon.exterior())
+            .chain(polygon.interiors())
+            .map(|ring| Euclidean.distance(point, ring))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Returns the bounding box of the polygon for the R-tree. Empty polygons have none and can never collide.
+    fn bounding_rectangle(polygon: &Polygon<f64>, index: usize) -> Option<IndexedRectangle> {
+        let rect = polygon.bounding_rect()?;
+        let rectangle: Rectangle<[f64; 2]> =
+            Rectangle::from_corners([rect.min().x, rect.min().y], [rect.max().x, rect.max().y]);
+        Some(GeomWithData::new(rectangle, index))
+    }
+}
+
+impl CollisionChecker for PolygonCollisionChecker {
+    /// Does nothing
+    /// Return
+    ///     true: always
+    fn init(&self) -> bool {
+        true
+    }
+
+    /// Checks the edge against every polygon whose bounding box intersects the bounding box of the edge.
+    fn is_edge_colliding(&self, begin: &Point, end: &Point) -> bool {
+        let envelope: AABB<[f64; 2]> =
+            AABB::from_corners([begin.get_x(), begin.get_y()], [end.get_x(), end.get_y()]);
+        let line: Line<f64> = Line::new((begin.get_x(), begin.get_y()), (end.get_x(), end.get_y()));
+        self.tree
+            .locate_in_envelope_intersecting(&envelope)
+            .any(|rectangle| self.polygons[rectangle.data].intersects(&line))
+    }
+
+    /// Checks the node against every polygon whose bounding box contains the node.
+    fn is_node_colliding(&self, node: &Point) -> bool {
+        let point: geo::Point<f64> = geo::Point::new(node.get_x(), node.get_y());
+        self.tree
+            .locate_all_at_point(&[node.get_x(), node.get_y()])
+            .any(|rectangle| self.polygons[rectangle.data].intersects(&point))
+    }
+
+    /// Returns the exact distance to the closest obstacle.
+    /// Inside obstacles it is the negative distance to the boundary of the deepest obstacle.
+    /// Polygons are visited in the order of the distance to their bounding boxes, until no polygon can be closer.
+    fn clearance(&self, node: &Point) -> Option<f64> {
+        let point: geo::Point<f64> = geo::Point::new(node.get_x(), node.get_y());
+        let depth: f64 = self
+            .tree
+            .locate_all_at_point(&[node.get_x(), node.get_y()])
+            .map(|rectangle| &self.polygons[rectangle.data])
+            .filter(|polygon| polygon.intersects(&point))
+            .map(|polygon| Self::distance_to_boundary(polygon, &point))
+            .fold(f64::NEG_INFINITY, f64::max);
+        if depth > f64::NEG_INFINITY {
+            return Some(-depth);
+        }
+
+        let mut clearance: f64 = f64::INFINITY;
+        for (rectangle, distance_2) in self
+            .tree
+            .nearest_neighbor_iter_with_distance_2(&[node.get_x(), node.get_y()])
+        {
+            if distance_2.sqrt() >= clearance {
+                break;
+            }
+            clearance = clearance.min(Euclidean.distance(&point, &self.polygons[rectangle.data]));
+        }
+        Some(clearance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PolygonCollisionChecker;
+    use crate::collision_checker::CollisionChecker;
+    use crate::space::Point;
+    use geo::{polygon, MultiPolygon, Polygon};
+
+    /// Square between (1, 1) and (4, 4) with a hole between (2, 2) and (3, 3).
+    fn square_with_hole() -> Polygon<f64> {
+        polygon!(
+            exterior: [(x: 1., y: 1.), (x: 4., y: 1.), (x: 4., y: 4.), (x: 1., y: 4.)],
+            interiors: [[(x: 2., y: 2.), (x: 3., y: 2.), (x: 3., y: 3.), (x: 2., y: 3.)]],
+        )
+    }
+
+    fn unit_square(x: f64, y: f64) -> Polygon<f64> {
+        polygon![(x: x, y: y), (x: x + 1., y: y), (x: x + 1., y: y + 1.), (x: x, y: y + 1.)]
+    }
+
+    #[test]
+    fn test_polygon_node() {
+        let cc: PolygonCollisionChecker = PolygonCollisionChecker::new(vec![square_with_hole()]);
+        assert!(cc.init());
+        assert!(cc.is_node_colliding(&Point::new(1.5, 1.5)));
+        assert!(!cc.is_node_colliding(&Point::new(0.5, 0.5)));
+        assert!(!cc.is_node_colliding(&Point::new(2.5, 2.5)));
+        assert!(!cc.is_node_colliding(&Point::new(5f64, 5f64)));
+    }
+
+    // Test that nodes on the outer boundary and on the boundary of a hole are in collision.
+    #[test]
+    fn test_polygon_node_on_boundary() {
+        let cc: PolygonCollisionChecker = PolygonCollisionChecker::new(vec![square_with_hole()]);
+        assert!(cc.is_node_colliding(&Point::new(1f64, 2.5)));
+        assert!(cc.is_node_colliding(&Point::new(4f64, 4f64)));
+        assert!(cc.is_node_colliding(&Point::new(2f64, 2.5)));
+    }
+
+    #[test]
+    fn test_polygon_edge() {
+        let cc: PolygonCollisionChecker = PolygonCollisionChecker::new(vec![square_with_hole()]);
+        assert!(cc.is_edge_colliding(&Point::new(0f64, 0f64), &Point::new(5f64, 5f64)));
+        assert!(cc.is_edge_colliding(&Point::new(0f64, 2.5), &Point::new(2.5, 2.5)));
+        assert!(!cc.is_edge_colliding(&Point::new(0f64, 0f64), &Point::new(5f64, 0f64)));
+        assert!(!cc.is_edge_colliding(&Point::new(0f64, 0f64), &Point::new(0f64, 5f64)));
+    }
+
+    // Test that an edge inside a hole is free, but touching the hole or the outer boundary is a collision.
+    #[test]
+    fn test_polygon_edge_touching() {
+        let cc: PolygonCollisionChecker = PolygonCollisionChecker::new(vec![square_with_hole()]);
+        assert!(!cc.is_edge_colliding(&Point::new(2.2, 2.2), &Point::new(2.8, 2.8)));
+        assert!(cc.is_edge_colliding(&Point::new(2.5, 2.5), &Point::new(3f64, 2.5)));
+        assert!(cc.is_edge_colliding(&Point::new(0f64, 1f64), &Point::new(1f64, 1f64)));
+        assert!(cc.is_edge_colliding(&Point::new(0f64, 2f64), &Point::new(2f64, 0f64)));
+    }
+
+    // Test that an edge passing between two obstacles is free, although it intersects both bounding boxes.
+    #[test]
+    fn test_polygon_edge_between_obstacles() {
+        let triangle_low: Polygon<f64> = polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 1.)];
+        let triangle_high: Polygon<f64> = polygon![(x: 0., y: 1.), (x: 0., y: 2.), (x: 2., y: 2.)];
+        let cc: PolygonCollisionChecker =
+            PolygonCollisionChecker::new(vec![triangle_low, triangle_high]);
+        assert!(!cc.is_edge_colliding(&Point::new(0.1, 0.5), &Point::new(1.9, 1.5)));
+        assert!(cc.is_edge_colliding(&Point::new(0.1, 0.5), &Point::new(1.9, 0.5)));
+    }
+
+    #[test]
+    fn test_multi_polygon() {
+        let multi_polygon: MultiPolygon<f64> =
+            MultiPolygon::new(vec![unit_square(0f64, 0f64), unit_square(5f64, 5f64)]);
+        let mut cc: PolygonCollisionChecker =
+            PolygonCollisionChecker::from_multi_polygon(multi_polygon);
+        assert!(cc.is_node_colliding(&Point::new(0.5, 0.5)));
+        assert!(cc.is_node_colliding(&Point::new(5.5, 5.5)));
+        assert!(!cc.is_node_colliding(&Point::new(3f64, 3f64)));
+
+        cc.add_multi_polygon(MultiPolygon::new(vec![unit_square(2.5, 2.5)]));
+        assert!(cc.is_node_colliding(&Point::new(3f64, 3f64)));
+        assert_eq!(cc.get_polygons().len(), 3);
+    }
+
+    // Test that the R-tree gives the same results as checking every polygon on a large map.
+    #[test]
+    fn test_polygon_many_obstacles() {
+        use geo::{Intersects, Line};
+
+        let polygons: Vec<Polygon<f64>> = (0..50)
+            .flat_map(|i| (0..50).map(move |j| unit_square(2f64 * i as f64, 2f64 * j as f64)))
+            .collect();
+        let cc: PolygonCollisionChecker = PolygonCollisionChecker::new(polygons.clone());
+
+        for k in 0..200 {
+            let begin: Point = Point::new((k * 37 % 100) as f64 + 0.3, (k * 53 % 100) as f64 + 1.5);
+            let end: Point = Point::new((k * 11 % 100) as f64 + 1.5, (k * 29 % 100) as f64 + 0.7);
+            let point: geo::Point<f64> = geo::Point::new(begin.get_x(), begin.get_y());
+            let line: Line<f64> =
+                Line::new((begin.get_x(), begin.get_y()), (end.get_x(), end.get_y()));
+            assert_eq!(
+                cc.is_node_colliding(&begin),
+                polygons.iter().any(|p| p.intersects(&point))
+            );
+            assert_eq!(
+                cc.is_edge_colliding(&begin, &end),
+                polygons.iter().any(|p| p.intersects(&line))
+            );
+        }
+    }
+
+    #[test]
+    fn test_empty_polygon_never_collides() {
+        let mut cc: PolygonCollisionChecker = PolygonCollisionChecker::default();
+        cc.add_polygon(Polygon::new(geo::LineString::new(vec![]), vec![]));
+        assert!(!cc.is_node_colliding(&Point::new(0f64, 0f64)));
+        assert!(!cc.is_edge_colliding(&Point::new(0f64, 0f64), &Point::new(1f64, 1f64)));
+    }
+
+    // Test that inflated obstacles cover all points within the distance, but not much more.
+    #[test]
+    fn test_inflate() {
+        let cc: PolygonCollisionChecker =
+            PolygonCollisionChecker::new(vec![unit_square(1f64, 1f64)]);
+        let inflated: PolygonCollisionChecker = cc.inflate(0.5);
+        assert!(inflated.is_node_colliding(&Point::new(0.51, 1.5)));
+        assert!(!inflated.is_node_colliding(&Point::new(0.45, 1.5)));
+        for i in 0..100 {
+            let angle: f64 = std::f64::consts::FRAC_PI_2 * i as f64 / 100.0;
+            let on_arc: Point = Point::new(2f64 + 0.5 * angle.cos(), 2f64 + 0.5 * angle.sin());
+            assert!(inflated.is_node_colliding(&on_arc));
+        }
+        assert!(!inflated.is_node_colliding(&Point::new(2.37, 2.37)));
+        assert_eq!(cc.get_polygons().len(), 1);
+    }
+
+    #[test]
+    fn test_polygon_colliding() {
+        let cc: PolygonCollisionChecker = PolygonCollisionChecker::new(vec![square_with_hole()]);
+        assert!(cc.is_polygon_colliding(&unit_square(0.5, 0.5)));
+        assert!(!cc.is_polygon_colliding(&unit_square(-1.5, -1.5)));
+        assert!(!cc
+            .is_polygon_colliding(&polygon![(x: 2.2, y: 2.2), (x: 2.8, y: 2.2), (x: 2.8, y: 2.8)]));
+    }
+
+    #[test]
+    fn test_clearance() {
+        let cc: PolygonCollisionChecker = PolygonCollisionChecker::new(vec![
+            square_with_hole(),
+            polygon![(x: 6., y: 1.), (x: 7., y: 1.), (x: 7., y: 2.), (x: 6., y: 2.)],
+        ]);
+        assert_eq!(cc.clearance(&Point::new(0f64, 1f64)), Some(1f64));
+        assert_eq!(cc.clearance(&Point::new(5.5, 1.5)), Some(0.5));
+        assert_eq!(cc.clearance(&Point::new(2.5, 2.25)), Some(0.25));
+        assert_eq!(cc.clearance(&Point::new(1f64, 2f64)), Some(0f64));
+        assert_eq!(cc.clearance(&Point::new(1.5, 2.5)), Some(-0.5));
+        assert_eq!(cc.clearance(&Point::new(6.5, 1.25)), Some(-0.25));
+        assert_eq!(
+            PolygonCollisionChecker::default().clearance(&Point::new(0f64, 0f64)),
+            Some(f64::INFINITY)
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt;
+use std::io;
+
+use crate::space::Point;
+
+/// Errors that can occur while setting up or solving a planning problem.
+/// The dimension of the start and goal defaults to 2D, like the one of `Point`.
+#[derive(Debug)]
+pub enum PlanningError<const N: usize = 2> {
+    /// A lower boundary is greater than the upper boundary.
+    InvalidBounds,
+    /// The start is inside an obstacle.
+    StartInCollision { start: Point<N> },
+    /// The goal is inside an obstacle.
+    GoalInCollision { goal: Point<N> },
+    /// The start is outside of the boundaries.
+    StartOutOfBounds { start: Point<N> },
+    /// The goal is outside of the boundaries.
+    GoalOutOfBounds { goal: Point<N> },
+    /// The planner terminated without connecting start and goal.
+    NoSolution,
+    /// The planner ran out of time without connecting start and goal.
+    Timeout,
+    /// The planner could not add a single node within the given number of consecutive sampling attempts.
+    /// Happens, if the free space is blocked completely or the boundaries have no area.
+    NoValidSamples { attempts: usize },
+    /// `solve` was called before a successful `setup`.
+    NotReady,
+    /// Reading or writing a file failed.
+    Io(io::Error),
+    /// A map could not be parsed as WKT.
+    InvalidWkt { message: String },
+    /// A map could not be parsed as GeoJSON.
+    InvalidGeoJson { message: String },
+    /// A map contains a geometry that is not an area, like a point or a line.
+    UnsupportedGeometry { geometry: &'static str },
+    /// A map contains a geometry that is not a valid obstacle, like a polygon without area.
+    InvalidGeometry { message: String },
+    /// A map does not contain any obstacle, so no boundaries can be derived from it.
+    EmptyMap,
+    /// A raster map or its metadata could not be read.
+    InvalidMap { message: String },
+    /// The trajectory of a dynamic obstacle is empty or not ordered by time.
+    InvalidTrajectory { message: String },
+    /// A state space could not be composed, e.g. because the sub-spaces have more axes than the compound state.
+    InvalidStateSpace { message: String },
+}
+
+impl<const N: usize> fmt::Display for PlanningError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanningError::InvalidBounds => {
+                write!(f, "lower boundaries must not exceed upper boundaries")
+            }
+            PlanningError::StartInCollision { start } => {
+                write!(f, "start {} is in collision", start.to_wkt())
+            }
+            PlanningError::GoalInCollision { goal } => {
+                write!(f, "goal {} is in collision", goal.to_wkt())
+            }
+            PlanningError::StartOutOfBounds { start } => {
+                write!(f, "start {} is outside of the boundaries", start.to_wkt())
+            }
+            PlanningError::GoalOutOfBounds { goal } => {
+                write!(f, "goal {} is outside of the boundaries", goal.to_wkt())
+            }
+            PlanningError::NoSolution => write!(f, "no solution was found"),
+            PlanningError::Timeout => write!(f, "no solution was found before the timeout"),
+            PlanningError::NoValidSamples { attempts } => {
+                write!(f, "no valid sample within {attempts} attempts")
+            }
+            PlanningError::NotReady => write!(f, "planning setup is not ready for solving"),
+            PlanningError::Io(error) => write!(f, "I/O error: {error}"),
+            PlanningError::InvalidWkt { message } => write!(f, "invalid WKT: {message}"),
+            PlanningError::InvalidGeoJson { message } => write!(f, "invalid GeoJSON: {message}"),
+            PlanningError::UnsupportedGeometry { geometry } => {
+                write!(
+                    f,
+                    "unsupported geometry {geometry}, only areas can be obstacles"
+                )
+            }
+            PlanningError::InvalidGeometry { message } => write!(f, "invalid geometry: {message}"),
+            PlanningError::EmptyMap => write!(f, "map does not contain any obstacle"),
+            PlanningError::InvalidMap { message } => write!(f, "invalid map: {message}"),
+            PlanningError::InvalidTrajectory { message } => {
+                write!(f, "invalid trajectory: {message}")
+            }
+            PlanningError::InvalidStateSpace { message } => {
+                write!(f, "invalid state space: {message}")
+            }
+        }
+    }
+}
+
+impl<const N: usize> std::error::Error for PlanningError<N> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PlanningError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<const N: usize> From<io::Error> for PlanningError<N> {
+    fn from(error: io::Error) -> Self {
+        PlanningError::Io(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let error = PlanningError::StartInCollision {
+            start: Point::new(1f64, 2f64),
+        };
+        assert_eq!(error.to_string(), "start POINT(1 2) is in collision");
+        assert_eq!(
+            PlanningError::<2>::NoValidSamples { attempts: 10 }.to_string(),
+            "no valid sample within 10 attempts"
+        );
+    }
+
+    #[test]
+    fn test_io_source() {
+        use std::error::Error;
+
+        let error: PlanningError = io::Error::new(io::ErrorKind::NotFound, "missing").into();
+        assert!(matches!(error, PlanningError::Io(_)));
+        assert!(error.source().is_some());
+    }
+}
@@ -11,8 +11,14 @@ The main idea is to easily usable with various problems in different domains. It
 
 pub mod boundaries;
 pub mod collision_checker;
+pub mod error;
 pub mod optimizer;
 pub mod planner;
 pub mod problem;
+pub mod sampler;
 pub mod setup;
 pub mod space;
+pub mod state_space;
+
+#[cfg(test)]
+mod test_support;
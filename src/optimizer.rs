@@ -1,36 +1,39 @@
 use std::marker::PhantomData;
 
+use crate::collision_checker::CollisionChecker;
 use crate::space::Point;
+use crate::state_space::StateSpace;
 
 /// Every Custom Optimizer needs to be based on this trait.
-pub trait Optimizer {
+/// The dimension defaults to 2D. Optimizers for other configuration spaces implement `Optimizer<N>`.
+pub trait Optimizer<const N: usize = 2> {
     /// Returns a vector of triplets. Every consists of a start-node, end-node, and the calculated edge weight. Batch-wise weight calculation allows the Optimizer to use parallelism.
     ///
     /// ## Arguments
     /// A batch of edges on which he cost needs to be returned. A single edge is presented a pair of start-node and end-node. The batch is represented as a vector of pairs / edges.
-    fn get_edge_weight(&self, begin: Point, end: Point) -> (Point, Point, f64);
+    fn get_edge_weight(&self, begin: Point<N>, end: Point<N>) -> (Point<N>, Point<N>, f64);
 
     /// The init function allows the Optimizer to execute code before running. This function is called only once and before all the other functions are called. This allows setup function like reading a file or connecting to a Database.
     fn init(&mut self) -> bool;
 }
 
-/// Simple Optimizer used for examples and testing.
+/// Simple Optimizer used for examples and testing. Works in any dimension.
 #[derive(Debug, Copy, Clone)]
 pub struct DefaultOptimizer {
     pub phantom: PhantomData<f64>,
 }
 
 impl DefaultOptimizer {
-    pub fn new_box() -> Box<dyn Optimizer> {
+    pub fn new_box<const N: usize>() -> Box<dyn Optimizer<N>> {
         Box::new(DefaultOptimizer {
             phantom: PhantomData,
         })
     }
 }
 
-impl Optimizer for DefaultOptimizer {
-    // Cost is based on the distance in 2D. Which is basically just Pythagoras.
-    fn get_edge_weight(&self, begin: Point, end: Point) -> (Point, Point, f64) {
+impl<const N: usize> Optimizer<N> for DefaultOptimizer {
+    // Cost is the euclidean distance. Which is basically just Pythagoras.
+    fn get_edge_weight(&self, begin: Point<N>, end: Point<N>) -> (Point<N>, Point<N>, f64) {
         let cost: f64 = begin.euclidean_distance(&end);
         (begin, end, cost)
     }
@@ -41,18 +44,102 @@ impl Optimizer for DefaultOptimizer {
     }
 }
 
+/// Cost is the distance in the state space, e.g. including the rotation in `SE2StateSpace`.
+/// Planners that take their edge costs from the optimizer, like PRM* and RRT*, use it to plan with the metric of their state space.
+pub struct StateSpaceOptimizer<const N: usize = 2> {
+    state_space: Box<dyn StateSpace<N>>,
+}
+
+impl<const N: usize> StateSpaceOptimizer<N> {
+    pub fn new(state_space: Box<dyn StateSpace<N>>) -> Self {
+        StateSpaceOptimizer { state_space }
+    }
+
+    pub fn new_box(state_space: Box<dyn StateSpace<N>>) -> Box<dyn Optimizer<N>> {
+        Box::new(StateSpaceOptimizer::new(state_space))
+    }
+}
+
+impl<const N: usize> Optimizer<N> for StateSpaceOptimizer<N> {
+    fn get_edge_weight(&self, begin: Point<N>, end: Point<N>) -> (Point<N>, Point<N>, f64) {
+        let cost: f64 = self.state_space.distance(&begin, &end);
+        (begin, end, cost)
+    }
+
+    /// Does not do anything. Returns always true without any condition.
+    fn init(&mut self) -> bool {
+        true
+    }
+}
+
+/// Prefers edges that keep a safety margin to obstacles. Needs a collision checker that supports clearance queries.
+/// The cost is the length of the edge, where every piece is weighted by `1 + weight / clearance`.
+/// - weight: Clearance at which a piece costs twice its length
+/// - resolution: Length of the pieces, at whose middle the clearance is queried
+/// - min_clearance: Lower limit of the clearance, so pieces at or inside obstacles have a finite cost
+pub struct ClearanceOptimizer<const N: usize = 2> {
+    collision_checker: Box<dyn CollisionChecker<N>>,
+    pub weight: f64,
+    pub resolution: f64,
+    pub min_clearance: f64,
+}
+
+impl<const N: usize> ClearanceOptimizer<N> {
+    pub fn new(collision_checker: Box<dyn CollisionChecker<N>>, weight: f64) -> Self {
+        ClearanceOptimizer {
+            collision_checker,
+            weight,
+            resolution: 0.1f64,
+            min_clearance: 0.01f64,
+        }
+    }
+
+    pub fn new_box(
+        collision_checker: Box<dyn CollisionChecker<N>>,
+        weight: f64,
+    ) -> Box<dyn Optimizer<N>> {
+        Box::new(ClearanceOptimizer::new(collision_checker, weight))
+    }
+}
+
+impl<const N: usize> Optimizer<N> for ClearanceOptimizer<N> {
+    /// Sums the weighted pieces of the edge. An unknown clearance counts as infinite, so the piece costs its length.
+    fn get_edge_weight(&self, begin: Point<N>, end: Point<N>) -> (Point<N>, Point<N>, f64) {
+        let length: f64 = begin.euclidean_distance(&end);
+        let pieces: f64 = (length / self.resolution).ceil().max(1f64);
+        let cost: f64 = (0..pieces as usize)
+            .map(|piece| {
+                let middle: Point<N> = begin.interpolate(&end, (piece as f64 + 0.5) / pieces);
+                let clearance: f64 = self
+                    .collision_checker
+                    .clearance(&middle)
+                    .unwrap_or(f64::INFINITY)
+                    .max(self.min_clearance);
+                length / pieces * (1f64 + self.weight / clearance)
+            })
+            .sum();
+        (begin, end, cost)
+    }
+
+    /// Initializes the collision checker.
+    fn init(&mut self) -> bool {
+        self.collision_checker.init()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::marker::PhantomData;
 
-    use super::{DefaultOptimizer, Optimizer};
+    use super::{ClearanceOptimizer, DefaultOptimizer, Optimizer, StateSpaceOptimizer};
+    use crate::collision_checker::{NaiveCollisionChecker, PolygonCollisionChecker};
 
     #[test]
     fn test_default_init() {
         let mut optimizer: DefaultOptimizer = DefaultOptimizer {
             phantom: PhantomData,
         };
-        assert!(optimizer.init());
+        assert!(Optimizer::<2>::init(&mut optimizer));
     }
 
     #[test]
@@ -82,4 +169,63 @@ mod tests {
         let cost: f64 = optimizer.get_edge_weight(a, b).2;
         assert_eq!(1f64, cost);
     }
+
+    #[test]
+    fn test_clearance_edge_weight_free_space() {
+        use crate::space::Point;
+
+        let mut optimizer: ClearanceOptimizer =
+            ClearanceOptimizer::new(NaiveCollisionChecker::new_box(), 1f64);
+        assert!(optimizer.init());
+        let cost: f64 = optimizer
+            .get_edge_weight(Point::new(0f64, 0f64), Point::new(3f64, 4f64))
+            .2;
+        assert!((cost - 5f64).abs() < 1e-9);
+    }
+
+    // Test that an edge close to an obstacle costs more than an edge of the same length far away.
+    #[test]
+    fn test_clearance_edge_weight() {
+        use crate::space::Point;
+        use geo::polygon;
+
+        let cc = PolygonCollisionChecker::new_box(vec![polygon![
+            (x: 0., y: 0.),
+            (x: 4., y: 0.),
+            (x: 4., y: 1.),
+            (x: 0., y: 1.)
+        ]]);
+        let optimizer: ClearanceOptimizer = ClearanceOptimizer::new(cc, 1f64);
+        let close: f64 = optimizer
+            .get_edge_weight(Point::new(0f64, 1.5), Point::new(4f64, 1.5))
+            .2;
+        let far: f64 = optimizer
+            .get_edge_weight(Point::new(0f64, 3f64), Point::new(4f64, 3f64))
+            .2;
+        assert!((close - 12f64).abs() < 1e-9);
+        assert!((far - 6f64).abs() < 1e-9);
+
+        let inside: f64 = optimizer
+            .get_edge_weight(Point::new(1f64, 0.5), Point::new(2f64, 0.5))
+            .2;
+        assert!((inside - 101f64).abs() < 1e-9);
+    }
+
+    // Test that the rotation is part of the cost in SE(2).
+    #[test]
+    fn test_state_space_edge_weight() {
+        use crate::space::Point;
+        use crate::state_space::SE2StateSpace;
+
+        let mut optimizer: StateSpaceOptimizer<3> =
+            StateSpaceOptimizer::new(SE2StateSpace::new_box(2f64));
+        assert!(optimizer.init());
+        let cost: f64 = optimizer
+            .get_edge_weight(
+                Point::from_coordinates([0f64, 0f64, 0f64]),
+                Point::from_coordinates([3f64, 0f64, 2f64]),
+            )
+            .2;
+        assert!((cost - 5f64).abs() < 1e-9);
+    }
 }
@@ -1,4 +1,11 @@
-use crate::{boundaries::Boundaries, collision_checker::CollisionChecker, space::Point};
+use crate::{
+    boundaries::Boundaries,
+    collision_checker::{CollisionChecker, CollisionStatistics},
+    error::PlanningError,
+    planner::termination::{TerminationCriteria, TerminationReason},
+    sampler::StateSampler,
+    space::Point,
+};
 
 /// Custom planners can use these traits to implement other algorithm for planning.
 ///
@@ -9,14 +16,38 @@ use crate::{boundaries::Boundaries, collision_checker::CollisionChecker, space::
 /// # Planner
 /// Defines a trait called `Planner` with generic type `T` that represents a continuous space.
 ///
-/// The trait provides several methods for setting the start and goal points, boundaries, and collision checker, as well as initializing the planner, solving the planning problem, and getting the solution cost.
-///
-pub trait Planner {
-    fn set_start(&mut self, start: Point);
-    fn set_goal(&mut self, goal: Point);
-    fn set_boundaries(&mut self, boundaries: Boundaries);
-    fn set_collision_checker(&mut self, cc: Box<dyn CollisionChecker>);
+/// The trait provides several methods for setting the start and goal points, boundaries, collision checker, and termination criteria, as well as initializing the planner, solving the planning problem, and getting the solution cost and path.
+/// The dimension of the configuration space defaults to 2D. Planners that work in any dimension implement `Planner<N>` for all `N`.
+pub trait Planner<const N: usize = 2> {
+    fn set_start(&mut self, start: Point<N>);
+    fn set_goal(&mut self, goal: Point<N>);
+    fn set_boundaries(&mut self, boundaries: Boundaries<N>);
+    fn set_collision_checker(&mut self, cc: Box<dyn CollisionChecker<N>>);
+
+    /// Returns the collision checker of the planner. Used to validate start and goal before planning.
+    fn get_collision_checker(&self) -> &dyn CollisionChecker<N>;
+
+    /// Replaces the sampling strategy of the planner. The default samples uniformly inside the boundaries.
+    fn set_sampler(&mut self, sampler: Box<dyn StateSampler<N>>);
+
+    /// Replaces the default termination criteria of the planner. The default stops once the graph reaches `config.max_size` nodes.
+    fn set_termination_criteria(&mut self, criteria: Box<dyn TerminationCriteria>);
     fn init(&mut self);
-    fn solve(&mut self);
+
+    /// Runs the planner until the termination criteria is met.
+    /// Returns an error, if the planner could not grow anymore.
+    fn solve(&mut self) -> Result<(), PlanningError<N>>;
     fn get_solution_cost(&self) -> f64;
+
+    /// Returns the solution as an ordered sequence of points from start to goal.
+    /// The vector is empty if no solution was found.
+    fn get_solution_path(&self) -> Vec<Point<N>>;
+
+    /// Returns why the last call to `solve` stopped. `None` if `solve` was not called yet.
+    fn get_termination_reason(&self) -> Option<TerminationReason>;
+
+    /// Returns the counters of the collision checker, e.g. if it is wrapped in a `CachingCollisionChecker`.
+    fn get_collision_statistics(&self) -> Option<CollisionStatistics> {
+        self.get_collision_checker().get_statistics()
+    }
 }
@@ -1,10 +1,13 @@
+use crate::error::PlanningError;
 use crate::space::Point;
 use std::fs::File;
 use std::io::Write;
 
 use num::Signed;
+use petgraph::algo::astar;
 use petgraph::dot::Dot;
-use petgraph::graph::{Graph, NodeIndex};
+use petgraph::graph::{EdgeReference, Graph, NodeIndex};
+use petgraph::visit::{EdgeFiltered, EdgeRef};
 use petgraph::Undirected;
 
 /// Returns true if there is an edge between the given nodes in the graph, false otherwise.
@@ -32,20 +35,17 @@ pub fn is_edge_already_in_graph<T: Copy + Clone + Signed + std::fmt::Debug>(
 ///
 /// * `graph` - A reference to the graph to be written to file.
 /// * `path` - The path to the file where the graph will be written.
-pub fn write_graph_to_file(graph: &Graph<Point, f64, Undirected>, path: &str) {
+///
+/// # Errors
+/// Returns `PlanningError::Io`, if the file could not be created or written.
+pub fn write_graph_to_file<const N: usize>(
+    graph: &Graph<Point<N>, f64, Undirected>,
+    path: &str,
+) -> Result<(), PlanningError<N>> {
     let output = format!("{:?}", Dot::with_config(&graph, &[]));
-    let mut file = match File::create(path) {
-        Ok(file) => file,
-        Err(_) => {
-            println!("Could not open file at: {path}");
-            return;
-        }
-    };
-
-    match file.write_all(output.as_bytes()) {
-        Err(_) => println!("Could not write the solution path to file! -> {path}"),
-        Ok(_) => println!("Write graph to file: {path}"),
-    };
+    let mut file = File::create(path)?;
+    file.write_all(output.as_bytes())?;
+    Ok(())
 }
 
 /// Prints the graph using the `Dot` format.
@@ -53,6 +53,108 @@ pub fn write_graph_to_file(graph: &Graph<Point, f64, Undirected>, path: &str) {
 /// # Arguments
 ///
 /// * `graph` - A reference to the graph to be printed.
-pub fn print_graph(graph: &Graph<Point, f64, Undirected>) {
+pub fn print_graph<const N: usize>(graph: &Graph<Point<N>, f64, Undirected>) {
     println!("{:?}", Dot::with_config(graph, &[]));
 }
+
+/// Maps a path of node indices back to the points stored in the graph.
+///
+/// # Arguments
+///
+/// * `graph` - A reference to the graph that contains the nodes.
+/// * `path` - The node indices of the path, ordered from start to goal.
+pub fn path_to_points<const N: usize>(
+    graph: &Graph<Point<N>, f64, Undirected>,
+    path: &[NodeIndex],
+) -> Vec<Point<N>> {
+    path.iter().map(|index| graph[*index]).collect()
+}
+
+/// Finds the shortest path from start to goal with A*. Returns the cost and the node indices of the path.
+///
+/// # Arguments
+///
+/// * `graph` - A reference to the graph to search.
+/// * `start` - The index of the start node.
+/// * `goal` - The index of the goal node.
+/// * `directed` - Only follow edges from the node they were added from to the other one, e.g. for asymmetric state spaces.
+pub fn find_path<const N: usize>(
+    graph: &Graph<Point<N>, f64, Undirected>,
+    start: NodeIndex,
+    goal: NodeIndex,
+    directed: bool,
+) -> Option<(f64, Vec<NodeIndex>)> {
+    let edges = EdgeFiltered::from_fn(graph, |edge: EdgeReference<'_, f64>| {
+        !directed || graph.edge_endpoints(edge.id()) == Some((edge.source(), edge.target()))
+    });
+    astar(
+        &edges,
+        start,
+        |finish| finish == goal,
+        |e| *e.weight(),
+        |_| f64::default(),
+    )
+}
+
+/// Volume of the unit ball in the given dimension. Used for the connection radius of the optimal planners.
+/// Uses the recursion V(d) = V(d - 2) * 2 * pi / d.
+pub fn unit_ball_volume(dimension: usize) -> f64 {
+    match dimension {
+        0 => 1f64,
+        1 => 2f64,
+        _ => unit_ball_volume(dimension - 2) * std::f64::consts::TAU / dimension as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_graph_to_file_reports_io_error() {
+        let graph: Graph<Point, f64, Undirected> = Graph::new_undirected();
+        let result = write_graph_to_file(&graph, "/non/existent/directory/graph.dot");
+        assert!(matches!(result, Err(PlanningError::Io(_))));
+    }
+
+    #[test]
+    fn test_write_graph_to_file() {
+        let mut graph: Graph<Point, f64, Undirected> = Graph::new_undirected();
+        let a = graph.add_node(Point::new(0f64, 0f64));
+        let b = graph.add_node(Point::new(1f64, 0f64));
+        graph.add_edge(a, b, 1f64);
+
+        let path = std::env::temp_dir().join("mpl_test_write_graph_to_file.dot");
+        write_graph_to_file(&graph, path.to_str().unwrap()).unwrap();
+        let content: String = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(content.contains("graph"));
+    }
+
+    // Test that a directed search only follows the edges in the direction they were added.
+    #[test]
+    fn test_find_path_directed() {
+        let mut graph: Graph<Point, f64, Undirected> = Graph::new_undirected();
+        let a = graph.add_node(Point::new(0f64, 0f64));
+        let b = graph.add_node(Point::new(1f64, 0f64));
+        let c = graph.add_node(Point::new(2f64, 0f64));
+        graph.add_edge(a, b, 1f64);
+        graph.add_edge(c, b, 1f64);
+
+        assert_eq!(find_path(&graph, a, c, false), Some((2f64, vec![a, b, c])));
+        assert_eq!(find_path(&graph, a, c, true), None);
+        assert_eq!(find_path(&graph, c, a, true), None);
+        graph.add_edge(b, c, 3f64);
+        assert_eq!(find_path(&graph, a, c, true), Some((4f64, vec![a, b, c])));
+    }
+
+    #[test]
+    fn test_unit_ball_volume() {
+        use std::f64::consts::PI;
+
+        assert_eq!(unit_ball_volume(1), 2f64);
+        assert!((unit_ball_volume(2) - PI).abs() < 1e-12);
+        assert!((unit_ball_volume(3) - 4f64 / 3f64 * PI).abs() < 1e-12);
+        assert!((unit_ball_volume(6) - PI.powi(3) / 6f64).abs() < 1e-12);
+    }
+}
@@ -1,5 +1,10 @@
 pub mod base_planner;
 pub mod graph_utils;
+pub mod nearest_neighbors;
 pub mod prm;
 pub mod prm_star;
 pub mod rrt;
+pub mod rrt_connect;
+pub mod rrt_star;
+pub mod space_time_rrt;
+pub mod termination;
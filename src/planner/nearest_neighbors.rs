@@ -0,0 +1,255 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+use std::iter::Peekable;
+
+use rstar::primitives::GeomWithData;
+use rstar::RTree;
+
+use crate::space::Point;
+use crate::state_space::StateSpace;
+
+/// Entry of the rtree. The key of the state space is the geometry, the state is the data.
+type Entry<const N: usize> = GeomWithData<[f64; N], Point<N>>;
+
+/// Entries of the rtree ordered by the squared Euclidean distance of their keys to one image of the query.
+type ImageIter<'a, const N: usize> = Peekable<Box<dyn Iterator<Item = (&'a Entry<N>, f64)> + 'a>>;
+
+/// Nearest neighbor structure of the planners. The states are stored with the keys of the state space in an rtree.
+/// The Euclidean distance between keys is a lower bound of the distance in the state space.
+/// So the rtree is searched in the order of the lower bound, and the states are returned in the order of their exact distance.
+#[derive(Debug, Default)]
+pub struct NearestNeighbors<const N: usize = 2> {
+    tree: RTree<Entry<N>>,
+}
+
+impl<const N: usize> NearestNeighbors<N> {
+    pub fn new() -> Self {
+        NearestNeighbors { tree: RTree::new() }
+    }
+
+    pub fn insert(&mut self, state_space: &dyn StateSpace<N>, state: Point<N>) {
+        self.tree
+            .insert(GeomWithData::new(state_space.get_key(&state), state));
+    }
+
+    /// Returns the number of stored states.
+    pub fn size(&self) -> usize {
+        self.tree.size()
+    }
+
+    /// Iterates over all stored states with their distance, ordered from the closest to the farthest.
+    ///
+    /// # Arguments
+    /// - `query`: State, whose key and key images are searched in the rtree
+    /// - `distance`: Exact distance of a stored state, e.g. from the stored state to the query.
+    ///   Must not be smaller than the Euclidean distance of the keys, like every distance of the state space.
+    pub fn iter_nearest<'a, F: Fn(&Point<N>) -> f64>(
+        &'a self,
+        state_space: &dyn StateSpace<N>,
+        query: &Point<N>,
+        distance: F,
+    ) -> NearestIter<'a, N, F> {
+        let images: Vec<ImageIter<'a, N>> = state_space
+            .get_key_images(state_space.get_key(query))
+            .into_iter()
+            .map(|image| {
+                let iter: Box<dyn Iterator<Item = (&'a Entry<N>, f64)> + 'a> =
+                    Box::new(self.tree.nearest_neighbor_iter_with_distance_2(&image));
+                iter.peekable()
+            })
+            .collect();
+        NearestIter {
+            images,
+            candidates: BinaryHeap::new(),
+            visited: HashSet::new(),
+            distance,
+        }
+    }
+
+    /// Returns the closest state and its distance. None, if no state is stored.
+    pub fn nearest<F: Fn(&Point<N>) -> f64>(
+        &self,
+        state_space: &dyn StateSpace<N>,
+        query: &Point<N>,
+        distance: F,
+    ) -> Option<(Point<N>, f64)> {
+        self.iter_nearest(state_space, query, distance).next()
+    }
+
+    /// Returns all states within the radius, ordered from the closest to the farthest.
+    pub fn within_distance<F: Fn(&Point<N>) -> f64>(
+        &self,
+        state_space: &dyn StateSpace<N>,
+        query: &Point<N>,
+        radius: f64,
+        distance: F,
+    ) -> Vec<(Point<N>, f64)> {
+        self.iter_nearest(state_space, query, distance)
+            .take_while(|(_, distance)| *distance <= radius)
+            .collect()
+    }
+}
+
+/// State with its exact distance. Ordered, so that the binary heap returns the closest first.
+struct Candidate<const N: usize> {
+    distance: f64,
+    state: Point<N>,
+}
+
+impl<const N: usize> PartialEq for Candidate<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<const N: usize> Eq for Candidate<N> {}
+
+impl<const N: usize> PartialOrd for Candidate<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for Candidate<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.total_cmp(&self.distance)
+    }
+}
+
+/// Iterator of `NearestNeighbors::iter_nearest`.
+/// A candidate is returned, once no state that was not looked at yet can be closer, because the lower bound of the next entry is larger.
+pub struct NearestIter<'a, const N: usize, F> {
+    images: Vec<ImageIter<'a, N>>,
+    candidates: BinaryHeap<Candidate<N>>,
+    visited: HashSet<[u64; N]>,
+    distance: F,
+}
+
+impl<const N: usize, F: Fn(&Point<N>) -> f64> Iterator for NearestIter<'_, N, F> {
+    type Item = (Point<N>, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let next_image: Option<(usize, f64)> = self
+                .images
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(image, iter)| iter.peek().map(|(_, distance_2)| (image, *distance_2)))
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            let lower_bound: f64 =
+                next_image.map_or(f64::INFINITY, |(_, distance_2)| distance_2.sqrt());
+            if self
+                .candidates
+                .peek()
+                .is_some_and(|candidate| candidate.distance <= lower_bound)
+            {
+                return self
+                    .candidates
+                    .pop()
+                    .map(|candidate| (candidate.state, candidate.distance));
+            }
+            let (entry, _) = self.images[next_image?.0].next()?;
+            let state: Point<N> = entry.data;
+            if self
+                .visited
+                .insert(state.get_coordinates().map(f64::to_bits))
+            {
+                self.candidates.push(Candidate {
+                    distance: (self.distance)(&state),
+                    state,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::{EuclideanStateSpace, SE2StateSpace};
+    use std::f64::consts::PI;
+
+    // Test that the order is the same as the one of the rtree for the Euclidean state space.
+    #[test]
+    fn test_euclidean_order() {
+        let space: EuclideanStateSpace = EuclideanStateSpace {};
+        let mut nn: NearestNeighbors = NearestNeighbors::new();
+        for (x, y) in [(0.0, 0.0), (3.0, 0.0), (1.0, 1.0), (0.0, 2.0)] {
+            nn.insert(&space, Point::new(x, y));
+        }
+        assert_eq!(nn.size(), 4);
+
+        let query: Point = Point::new(0.0, 0.5);
+        let distances: Vec<f64> = nn
+            .iter_nearest(&space, &query, |state| state.euclidean_distance(&query))
+            .map(|(_, distance)| distance)
+            .collect();
+        assert_eq!(distances.len(), 4);
+        assert!(distances.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(distances[0], 0.5);
+
+        let nearest: Option<(Point, f64)> =
+            nn.nearest(&space, &query, |state| state.euclidean_distance(&query));
+        assert_eq!(nearest.map(|(state, _)| state), Some(Point::new(0.0, 0.0)));
+        assert_eq!(
+            nn.within_distance(&space, &query, 1.5, |state| state
+                .euclidean_distance(&query))
+                .len(),
+            3
+        );
+    }
+
+    // Test that a heading across PI is found as nearest neighbor, and every state is returned only once.
+    #[test]
+    fn test_se2_wrap_around() {
+        let space: SE2StateSpace = SE2StateSpace::default();
+        let mut nn: NearestNeighbors<3> = NearestNeighbors::new();
+        let across: Point<3> = Point::from_coordinates([0.0, 0.0, -PI + 0.1]);
+        let straight: Point<3> = Point::from_coordinates([0.0, 0.0, PI - 1.0]);
+        nn.insert(&space, across);
+        nn.insert(&space, straight);
+
+        let query: Point<3> = Point::from_coordinates([0.0, 0.0, PI - 0.1]);
+        let result: Vec<(Point<3>, f64)> = nn
+            .iter_nearest(&space, &query, |state| space.distance(state, &query))
+            .collect();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].0, across);
+        assert!((result[0].1 - 0.2).abs() < 1e-9);
+        assert_eq!(result[1].0, straight);
+    }
+
+    // Test that joint angles on both sides of the seam are neighbors.
+    #[test]
+    fn test_joint_wrap_around() {
+        use crate::boundaries::{Boundaries, Topology};
+        use crate::state_space::JointStateSpace;
+
+        let mut boundaries: Boundaries = Boundaries::new(-PI, PI, -PI, PI);
+        boundaries.set_topology([Topology::Circular; 2]);
+        let space: JointStateSpace = JointStateSpace::new(&boundaries);
+        let mut nn: NearestNeighbors = NearestNeighbors::new();
+        let across: Point = Point::new(-PI + 0.1, -PI + 0.1);
+        nn.insert(&space, across);
+        nn.insert(&space, Point::new(1.0, 1.0));
+        nn.insert(&space, Point::new(PI - 1.0, PI - 1.0));
+
+        let query: Point = Point::new(PI - 0.1, PI - 0.1);
+        let result: Vec<(Point, f64)> = nn
+            .iter_nearest(&space, &query, |state| space.distance(state, &query))
+            .collect();
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].0, across);
+        assert!((result[0].1 - 0.2 * 2f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty() {
+        let space: EuclideanStateSpace = EuclideanStateSpace {};
+        let nn: NearestNeighbors = NearestNeighbors::default();
+        let query: Point = Point::new(0.0, 0.0);
+        assert!(nn
+            .nearest(&space, &query, |state| state.euclidean_distance(&query))
+            .is_none());
+    }
+}
@@ -1,25 +1,33 @@
 use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::time::Instant;
 
-use petgraph::algo::astar;
 use petgraph::graph::{Graph, NodeIndex};
 use petgraph::Undirected;
-use rstar::RTree;
 
 use crate::boundaries::Boundaries;
 use crate::collision_checker::{CollisionChecker, NaiveCollisionChecker};
+use crate::error::PlanningError;
 use crate::planner::base_planner::Planner;
 use crate::planner::graph_utils as pg;
+use crate::planner::nearest_neighbors::NearestNeighbors;
+use crate::planner::termination::{
+    NodeLimit, PlannerProgress, TerminationCriteria, TerminationReason,
+};
+use crate::sampler::{StateSampler, UniformSampler};
 use crate::space::Point;
+use crate::state_space::{EuclideanStateSpace, StateSpace};
 
 /// # Holds configuration parameters for PRM*
 /// It does configure:
 /// - default_nearest_neighbors: Limits the number of nodes that are used to calculate motionCost to the n closest ones
 /// - max_size: Limits the number of Nodes in the graph before termination of the algrithm
+/// - max_sampling_attempts: Limits the number of consecutive failed attempts to add a node before the planner gives up
 pub struct Config {
     pub default_nearest_neighbors: u8,
     pub max_size: usize,
     pub n_grow_graph_between_checks: usize,
+    pub max_sampling_attempts: usize,
 }
 
 impl Default for Config {
@@ -28,6 +36,7 @@ impl Default for Config {
             default_nearest_neighbors: 10u8,
             max_size: 32usize,
             n_grow_graph_between_checks: 10usize,
+            max_sampling_attempts: 1000usize,
         }
     }
 }
@@ -41,51 +50,77 @@ impl Default for Config {
 /// # Source / Credits
 /// Kavraki, L. E.; Svestka, P.; Latombe, J.-C.; Overmars, M. H. (1996), "Probabilistic roadmaps for path planning in high-dimensional configuration spaces", IEEE Transactions on Robotics and Automation, 12 (4): 566–580, doi:10.1109/70.508439
 ///
-pub struct PRM {
-    pub start: Point,
-    pub goal: Point,
-    pub boundaries: Boundaries,
-    pub graph: Graph<Point, f64, Undirected>,
+/// Plans in any dimension `N`, e.g. `PRM<3>` for a drone. The default is 2D.
+/// Distances and motions are defined by the state space, which is Euclidean by default.
+pub struct PRM<const N: usize = 2> {
+    pub start: Point<N>,
+    pub goal: Point<N>,
+    pub boundaries: Boundaries<N>,
+    pub graph: Graph<Point<N>, f64, Undirected>,
     pub solution: Option<(f64, Vec<NodeIndex>)>,
     pub is_solved: bool,
-    pub collision_checker: Box<dyn CollisionChecker>,
-    tree: RTree<[f64; 2]>,
+    pub collision_checker: Box<dyn CollisionChecker<N>>,
+    pub sampler: Box<dyn StateSampler<N>>,
+    pub state_space: Box<dyn StateSpace<N>>,
+    tree: NearestNeighbors<N>,
     index_node_lookup: HashMap<String, NodeIndex>,
+    termination_criteria: Option<Box<dyn TerminationCriteria>>,
+    termination_reason: Option<TerminationReason>,
     pub config: Config,
 }
 
-impl Planner for PRM {
-    fn set_start(&mut self, start: Point) {
+impl<const N: usize> Planner<N> for PRM<N> {
+    fn set_start(&mut self, start: Point<N>) {
         self.start = start;
     }
 
-    fn set_goal(&mut self, goal: Point) {
+    fn set_goal(&mut self, goal: Point<N>) {
         self.goal = goal;
     }
 
-    fn set_boundaries(&mut self, boundaries: Boundaries) {
+    fn set_boundaries(&mut self, boundaries: Boundaries<N>) {
         self.boundaries = boundaries;
     }
 
-    fn set_collision_checker(&mut self, cc: Box<dyn CollisionChecker>) {
+    fn set_collision_checker(&mut self, cc: Box<dyn CollisionChecker<N>>) {
         self.collision_checker = cc;
     }
 
+    fn get_collision_checker(&self) -> &dyn CollisionChecker<N> {
+        self.collision_checker.as_ref()
+    }
+
+    fn set_sampler(&mut self, sampler: Box<dyn StateSampler<N>>) {
+        self.sampler = sampler;
+    }
+
+    fn set_termination_criteria(&mut self, criteria: Box<dyn TerminationCriteria>) {
+        self.termination_criteria = Some(criteria);
+    }
+
     fn init(&mut self) {
+        self.start = self.state_space.enforce_bounds(self.start);
+        self.goal = self.state_space.enforce_bounds(self.goal);
         self.add_node(self.start);
         self.add_node(self.goal);
     }
 
-    fn solve(&mut self) {
+    fn solve(&mut self) -> Result<(), PlanningError<N>> {
+        let start_time: Instant = Instant::now();
+        let mut iterations: usize = 0;
+        self.termination_reason = None;
+        if let Some(criteria) = &mut self.termination_criteria {
+            criteria.reset();
+        }
         loop {
             for _ in 0..self.config.n_grow_graph_between_checks {
-                let added_node: Point = self.add_random_node();
+                let added_node: Point<N> = self.add_random_node()?;
                 self.connect_node_to_graph(added_node);
             }
-            self.check_solution();
-            if self.is_termination_criteria_met() {
-                println!("Termination Criteria met");
-                break;
+            iterations += self.config.n_grow_graph_between_checks;
+            self.check_solution()?;
+            if self.is_termination_criteria_met(iterations, start_time) {
+                return Ok(());
             }
         }
     }
@@ -99,11 +134,24 @@ impl Planner for PRM {
             Some((cost, _)) => *cost,
         }
     }
+
+    /// Returns the points of the solution path from start to goal.
+    /// - empty: No solution was found
+    fn get_solution_path(&self) -> Vec<Point<N>> {
+        match &self.solution {
+            None => Vec::new(),
+            Some((_, path)) => pg::path_to_points(&self.graph, path),
+        }
+    }
+
+    fn get_termination_reason(&self) -> Option<TerminationReason> {
+        self.termination_reason
+    }
 }
 
-impl PRM {
+impl<const N: usize> PRM<N> {
     /// Standard constructor
-    pub fn new(collision_checker: Box<dyn CollisionChecker>) -> Self {
+    pub fn new(collision_checker: Box<dyn CollisionChecker<N>>) -> Self {
         PRM {
             start: Point::default(),
             goal: Point::default(),
@@ -112,12 +160,22 @@ impl PRM {
             solution: None,
             is_solved: false,
             collision_checker,
-            tree: RTree::new(),
+            sampler: UniformSampler::new_box(),
+            state_space: EuclideanStateSpace::new_box(),
+            tree: NearestNeighbors::new(),
             index_node_lookup: HashMap::new(),
+            termination_criteria: None,
+            termination_reason: None,
             config: Config::default(),
         }
     }
 
+    /// Replaces the state space, e.g. by `SE2StateSpace` for vehicles with a heading.
+    /// Has to be set before `init`, because the nodes are stored with the keys of the state space.
+    pub fn set_state_space(&mut self, state_space: Box<dyn StateSpace<N>>) {
+        self.state_space = state_space;
+    }
+
     /// Adds a node to the graph, lookup for nodeindex to point.wkt, and the rtree.
     ///
     /// # Arguments
@@ -131,7 +189,7 @@ impl PRM {
     /// 1. Adds the `node` to the graph using the `add_node` method of the `graph` object.
     /// 2. Inserts a mapping between the WKT representation of the `node` and its index in the lookup table using the `insert` method of the `index_node_lookup` hashmap.
     /// 3. Inserts the coordinates of the `node` into the tree data structure using the `insert` method of the `tree`.
-    fn add_node(&mut self, node: Point) {
+    fn add_node(&mut self, node: Point<N>) {
         if self.collision_checker.is_node_colliding(&node) {
             return;
         }
@@ -143,26 +201,39 @@ impl PRM {
             return;
         }
 
+        self.insert_node(node);
+    }
+
+    /// Inserts a node into the graph, the lookup and the rtree without any checks.
+    /// The caller has to make sure, that the node is not in collision and not already in the graph.
+    fn insert_node(&mut self, node: Point<N>) {
         let index = self.graph.add_node(node);
         self.index_node_lookup
             .insert(node.to_wkt().to_string(), index);
-        self.tree.insert([node.get_x(), node.get_y()]);
+        self.tree.insert(self.state_space.as_ref(), node);
     }
 
     /// Generates a random node and adds it to the graph, if:
     /// - It is not in collision
     /// - It is not already in the graph
     ///
-    /// This method generates a candidate node using the `generate_random_configuration` method of the `boundaries` object.
+    /// This method generates a candidate node using the `sampler`. Attempts without a candidate are skipped.
     /// It then checks if the candidate node collides with any existing nodes using the `is_node_colliding` method of the `collision_checker` object.
-    /// If there is a collision, it continues to the next iteration of the loop.
+    /// If there is a collision, it continues to the next attempt.
     /// If there is no collision, it checks if the candidate node already exists in the `index_node_lookup` map.
-    ///     If it does, it continues to the next iteration.
+    ///     If it does, it continues to the next attempt.
     ///     If it doesn't, it adds the candidate node to the data structure and returns it.
     ///
-    fn add_random_node(&mut self) -> Point {
-        loop {
-            let candidate: Point = self.boundaries.generate_random_configuration();
+    /// Gives up with `PlanningError::NoValidSamples` after `config.max_sampling_attempts` attempts.
+    fn add_random_node(&mut self) -> Result<Point<N>, PlanningError<N>> {
+        for _ in 0..self.config.max_sampling_attempts {
+            let candidate: Point<N> = match self
+                .sampler
+                .sample(&mut self.boundaries, self.collision_checker.as_ref())
+            {
+                Some(candidate) => self.state_space.enforce_bounds(candidate),
+                None => continue,
+            };
 
             if self.collision_checker.is_node_colliding(&candidate) {
                 continue;
@@ -175,9 +246,12 @@ impl PRM {
                 continue;
             }
 
-            self.add_node(candidate);
-            return candidate;
+            self.insert_node(candidate);
+            return Ok(candidate);
         }
+        Err(PlanningError::NoValidSamples {
+            attempts: self.config.max_sampling_attempts,
+        })
     }
 
     /// Try to connect a node to its k nearest neigbors.
@@ -190,64 +264,88 @@ impl PRM {
     /// - `node: Point`: f64he node to be connected to the graph.
     /// # Outputs
     /// None. The method modifies the graph by adding edges between the node and its neighbors.
-    fn connect_node_to_graph(&mut self, node: Point) {
-        let mut iterator = self
-            .tree
-            .nearest_neighbor_iter_with_distance_2(&[node.get_x(), node.get_y()]);
+    /// The weight of an edge is the distance in the state space, so the solution cost is the length of the path.
+    /// For asymmetric state spaces, the edge from the neighbor to the node is checked and added separately.
+    fn connect_node_to_graph(&mut self, node: Point<N>) {
+        let state_space: &dyn StateSpace<N> = self.state_space.as_ref();
+        let mut iterator = self.tree.iter_nearest(state_space, &node, |neighbor| {
+            state_space.distance(&node, neighbor)
+        });
 
         for _ in 0..self.config.default_nearest_neighbors {
-            if let Some((neighbor, distance)) = iterator.next() {
-                let neighbor_point = Point::new(neighbor[0], neighbor[1]);
-
+            if let Some((neighbor_point, distance)) = iterator.next() {
                 if node == neighbor_point
-                    || self
-                        .collision_checker
-                        .is_edge_colliding(&node, &neighbor_point)
+                    || state_space.is_motion_colliding(
+                        self.collision_checker.as_ref(),
+                        &node,
+                        &neighbor_point,
+                    )
                 {
                     continue;
                 }
 
-                let a = *self
-                    .index_node_lookup
-                    .get(&node.to_wkt().to_string())
-                    .unwrap();
-                let b = *self
-                    .index_node_lookup
-                    .get(&neighbor_point.to_wkt().to_string())
-                    .unwrap();
-
-                self.graph.add_edge(a, b, distance);
+                let (Some(a), Some(b)) = (
+                    self.index_node_lookup.get(&node.to_wkt().to_string()),
+                    self.index_node_lookup
+                        .get(&neighbor_point.to_wkt().to_string()),
+                ) else {
+                    continue;
+                };
+
+                self.graph.add_edge(*a, *b, distance);
+                if !state_space.is_symmetric()
+                    && !state_space.is_motion_colliding(
+                        self.collision_checker.as_ref(),
+                        &neighbor_point,
+                        &node,
+                    )
+                {
+                    let reverse: f64 = state_space.distance(&neighbor_point, &node);
+                    self.graph.add_edge(*b, *a, reverse);
+                }
             }
         }
     }
 
     /// Applies the A* algorithm to the graph.
-    fn check_solution(&mut self) {
+    ///
+    /// # Errors
+    /// Start and goal are only missing in the graph, if `add_node` rejected them because they are in collision.
+    fn check_solution(&mut self) -> Result<(), PlanningError<N>> {
         let start = *self
             .index_node_lookup
             .get(&self.start.to_wkt().to_string())
-            .unwrap();
+            .ok_or(PlanningError::StartInCollision { start: self.start })?;
         let goal = *self
             .index_node_lookup
             .get(&self.goal.to_wkt().to_string())
-            .unwrap();
-        self.solution = astar(
-            &self.graph,
-            start,
-            |finish| finish == goal,
-            |e| *e.weight(),
-            |_| f64::default(),
-        );
+            .ok_or(PlanningError::GoalInCollision { goal: self.goal })?;
+        self.solution = pg::find_path(&self.graph, start, goal, !self.state_space.is_symmetric());
         self.is_solved = self.solution.is_some();
+        Ok(())
     }
 
-    /// Determines which criteria is used to stop the algorithm. Check the max_size parameter and compares it to the number of nodes in the graph.     
-    fn is_termination_criteria_met(&self) -> bool {
-        self.graph.node_count() >= self.config.max_size
+    /// Determines whether the algorithm stops. Uses the termination criteria, if one was set.
+    /// Otherwise the max_size parameter is compared to the number of nodes in the graph.
+    fn is_termination_criteria_met(&mut self, iterations: usize, start_time: Instant) -> bool {
+        let progress: PlannerProgress = PlannerProgress {
+            iterations,
+            node_count: self.graph.node_count(),
+            solution_cost: self.solution.as_ref().map(|(cost, _)| *cost),
+            elapsed: start_time.elapsed(),
+        };
+        self.termination_reason = match &mut self.termination_criteria {
+            Some(criteria) => criteria.check(&progress),
+            None => NodeLimit {
+                max_nodes: self.config.max_size,
+            }
+            .check(&progress),
+        };
+        self.termination_reason.is_some()
     }
 
     /// Returns the graph object (petgraph)
-    pub fn get_graph(&self) -> &Graph<Point, f64, Undirected> {
+    pub fn get_graph(&self) -> &Graph<Point<N>, f64, Undirected> {
         &self.graph
     }
 
@@ -257,9 +355,9 @@ impl PRM {
     }
 }
 
-impl Default for PRM {
+impl<const N: usize> Default for PRM<N> {
     fn default() -> Self {
-        let collision_checker: Box<dyn CollisionChecker> = Box::new(NaiveCollisionChecker {
+        let collision_checker: Box<dyn CollisionChecker<N>> = Box::new(NaiveCollisionChecker {
             phantom: PhantomData,
         });
         PRM::new(collision_checker)
@@ -273,6 +371,7 @@ mod test {
     use crate::collision_checker::{CollisionChecker, NaiveCollisionChecker};
     use crate::planner::base_planner::Planner;
     use crate::space::Point;
+    use crate::test_support::BlockedCollisionChecker;
     use std::marker::PhantomData;
 
     // Test that the function 'test_default_f64' returns a PRM instance with the 'is_solved' field set to false.
@@ -394,4 +493,270 @@ mod test {
         assert_eq!(planner.tree.size(), 2);
         assert_eq!(planner.index_node_lookup.len(), 2);
     }
+
+    // Test that the solution path starts at the start point and ends at the goal point.
+    #[test]
+    fn test_prm_solution_path() {
+        let start: Point = Point::new(0f64, 0f64);
+        let goal: Point = Point::new(3f64, 3f64);
+        let mut planner: PRM = PRM::default();
+        planner.set_start(start);
+        planner.set_goal(goal);
+        planner.set_boundaries(Boundaries::new(0f64, 3f64, 0f64, 3f64));
+        assert!(planner.get_solution_path().is_empty());
+
+        planner.init();
+        planner.solve().unwrap();
+        let path: Vec<Point> = planner.get_solution_path();
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+
+        // The cost is the length of the path, which can not be shorter than the straight line.
+        let length: f64 = path
+            .windows(2)
+            .map(|pair| pair[0].euclidean_distance(&pair[1]))
+            .sum();
+        assert!((planner.get_solution_cost() - length).abs() < 1e-9);
+        assert!(length >= start.euclidean_distance(&goal));
+    }
+
+    // Test that the default termination stops at config.max_size nodes.
+    #[test]
+    fn test_prm_default_termination() {
+        use crate::planner::termination::TerminationReason;
+
+        let mut planner: PRM = PRM::default();
+        planner.set_start(Point::new(0f64, 0f64));
+        planner.set_goal(Point::new(3f64, 3f64));
+        planner.set_boundaries(Boundaries::new(0f64, 3f64, 0f64, 3f64));
+        planner.init();
+        planner.solve().unwrap();
+        assert!(planner.graph.node_count() >= planner.config.max_size);
+        assert_eq!(
+            planner.get_termination_reason(),
+            Some(TerminationReason::NodeLimit)
+        );
+    }
+
+    // Test that the planner stops at the first solution if asked to.
+    #[test]
+    fn test_prm_solution_found_termination() {
+        use crate::planner::termination::{SolutionFound, TerminationReason};
+
+        let mut planner: PRM = PRM::default();
+        planner.config.max_size = usize::MAX;
+        planner.set_start(Point::new(0f64, 0f64));
+        planner.set_goal(Point::new(3f64, 3f64));
+        planner.set_boundaries(Boundaries::new(0f64, 3f64, 0f64, 3f64));
+        planner.set_termination_criteria(SolutionFound::new_box());
+        planner.init();
+        planner.solve().unwrap();
+        assert!(planner.is_solved);
+        assert_eq!(
+            planner.get_termination_reason(),
+            Some(TerminationReason::SolutionFound)
+        );
+    }
+
+    // Test that a fully blocked map makes the planner give up instead of looping forever.
+    #[test]
+    fn test_prm_blocked_map_no_valid_samples() {
+        use crate::error::PlanningError;
+
+        let mut planner: PRM = PRM::new(Box::new(BlockedCollisionChecker {}));
+        planner.set_boundaries(Boundaries::new(0f64, 3f64, 0f64, 3f64));
+        planner.init();
+        assert!(matches!(
+            planner.solve(),
+            Err(PlanningError::NoValidSamples { attempts: 1000 })
+        ));
+    }
+
+    // Test that boundaries without area make the planner give up, because every sample is a duplicate.
+    #[test]
+    fn test_prm_zero_area_no_valid_samples() {
+        use crate::error::PlanningError;
+
+        let mut planner: PRM = PRM::default();
+        planner.config.max_sampling_attempts = 10;
+        planner.set_start(Point::new(1f64, 1f64));
+        planner.set_goal(Point::new(1f64, 1f64));
+        planner.set_boundaries(Boundaries::new(1f64, 1f64, 1f64, 1f64));
+        planner.init();
+        assert!(matches!(
+            planner.solve(),
+            Err(PlanningError::NoValidSamples { attempts: 10 })
+        ));
+    }
+
+    // Test that planners with the same seed build identical graphs and find the same solution.
+    #[test]
+    fn test_prm_same_seed_same_graph() {
+        let solve = |seed: u64| -> PRM {
+            let mut planner: PRM = PRM::default();
+            planner.set_start(Point::new(0f64, 0f64));
+            planner.set_goal(Point::new(3f64, 3f64));
+            planner.set_boundaries(Boundaries::new_seeded(0f64, 3f64, 0f64, 3f64, seed));
+            planner.init();
+            planner.solve().unwrap();
+            planner
+        };
+        let planner1: PRM = solve(42);
+        let planner2: PRM = solve(42);
+
+        assert_eq!(
+            planner1.graph.node_weights().collect::<Vec<_>>(),
+            planner2.graph.node_weights().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            planner1
+                .graph
+                .raw_edges()
+                .iter()
+                .map(|e| (e.source(), e.target(), e.weight))
+                .collect::<Vec<_>>(),
+            planner2
+                .graph
+                .raw_edges()
+                .iter()
+                .map(|e| (e.source(), e.target(), e.weight))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(planner1.get_solution_cost(), planner2.get_solution_cost());
+
+        let planner3: PRM = solve(43);
+        assert_ne!(
+            planner1.graph.node_weights().collect::<Vec<_>>(),
+            planner3.graph.node_weights().collect::<Vec<_>>()
+        );
+    }
+
+    // Test that every node is checked for collision only once and the counters are available from the planner.
+    #[test]
+    fn test_prm_collision_statistics() {
+        use crate::collision_checker::{CachingCollisionChecker, CollisionStatistics};
+
+        let mut planner: PRM = PRM::new(CachingCollisionChecker::new_box(
+            NaiveCollisionChecker::new_box(),
+        ));
+        planner.set_start(Point::new(0f64, 0f64));
+        planner.set_goal(Point::new(3f64, 3f64));
+        planner.set_boundaries(Boundaries::new_seeded(0f64, 3f64, 0f64, 3f64, 42));
+        planner.init();
+        planner.solve().unwrap();
+
+        let statistics: CollisionStatistics = planner.get_collision_statistics().unwrap();
+        assert_eq!(statistics.node_checks, planner.graph.node_count());
+        assert_eq!(statistics.node_hits, 0);
+        assert!(statistics.edge_checks > 0);
+        assert_eq!(PRM::<2>::default().get_collision_statistics(), None);
+    }
+
+    // Test that attempts without a sample count as failed sampling attempts.
+    #[test]
+    fn test_prm_sampler_without_samples() {
+        use crate::error::PlanningError;
+        use crate::sampler::StateSampler;
+
+        struct EmptySampler {}
+
+        impl StateSampler for EmptySampler {
+            fn sample(
+                &mut self,
+                _boundaries: &mut Boundaries,
+                _collision_checker: &dyn CollisionChecker,
+            ) -> Option<Point> {
+                None
+            }
+        }
+
+        let mut planner: PRM = PRM::default();
+        planner.set_boundaries(Boundaries::new(0f64, 3f64, 0f64, 3f64));
+        planner.set_sampler(Box::new(EmptySampler {}));
+        planner.init();
+        assert!(matches!(
+            planner.solve(),
+            Err(PlanningError::NoValidSamples { attempts: 1000 })
+        ));
+    }
+
+    // Test that a quasi-random sampler builds identical roadmaps without a seed.
+    #[test]
+    fn test_prm_quasi_random_deterministic() {
+        use crate::sampler::{HaltonSampler, SobolSampler, StateSampler};
+
+        let solve = |sampler: Box<dyn StateSampler>| -> PRM {
+            let mut planner: PRM = PRM::default();
+            planner.set_start(Point::new(0f64, 0f64));
+            planner.set_goal(Point::new(3f64, 3f64));
+            planner.set_boundaries(Boundaries::new(0f64, 3f64, 0f64, 3f64));
+            planner.set_sampler(sampler);
+            planner.init();
+            planner.solve().unwrap();
+            planner
+        };
+        for new_box in [HaltonSampler::new_box, SobolSampler::new_box] {
+            let planner1: PRM = solve(new_box(1, None));
+            let planner2: PRM = solve(new_box(1, None));
+            assert_eq!(
+                planner1.graph.node_weights().collect::<Vec<_>>(),
+                planner2.graph.node_weights().collect::<Vec<_>>()
+            );
+            assert_eq!(planner1.get_solution_cost(), planner2.get_solution_cost());
+        }
+    }
+
+    // Test that a start in collision is reported instead of panicking in the lookup.
+    #[test]
+    fn test_prm_start_in_collision() {
+        use crate::error::PlanningError;
+
+        /// Blocks the unit square around the origin.
+        struct OriginCollisionChecker {}
+
+        impl CollisionChecker for OriginCollisionChecker {
+            fn init(&self) -> bool {
+                true
+            }
+
+            fn is_node_colliding(&self, node: &Point) -> bool {
+                node.get_x() < 1f64 && node.get_y() < 1f64
+            }
+
+            fn is_edge_colliding(&self, begin: &Point, end: &Point) -> bool {
+                self.is_node_colliding(begin) || self.is_node_colliding(end)
+            }
+        }
+
+        let start: Point = Point::new(0f64, 0f64);
+        let mut planner: PRM = PRM::new(Box::new(OriginCollisionChecker {}));
+        planner.set_start(start);
+        planner.set_goal(Point::new(3f64, 3f64));
+        planner.set_boundaries(Boundaries::new(0f64, 3f64, 0f64, 3f64));
+        planner.init();
+        assert!(matches!(
+            planner.solve(),
+            Err(PlanningError::StartInCollision { start: s }) if s == start
+        ));
+    }
+
+    // Test that the roadmap is built in 6D, e.g. the joint space of an arm.
+    #[test]
+    fn test_prm_6d() {
+        let start: Point<6> = Point::default();
+        let goal: Point<6> = Point::from_coordinates([1f64; 6]);
+        let mut boundaries: Boundaries<6> = Boundaries::from_limits([0f64; 6], [1f64; 6]);
+        boundaries.set_seed(42);
+        let mut planner: PRM<6> = PRM::default();
+        planner.set_start(start);
+        planner.set_goal(goal);
+        planner.set_boundaries(boundaries);
+        planner.init();
+        planner.solve().unwrap();
+
+        assert_eq!(planner.tree.size(), planner.graph.node_count());
+        let path: Vec<Point<6>> = planner.get_solution_path();
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+    }
 }
@@ -1,99 +1,162 @@
 use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::time::Instant;
 
-use petgraph::algo::astar;
 use petgraph::graph::{Graph, NodeIndex};
 use petgraph::Undirected;
-use rstar::RTree;
 
 use crate::boundaries::Boundaries;
 use crate::collision_checker::{CollisionChecker, NaiveCollisionChecker};
+use crate::error::PlanningError;
 use crate::optimizer::{DefaultOptimizer, Optimizer};
 use crate::planner::base_planner::Planner;
 use crate::planner::graph_utils as pg;
+use crate::planner::nearest_neighbors::NearestNeighbors;
+use crate::planner::termination::{
+    NodeLimit, PlannerProgress, TerminationCriteria, TerminationReason,
+};
+use crate::sampler::{StateSampler, UniformSampler};
 use crate::space::Point;
+use crate::state_space::{EuclideanStateSpace, StateSpace};
+
+/// Determines how many neighbors a new node is connected to.
+/// - KNearest: The k_PRM* nearest neighbors with k = k_constant * log(n)
+/// - Radius: All neighbors within the r_PRM* radius with r = gamma * (log(n) / n)^(1/d)
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ConnectionStrategy {
+    KNearest,
+    Radius,
+}
 
 /// # Holds configuration parameters for PRM*
 /// It does configure:
-/// - default_nearest_neighbors: Limits the number of nodes that are used to calculate motionCost to the n closest ones
+/// - connection_strategy: Use the k-nearest (k_PRM*) or the radius (r_PRM*) variant to connect new nodes
+/// - k_constant: Constant of the number of neighbors k = k_constant * log(n). Must be at least e * (1 + 1/d) to be asymptotically optimal, where d is the dimension. The default is this lower limit.
+/// - gamma: Constant of the connection radius. If `None`, a value that guarantees asymptotic optimality is derived from the boundaries.
 /// - max_size: Limits the number of Nodes in the graph before termination of the algrithm
+/// - max_sampling_attempts: Limits the number of consecutive failed attempts to add a node before the planner gives up
 pub struct Config {
-    pub default_nearest_neighbors: u8,
+    pub connection_strategy: ConnectionStrategy,
+    pub k_constant: f64,
+    pub gamma: Option<f64>,
     pub max_size: usize,
+    pub max_sampling_attempts: usize,
 }
 
-impl Default for Config {
-    fn default() -> Self {
+impl Config {
+    /// Configuration for a configuration space with the given dimension. The dimension determines the default k_constant.
+    pub fn new(dimension: usize) -> Self {
         Config {
-            default_nearest_neighbors: 10u8,
+            connection_strategy: ConnectionStrategy::KNearest,
+            k_constant: std::f64::consts::E * (1f64 + 1f64 / dimension as f64),
+            gamma: None,
             max_size: 32usize,
+            max_sampling_attempts: 1000usize,
         }
     }
 }
 
+/// Configuration for 2D.
+impl Default for Config {
+    fn default() -> Self {
+        Config::new(2)
+    }
+}
+
 /// # Probabilisic Road Map PRM* for optimal planning
 /// It is an algorithm which is:
 /// - probabilistically complete and
-/// - probabilistically optimal algorithm
+/// - asymptotically optimal algorithm
 /// - Multi-query capable It can be used to do multi-queries.
 ///
+/// In contrast to PRM the number of neighbors (or the connection radius) grows logarithmically with the size of the roadmap.
+///
 /// # Source / Credits
 /// Kavraki, L. E.; Svestka, P.; Latombe, J.-C.; Overmars, M. H. (1996), "Probabilistic roadmaps for path planning in high-dimensional configuration spaces", IEEE Transactions on Robotics and Automation, 12 (4): 566–580, doi:10.1109/70.508439
 ///
+/// Karaman, S.; Frazzoli, E. (2011), "Sampling-based algorithms for optimal motion planning", The International Journal of Robotics Research, 30 (7): 846–894, doi:10.1177/0278364911406761
+///
+/// Plans in any dimension `N`, e.g. `PRMstar<6>` for the joint space of an arm. The default is 2D.
+/// Neighbors and motions are defined by the state space, which is Euclidean by default. Edge costs are provided by the `Optimizer`.
+///
 /// # Example
 ///
-pub struct PRMstar {
-    pub start: Point,
-    pub goal: Point,
-    pub boundaries: Boundaries,
-    pub graph: Graph<Point, f64, Undirected>,
+pub struct PRMstar<const N: usize = 2> {
+    pub start: Point<N>,
+    pub goal: Point<N>,
+    pub boundaries: Boundaries<N>,
+    pub graph: Graph<Point<N>, f64, Undirected>,
     pub solution: Option<(f64, Vec<NodeIndex>)>,
-    pub optimizer: Box<dyn Optimizer>,
+    pub optimizer: Box<dyn Optimizer<N>>,
     pub is_solved: bool,
-    pub collision_checker: Box<dyn CollisionChecker>,
-    tree: RTree<[f64; 2]>,
+    pub collision_checker: Box<dyn CollisionChecker<N>>,
+    pub sampler: Box<dyn StateSampler<N>>,
+    pub state_space: Box<dyn StateSpace<N>>,
+    tree: NearestNeighbors<N>,
     index_node_lookup: HashMap<String, NodeIndex>,
+    termination_criteria: Option<Box<dyn TerminationCriteria>>,
+    termination_reason: Option<TerminationReason>,
     pub config: Config,
 }
 
-impl Planner for PRMstar {
+impl<const N: usize> Planner<N> for PRMstar<N> {
     /// Setter for start
-    fn set_start(&mut self, start: Point) {
+    fn set_start(&mut self, start: Point<N>) {
         self.start = start;
     }
 
     /// Setter for goal
-    fn set_goal(&mut self, goal: Point) {
+    fn set_goal(&mut self, goal: Point<N>) {
         self.goal = goal;
     }
 
     /// Setter for boundaries
-    fn set_boundaries(&mut self, boundaries: Boundaries) {
+    fn set_boundaries(&mut self, boundaries: Boundaries<N>) {
         self.boundaries = boundaries;
     }
 
     /// Setter for Collision Checker
-    fn set_collision_checker(&mut self, cc: Box<dyn CollisionChecker>) {
+    fn set_collision_checker(&mut self, cc: Box<dyn CollisionChecker<N>>) {
         self.collision_checker = cc;
     }
 
+    fn get_collision_checker(&self) -> &dyn CollisionChecker<N> {
+        self.collision_checker.as_ref()
+    }
+
+    fn set_sampler(&mut self, sampler: Box<dyn StateSampler<N>>) {
+        self.sampler = sampler;
+    }
+
+    fn set_termination_criteria(&mut self, criteria: Box<dyn TerminationCriteria>) {
+        self.termination_criteria = Some(criteria);
+    }
+
     /// Initializes the problem by adding the start and goal fields into the solution graph
     fn init(&mut self) {
+        self.start = self.state_space.enforce_bounds(self.start);
+        self.goal = self.state_space.enforce_bounds(self.goal);
         self.add_node(self.start);
         self.add_node(self.goal);
     }
 
     /// Use the current configuration to solve the problem
-    fn solve(&mut self) {
+    fn solve(&mut self) -> Result<(), PlanningError<N>> {
+        let start_time: Instant = Instant::now();
+        let mut iterations: usize = 0;
+        self.termination_reason = None;
+        if let Some(criteria) = &mut self.termination_criteria {
+            criteria.reset();
+        }
         loop {
-            let added_node: Point = self.add_random_node();
+            let added_node: Point<N> = self.add_random_node()?;
             self.connect_node_to_graph(added_node);
+            iterations += 1;
 
-            self.find_path(self.start, self.goal);
+            self.find_path(self.start, self.goal)?;
 
-            if self.is_termination_criteria_met() {
-                println!("Termination Criteria met");
-                break;
+            if self.is_termination_criteria_met(iterations, start_time) {
+                return Ok(());
             }
         }
     }
@@ -107,16 +170,29 @@ impl Planner for PRMstar {
             Some((cost, _)) => *cost,
         }
     }
+
+    /// Returns the points of the solution path from start to goal.
+    /// - empty: No solution was found
+    fn get_solution_path(&self) -> Vec<Point<N>> {
+        match &self.solution {
+            None => Vec::new(),
+            Some((_, path)) => pg::path_to_points(&self.graph, path),
+        }
+    }
+
+    fn get_termination_reason(&self) -> Option<TerminationReason> {
+        self.termination_reason
+    }
 }
 
-impl PRMstar {
+impl<const N: usize> PRMstar<N> {
     /// Standard constructor
     pub fn new(
-        start: Point,
-        goal: Point,
-        boundaries: Boundaries,
-        optimizer: Box<dyn Optimizer>,
-        collision_checker: Box<dyn CollisionChecker>,
+        start: Point<N>,
+        goal: Point<N>,
+        boundaries: Boundaries<N>,
+        optimizer: Box<dyn Optimizer<N>>,
+        collision_checker: Box<dyn CollisionChecker<N>>,
     ) -> Self {
         PRMstar {
             start,
@@ -127,14 +203,24 @@ impl PRMstar {
             optimizer,
             is_solved: false,
             collision_checker,
-            tree: RTree::new(),
+            sampler: UniformSampler::new_box(),
+            state_space: EuclideanStateSpace::new_box(),
+            tree: NearestNeighbors::new(),
             index_node_lookup: HashMap::new(),
-            config: Config::default(),
+            termination_criteria: None,
+            termination_reason: None,
+            config: Config::new(N),
         }
     }
 
+    /// Replaces the state space, e.g. by `SE2StateSpace` for vehicles with a heading.
+    /// Has to be set before `init`, because the nodes are stored with the keys of the state space.
+    pub fn set_state_space(&mut self, state_space: Box<dyn StateSpace<N>>) {
+        self.state_space = state_space;
+    }
+
     /// Adds a node to the graph, lookup for nodeindex to point.wkt, and the rtree.
-    fn add_node(&mut self, node: Point) {
+    fn add_node(&mut self, node: Point<N>) {
         if self.collision_checker.is_node_colliding(&node) {
             return;
         }
@@ -146,19 +232,32 @@ impl PRMstar {
             return;
         }
 
+        self.insert_node(node);
+    }
+
+    /// Inserts a node into the graph, the lookup and the rtree without any checks.
+    /// The caller has to make sure, that the node is not in collision and not already in the graph.
+    fn insert_node(&mut self, node: Point<N>) {
         let index = self.graph.add_node(node);
         self.index_node_lookup
             .insert(node.to_wkt().to_string(), index);
-        self.tree.insert([node.get_x(), node.get_y()]);
+        self.tree.insert(self.state_space.as_ref(), node);
     }
 
     /// Generates a random node and adds it to the graph, if:
     /// - It is not in collision
     /// - It is not already in the graph
-    fn add_random_node(&mut self) -> Point {
-        let mut candidate: Point;
-        loop {
-            candidate = self.boundaries.generate_random_configuration();
+    ///
+    /// Gives up with `PlanningError::NoValidSamples` after `config.max_sampling_attempts` attempts.
+    fn add_random_node(&mut self) -> Result<Point<N>, PlanningError<N>> {
+        for _ in 0..self.config.max_sampling_attempts {
+            let candidate: Point<N> = match self
+                .sampler
+                .sample(&mut self.boundaries, self.collision_checker.as_ref())
+            {
+                Some(candidate) => self.state_space.enforce_bounds(candidate),
+                None => continue,
+            };
 
             if self.collision_checker.is_node_colliding(&candidate) {
                 continue;
@@ -171,46 +270,99 @@ impl PRMstar {
                 continue;
             }
 
-            break;
+            self.insert_node(candidate);
+            return Ok(candidate);
         }
-        self.add_node(candidate);
-        candidate
-    }
-
-    /// Try to connect a node to its k nearest neigbors.
-    fn connect_node_to_graph(&mut self, node: Point) {
-        let mut iterator = self
-            .tree
-            .nearest_neighbor_iter(&[node.get_x(), node.get_y()]);
-        for _ in 0..self.config.default_nearest_neighbors {
-            if let Some(neighbor) = iterator.next() {
-                let neighbor_point = Point::new(neighbor[0], neighbor[1]);
-
-                if node == neighbor_point {
-                    continue;
-                }
-
-                if self
-                    .collision_checker
-                    .is_edge_colliding(&node, &neighbor_point)
-                {
-                    continue;
-                }
-
-                let weight = self.optimizer.get_edge_weight(node, neighbor_point).2;
-                let a = *self
-                    .index_node_lookup
-                    .get(&node.to_wkt().to_string())
-                    .unwrap();
-                let b = *self
-                    .index_node_lookup
-                    .get(&neighbor_point.to_wkt().to_string())
-                    .unwrap();
-                self.graph.add_edge(a, b, weight);
+        Err(PlanningError::NoValidSamples {
+            attempts: self.config.max_sampling_attempts,
+        })
+    }
+
+    /// Number of neighbors k = k_constant * log(n) for a roadmap with n nodes.
+    pub fn get_number_of_neighbors(&self) -> usize {
+        let n: f64 = self.graph.node_count().max(1) as f64;
+        (self.config.k_constant * n.ln()).ceil() as usize
+    }
+
+    /// Smallest gamma that guarantees asymptotic optimality for the given boundaries:
+    /// gamma > 2 * (1 + 1/d)^(1/d) * (volume / unit_ball_volume)^(1/d)
+    pub fn optimal_gamma(boundaries: &Boundaries<N>) -> f64 {
+        let dimension: f64 = N as f64;
+        let volume: f64 = boundaries
+            .get_lower()
+            .iter()
+            .zip(boundaries.get_upper().iter())
+            .map(|(lower, upper)| upper - lower)
+            .product();
+        2f64 * (1f64 + 1f64 / dimension).powf(1f64 / dimension)
+            * (volume / pg::unit_ball_volume(N)).powf(1f64 / dimension)
+    }
+
+    /// Connection radius r = gamma * (log(n) / n)^(1/d) for a roadmap with n nodes.
+    pub fn get_connection_radius(&self) -> f64 {
+        let n: f64 = self.graph.node_count().max(1) as f64;
+        let gamma: f64 = self
+            .config
+            .gamma
+            .unwrap_or_else(|| PRMstar::optimal_gamma(&self.boundaries));
+        gamma * (n.ln() / n).powf(1f64 / N as f64)
+    }
+
+    /// Returns the neighbors a new node should be connected to, depending on the connection strategy.
+    /// The node itself is not part of the result.
+    fn get_neighbors(&self, node: Point<N>) -> Vec<Point<N>> {
+        let state_space: &dyn StateSpace<N> = self.state_space.as_ref();
+        let distance = |neighbor: &Point<N>| state_space.distance(&node, neighbor);
+        let neighbors: Vec<(Point<N>, f64)> = match self.config.connection_strategy {
+            ConnectionStrategy::KNearest => self
+                .tree
+                .iter_nearest(state_space, &node, distance)
+                .take(self.get_number_of_neighbors() + 1)
+                .collect(),
+            ConnectionStrategy::Radius => self.tree.within_distance(
+                state_space,
+                &node,
+                self.get_connection_radius(),
+                distance,
+            ),
+        };
+        neighbors
+            .into_iter()
+            .map(|(neighbor, _)| neighbor)
+            .filter(|neighbor| *neighbor != node)
+            .collect()
+    }
+
+    /// Try to connect a node to its neigbors.
+    /// For asymmetric state spaces, the edges from the neighbors to the node are checked and added as well.
+    fn connect_node_to_graph(&mut self, node: Point<N>) {
+        for neighbor_point in self.get_neighbors(node) {
+            self.connect_nodes(node, neighbor_point);
+            if !self.state_space.is_symmetric() {
+                self.connect_nodes(neighbor_point, node);
             }
         }
     }
 
+    /// Adds the edge from `begin` to `end`, if the motion is not in collision.
+    fn connect_nodes(&mut self, begin: Point<N>, end: Point<N>) {
+        if self
+            .state_space
+            .is_motion_colliding(self.collision_checker.as_ref(), &begin, &end)
+        {
+            return;
+        }
+
+        let weight = self.optimizer.get_edge_weight(begin, end).2;
+        let (Some(a), Some(b)) = (
+            self.index_node_lookup.get(&begin.to_wkt().to_string()),
+            self.index_node_lookup.get(&end.to_wkt().to_string()),
+        ) else {
+            return;
+        };
+        self.graph.add_edge(*a, *b, weight);
+    }
+
     /// Finds a path between a start and goal point using the A* algorithm.
     ///
     /// # Arguments
@@ -222,37 +374,58 @@ impl PRMstar {
     ///
     /// A tuple containing a boolean indicating whether a path was found and a vector of node indices
     /// representing the path. If no path was found, the vector will be empty.
-    fn find_path(&mut self, start: Point, goal: Point) -> (bool, Vec<NodeIndex>) {
+    ///
+    /// # Errors
+    /// Start and goal are only missing in the graph, if `add_node` rejected them because they are in collision.
+    fn find_path(
+        &mut self,
+        start: Point<N>,
+        goal: Point<N>,
+    ) -> Result<(bool, Vec<NodeIndex>), PlanningError<N>> {
         let start_index: NodeIndex = *self
             .index_node_lookup
             .get(&start.to_wkt().to_string())
-            .unwrap();
+            .ok_or(PlanningError::StartInCollision { start })?;
         let goal_index: NodeIndex = *self
             .index_node_lookup
             .get(&goal.to_wkt().to_string())
-            .unwrap();
+            .ok_or(PlanningError::GoalInCollision { goal })?;
 
-        self.solution = astar(
+        self.solution = pg::find_path(
             &self.graph,
             start_index,
-            |finish| finish == goal_index,
-            |e| *e.weight(),
-            |_| f64::default(),
+            goal_index,
+            !self.state_space.is_symmetric(),
         );
+        self.is_solved = self.solution.is_some();
 
-        match &self.solution {
+        Ok(match &self.solution {
             Some((_cost, path)) => (true, path.to_vec()),
             None => (false, vec![]),
-        }
+        })
     }
 
-    /// Determines which criteria is used to stop the algorithm. Check the max_size parameter and compares it to the number of nodes in the graph.     
-    fn is_termination_criteria_met(&self) -> bool {
-        self.graph.node_count() >= self.config.max_size
+    /// Determines whether the algorithm stops. Uses the termination criteria, if one was set.
+    /// Otherwise the max_size parameter is compared to the number of nodes in the graph.
+    fn is_termination_criteria_met(&mut self, iterations: usize, start_time: Instant) -> bool {
+        let progress: PlannerProgress = PlannerProgress {
+            iterations,
+            node_count: self.graph.node_count(),
+            solution_cost: self.solution.as_ref().map(|(cost, _)| *cost),
+            elapsed: start_time.elapsed(),
+        };
+        self.termination_reason = match &mut self.termination_criteria {
+            Some(criteria) => criteria.check(&progress),
+            None => NodeLimit {
+                max_nodes: self.config.max_size,
+            }
+            .check(&progress),
+        };
+        self.termination_reason.is_some()
     }
 
     /// Returns the graph object (petgraph)
-    pub fn get_graph(&self) -> &Graph<Point, f64, Undirected> {
+    pub fn get_graph(&self) -> &Graph<Point<N>, f64, Undirected> {
         &self.graph
     }
 
@@ -281,7 +454,8 @@ impl Default for PRMstar {
 mod test {
     use crate::{planner::base_planner::Planner, space::Point};
 
-    use super::PRMstar;
+    use super::{ConnectionStrategy, PRMstar};
+    use crate::test_support::{BlockedCollisionChecker, BoxCollisionChecker};
     use crate::{
         boundaries::Boundaries,
         collision_checker::{CollisionChecker, NaiveCollisionChecker},
@@ -370,7 +544,14 @@ mod test {
 
         assert!(!planner.is_solved);
         // Add assertions for the custom configuration
-        assert_eq!(planner.config.default_nearest_neighbors, 10u8);
+        assert_eq!(
+            planner.config.connection_strategy,
+            ConnectionStrategy::KNearest
+        );
+        assert_eq!(
+            planner.config.k_constant,
+            std::f64::consts::E * (1f64 + 1f64 / 2f64)
+        );
         assert_eq!(planner.config.max_size, 32usize);
     }
 
@@ -435,4 +616,134 @@ mod test {
         assert_eq!(planner.tree.size(), 2);
         assert_eq!(planner.index_node_lookup.len(), 2);
     }
+
+    fn create_obstacle_planner(strategy: ConnectionStrategy) -> PRMstar {
+        let start: Point = Point::new(0f64, 0f64);
+        let goal: Point = Point::new(3f64, 3f64);
+        let bounds: Boundaries = Boundaries::new(0f64, 3f64, 0f64, 3f64);
+        let optimizer: Box<dyn Optimizer> = Box::new(DefaultOptimizer {
+            phantom: PhantomData,
+        });
+        let cc: Box<dyn CollisionChecker> = Box::new(BoxCollisionChecker {});
+        let mut planner: PRMstar = PRMstar::new(start, goal, bounds, optimizer, cc);
+        planner.config.connection_strategy = strategy;
+        planner.init();
+        planner
+    }
+
+    // Test that the number of neighbors grows logarithmically with the roadmap size.
+    #[test]
+    fn test_number_of_neighbors_grows() {
+        let mut planner: PRMstar = create_obstacle_planner(ConnectionStrategy::KNearest);
+        let k_small: usize = planner.get_number_of_neighbors();
+        planner.config.max_size = 200;
+        planner.solve().unwrap();
+        let k_large: usize = planner.get_number_of_neighbors();
+        let expected: usize = (planner.config.k_constant * 200f64.ln()).ceil() as usize;
+        assert!(k_small < k_large);
+        assert_eq!(k_large, expected);
+    }
+
+    // Test that the connection radius shrinks with the roadmap size.
+    #[test]
+    fn test_connection_radius_shrinks() {
+        let mut planner: PRMstar = create_obstacle_planner(ConnectionStrategy::Radius);
+        planner.config.max_size = 20;
+        planner.solve().unwrap();
+        let radius_small: f64 = planner.get_connection_radius();
+        planner.config.max_size = 200;
+        planner.solve().unwrap();
+        let radius_large: f64 = planner.get_connection_radius();
+        assert!(radius_large < radius_small);
+    }
+
+    // Test that the solution cost decreases towards the optimum as the roadmap grows around an obstacle.
+    #[test]
+    fn test_cost_decreases_with_roadmap_size() {
+        for strategy in [ConnectionStrategy::KNearest, ConnectionStrategy::Radius] {
+            let mut planner: PRMstar = create_obstacle_planner(strategy);
+            planner.config.max_size = 50;
+            planner.solve().unwrap();
+            let cost_small: f64 = planner.get_solution_cost();
+
+            planner.config.max_size = 300;
+            planner.solve().unwrap();
+            let cost_large: f64 = planner.get_solution_cost();
+
+            // Shortest path passes the obstacle corner at (1, 2) or (2, 1).
+            let optimum: f64 = 2f64 * 5f64.sqrt();
+            assert!(planner.is_solved);
+            assert!(cost_large <= cost_small);
+            assert!(cost_large >= optimum);
+            assert!(cost_large < 1.1 * optimum);
+        }
+    }
+
+    // Test that a fully blocked map makes the planner give up instead of looping forever.
+    #[test]
+    fn test_blocked_map_no_valid_samples() {
+        use crate::error::PlanningError;
+
+        let start: Point = Point::new(0f64, 0f64);
+        let goal: Point = Point::new(3f64, 3f64);
+        let bounds: Boundaries = Boundaries::new(0f64, 3f64, 0f64, 3f64);
+        let optimizer: Box<dyn Optimizer> = Box::new(DefaultOptimizer {
+            phantom: PhantomData,
+        });
+        let cc: Box<dyn CollisionChecker> = Box::new(BlockedCollisionChecker {});
+        let mut planner: PRMstar = PRMstar::new(start, goal, bounds, optimizer, cc);
+        planner.init();
+        assert!(matches!(
+            planner.solve(),
+            Err(PlanningError::NoValidSamples { attempts: 1000 })
+        ));
+    }
+
+    // Test that the dimension of the planner is used for the neighbors and the connection radius.
+    #[test]
+    fn test_dimension() {
+        use std::f64::consts::{E, PI};
+
+        let bounds: Boundaries = Boundaries::new(0f64, 3f64, 0f64, 3f64);
+        let expected: f64 = 2f64 * 1.5f64.sqrt() * (9f64 / PI).sqrt();
+        assert!((PRMstar::optimal_gamma(&bounds) - expected).abs() < 1e-12);
+
+        let bounds: Boundaries<3> = Boundaries::from_limits([0f64; 3], [3f64; 3]);
+        let unit_ball: f64 = 4f64 / 3f64 * PI;
+        let expected: f64 = 2f64 * (4f64 / 3f64 * 27f64 / unit_ball).cbrt();
+        assert!((PRMstar::optimal_gamma(&bounds) - expected).abs() < 1e-12);
+
+        let planner: PRMstar<3> = PRMstar::new(
+            Point::default(),
+            Point::from_coordinates([3f64; 3]),
+            bounds,
+            DefaultOptimizer::new_box(),
+            NaiveCollisionChecker::new_box(),
+        );
+        assert_eq!(planner.config.k_constant, E * (1f64 + 1f64 / 3f64));
+    }
+
+    // Test that the solution in 3D is close to the straight line without obstacles.
+    #[test]
+    fn test_prm_star_3d() {
+        let start: Point<3> = Point::default();
+        let goal: Point<3> = Point::from_coordinates([3f64; 3]);
+        let mut bounds: Boundaries<3> = Boundaries::from_limits([0f64; 3], [3f64; 3]);
+        bounds.set_seed(42);
+        let mut planner: PRMstar<3> = PRMstar::new(
+            start,
+            goal,
+            bounds,
+            DefaultOptimizer::new_box(),
+            NaiveCollisionChecker::new_box(),
+        );
+        planner.config.max_size = 200;
+        planner.init();
+        planner.solve().unwrap();
+
+        let optimum: f64 = start.euclidean_distance(&goal);
+        assert!(planner.is_solved);
+        assert!(planner.get_solution_cost() >= optimum - 1e-9);
+        assert!(planner.get_solution_cost() < 1.2 * optimum);
+    }
 }
@@ -1,22 +1,31 @@
 use std::collections::HashMap;
+use std::time::Instant;
 
-use petgraph::algo::astar;
 use petgraph::graph::{Graph, NodeIndex};
 use petgraph::Undirected;
-use rstar::RTree;
 
 use crate::boundaries::Boundaries;
 use crate::collision_checker::{CollisionChecker, NaiveCollisionChecker};
+use crate::error::PlanningError;
 use crate::planner::base_planner::Planner;
+use crate::planner::graph_utils as pg;
+use crate::planner::nearest_neighbors::NearestNeighbors;
+use crate::planner::termination::{
+    NodeLimit, PlannerProgress, TerminationCriteria, TerminationReason,
+};
+use crate::sampler::{StateSampler, UniformSampler};
 use crate::space::Point;
+use crate::state_space::{EuclideanStateSpace, StateSpace};
 
 /// # Holds configuration parameters for PRM*
 /// It does configure:
 /// - default_nearest_neighbors: Limits the number of nodes that are used to calculate motionCost to the n closest ones
 /// - max_size: Limits the number of Nodes in the graph before termination of the algrithm
+/// - max_sampling_attempts: Limits the number of consecutive failed attempts to add a node before the planner gives up
 pub struct Config {
     pub default_nearest_neighbors: u8,
     pub max_size: usize,
+    pub max_sampling_attempts: usize,
 }
 
 impl Default for Config {
@@ -24,6 +33,7 @@ impl Default for Config {
         Config {
             default_nearest_neighbors: 10u8,
             max_size: 32usize,
+            max_sampling_attempts: 1000usize,
         }
     }
 }
@@ -38,27 +48,34 @@ impl Default for Config {
 /// LaValle, S. M. (), "Rapidly-Exploring Random Trees: A New Tool for Path"
 /// [Link](https://www.cs.csustan.edu/~xliang/Courses/CS4710-21S/Papers/06%20RRT.pdf)
 ///
+/// Plans in any dimension `N`, e.g. `RRT<3>` for a drone. The default is 2D.
+/// Distances and motions are defined by the state space, which is Euclidean by default.
+///
 /// # Example
-pub struct RRT {
+pub struct RRT<const N: usize = 2> {
     pub solution: Option<(f64, Vec<NodeIndex>)>,
     pub is_solved: bool,
-    pub start: Point,
-    pub goal: Point,
-    pub graph: Graph<Point, f64, Undirected>,
-    tree: RTree<[f64; 2]>,
+    pub start: Point<N>,
+    pub goal: Point<N>,
+    pub graph: Graph<Point<N>, f64, Undirected>,
+    tree: NearestNeighbors<N>,
     index_node_lookup: HashMap<String, NodeIndex>,
-    pub boundaries: Boundaries,
-    pub collision_checker: Box<dyn CollisionChecker>,
+    termination_criteria: Option<Box<dyn TerminationCriteria>>,
+    termination_reason: Option<TerminationReason>,
+    pub boundaries: Boundaries<N>,
+    pub collision_checker: Box<dyn CollisionChecker<N>>,
+    pub sampler: Box<dyn StateSampler<N>>,
+    pub state_space: Box<dyn StateSpace<N>>,
     pub config: Config,
 }
 
-impl Planner for RRT {
+impl<const N: usize> Planner<N> for RRT<N> {
     /// Sets the start point for the RRT planner.
     ///
     /// # Arguments
     ///
     /// * `start` - The start point for the planner.
-    fn set_start(&mut self, start: Point) {
+    fn set_start(&mut self, start: Point<N>) {
         self.start = start;
     }
 
@@ -67,7 +84,7 @@ impl Planner for RRT {
     /// # Arguments
     ///
     /// * `goal` - The goal point for the planner.
-    fn set_goal(&mut self, goal: Point) {
+    fn set_goal(&mut self, goal: Point<N>) {
         self.goal = goal;
     }
 
@@ -76,7 +93,7 @@ impl Planner for RRT {
     /// # Arguments
     ///
     /// * `boundaries` - The boundaries for the planner.
-    fn set_boundaries(&mut self, boundaries: Boundaries) {
+    fn set_boundaries(&mut self, boundaries: Boundaries<N>) {
         self.boundaries = boundaries;
     }
 
@@ -85,44 +102,62 @@ impl Planner for RRT {
     /// # Arguments
     ///
     /// * `cc` - The collision checker for the planner.
-    fn set_collision_checker(&mut self, cc: Box<dyn CollisionChecker>) {
+    fn set_collision_checker(&mut self, cc: Box<dyn CollisionChecker<N>>) {
         self.collision_checker = cc;
     }
 
+    fn get_collision_checker(&self) -> &dyn CollisionChecker<N> {
+        self.collision_checker.as_ref()
+    }
+
+    fn set_sampler(&mut self, sampler: Box<dyn StateSampler<N>>) {
+        self.sampler = sampler;
+    }
+
+    fn set_termination_criteria(&mut self, criteria: Box<dyn TerminationCriteria>) {
+        self.termination_criteria = Some(criteria);
+    }
+
     /// Initializes the RRT planner by adding the start and goal nodes.
+    /// For asymmetric state spaces, the goal is not added to the rtree, so that the tree is only grown from the start.
     fn init(&mut self) {
+        self.start = self.state_space.enforce_bounds(self.start);
+        self.goal = self.state_space.enforce_bounds(self.goal);
         self.add_node(self.start);
-        self.add_node(self.goal);
+        if self.state_space.is_symmetric() {
+            self.add_node(self.goal);
+        } else {
+            let index = self.graph.add_node(self.goal);
+            self.index_node_lookup
+                .insert(self.goal.to_wkt().to_string(), index);
+        }
     }
 
     /// Solves the RRT planner. Runs until the termination criteria is met.
-    fn solve(&mut self) {
+    /// Gives up, if no node could be added for `config.max_sampling_attempts` consecutive iterations.
+    fn solve(&mut self) -> Result<(), PlanningError<N>> {
+        let start_time: Instant = Instant::now();
+        let mut iterations: usize = 0;
+        let mut failed_attempts: usize = 0;
+        self.termination_reason = None;
+        if let Some(criteria) = &mut self.termination_criteria {
+            criteria.reset();
+        }
         loop {
-            let random_node: Point = self.boundaries.generate_random_configuration();
-            if self.collision_checker.is_node_colliding(&random_node) {
-                continue;
-            }
-
-            let nearest_neighbour = match self.get_nearest_neighbor(random_node) {
-                Some(point) => point,
-                None => continue,
-            };
-
-            if self
-                .collision_checker
-                .is_edge_colliding(&random_node, &nearest_neighbour)
-            {
-                continue;
+            iterations += 1;
+            if self.add_random_node() {
+                failed_attempts = 0;
+            } else {
+                failed_attempts += 1;
+                if failed_attempts >= self.config.max_sampling_attempts {
+                    return Err(PlanningError::NoValidSamples {
+                        attempts: failed_attempts,
+                    });
+                }
             }
 
-            self.add_node(random_node);
-            self.add_edge(random_node, nearest_neighbour);
-
-            self.check_solution();
-
-            if self.is_termination_criteria_met() {
-                println!("Termination Criteria met");
-                break;
+            if self.is_termination_criteria_met(iterations, start_time) {
+                return Ok(());
             }
         }
     }
@@ -136,9 +171,22 @@ impl Planner for RRT {
             Some((cost, _)) => *cost,
         }
     }
+
+    /// Returns the points of the solution path from start to goal.
+    /// - empty: No solution was found
+    fn get_solution_path(&self) -> Vec<Point<N>> {
+        match &self.solution {
+            None => Vec::new(),
+            Some((_, path)) => pg::path_to_points(&self.graph, path),
+        }
+    }
+
+    fn get_termination_reason(&self) -> Option<TerminationReason> {
+        self.termination_reason
+    }
 }
 
-impl Default for RRT {
+impl<const N: usize> Default for RRT<N> {
     fn default() -> Self {
         RRT {
             config: Config::default(),
@@ -147,17 +195,24 @@ impl Default for RRT {
             start: Point::default(),
             goal: Point::default(),
             graph: Graph::new_undirected(),
-            tree: RTree::new(),
+            tree: NearestNeighbors::new(),
             index_node_lookup: HashMap::new(),
+            termination_criteria: None,
+            termination_reason: None,
             boundaries: Boundaries::default(),
             collision_checker: NaiveCollisionChecker::new_box(),
+            sampler: UniformSampler::new_box(),
+            state_space: EuclideanStateSpace::new_box(),
         }
     }
 }
 
-impl RRT {
+impl<const N: usize> RRT<N> {
     /// Constructor
-    pub fn new(mut boundaries: Boundaries, collision_checker: Box<dyn CollisionChecker>) -> Self {
+    pub fn new(
+        mut boundaries: Boundaries<N>,
+        collision_checker: Box<dyn CollisionChecker<N>>,
+    ) -> Self {
         RRT {
             config: Config::default(),
             solution: None,
@@ -165,25 +220,72 @@ impl RRT {
             start: boundaries.generate_random_configuration(),
             goal: boundaries.generate_random_configuration(),
             graph: Graph::new_undirected(),
-            tree: RTree::new(),
+            tree: NearestNeighbors::new(),
             index_node_lookup: HashMap::new(),
+            termination_criteria: None,
+            termination_reason: None,
 
             boundaries,
             collision_checker,
+            sampler: UniformSampler::new_box(),
+            state_space: EuclideanStateSpace::new_box(),
+        }
+    }
+
+    /// Replaces the state space, e.g. by `SE2StateSpace` for vehicles with a heading.
+    /// Has to be set before `init`, because the nodes are stored with the keys of the state space.
+    pub fn set_state_space(&mut self, state_space: Box<dyn StateSpace<N>>) {
+        self.state_space = state_space;
+    }
+
+    /// Samples a random node and connects it to its nearest neighbor, if neither the node nor the edge is in collision.
+    ///
+    /// # Returns
+    /// - true: The node was added to the graph
+    /// - false: The node was rejected
+    fn add_random_node(&mut self) -> bool {
+        let random_node: Point<N> = match self
+            .sampler
+            .sample(&mut self.boundaries, self.collision_checker.as_ref())
+        {
+            Some(point) => self.state_space.enforce_bounds(point),
+            None => return false,
+        };
+        if self.collision_checker.is_node_colliding(&random_node) {
+            return false;
+        }
+
+        let nearest_neighbour = match self.get_nearest_neighbor(random_node) {
+            Some(point) => point,
+            None => return false,
+        };
+
+        if self.state_space.is_motion_colliding(
+            self.collision_checker.as_ref(),
+            &nearest_neighbour,
+            &random_node,
+        ) {
+            return false;
         }
+
+        self.add_node(random_node);
+        self.add_edge(nearest_neighbour, random_node);
+
+        self.check_solution();
+        true
     }
 
     /// Adds a node to the graph, lookup for nodeindex to point.wkt, and the rtree.
-    fn add_node(&mut self, node: Point) {
+    fn add_node(&mut self, node: Point<N>) {
         let index = self.graph.add_node(node);
         self.index_node_lookup
             .insert(node.to_wkt().to_string(), index);
-        self.tree.insert([node.get_x(), node.get_y()]);
+        self.tree.insert(self.state_space.as_ref(), node);
     }
 
     /// Adds an edge to the graph and updates the lookup and rtree.
-    fn add_edge(&mut self, begin: Point, end: Point) {
-        let weight: f64 = begin.euclidean_distance(&end);
+    fn add_edge(&mut self, begin: Point<N>, end: Point<N>) {
+        let weight: f64 = self.state_space.distance(&begin, &end);
         let a = self.get_node_index(&begin);
         let b = self.get_node_index(&end);
         self.graph.add_edge(a, b, weight);
@@ -198,7 +300,7 @@ impl RRT {
     /// # Returns
     ///
     /// The `NodeIndex` of the node in the graph.
-    fn get_node_index(&mut self, node: &Point) -> NodeIndex {
+    fn get_node_index(&mut self, node: &Point<N>) -> NodeIndex {
         if let Some(index) = self.index_node_lookup.get(&node.to_wkt().to_string()) {
             *index
         } else {
@@ -207,55 +309,79 @@ impl RRT {
     }
 
     /// Adds a new point to the RRT graph by connecting it to its nearest neighbor in the graph.
+    /// The point itself is skipped. Nothing is added, if the point is already connected to that neighbor.
     ///
     /// # Arguments
     ///
     /// * `point` - The point to be added to the graph.
-    fn add_point_to_graph(&mut self, point: Point) {
+    /// * `outgoing` - Connect from the point to the neighbor, e.g. for the start. Otherwise from the neighbor to the point.
+    fn add_point_to_graph(&mut self, point: Point<N>, outgoing: bool) {
         // Find the first non-colliding neighbor (lazy evaluation)
-        let neighbor_to_connect: Option<[f64; 2]> = self
+        let state_space: &dyn StateSpace<N> = self.state_space.as_ref();
+        let motion = |neighbor: Point<N>| {
+            if outgoing {
+                (point, neighbor)
+            } else {
+                (neighbor, point)
+            }
+        };
+        let neighbor_to_connect: Option<Point<N>> = self
             .tree
-            .nearest_neighbor_iter(&[point.get_x(), point.get_y()])
-            .find(|coords| {
-                let neighbor = Point::new(coords[0], coords[1]);
-                !self.collision_checker.is_edge_colliding(&neighbor, &point)
+            .iter_nearest(state_space, &point, |neighbor| {
+                let (begin, end) = motion(*neighbor);
+                state_space.distance(&begin, &end)
             })
-            .copied();
+            .map(|(neighbor, _)| neighbor)
+            .filter(|neighbor| *neighbor != point)
+            .find(|neighbor| {
+                let (begin, end) = motion(*neighbor);
+                !state_space.is_motion_colliding(self.collision_checker.as_ref(), &begin, &end)
+            });
 
         // Now add the edge after the immutable borrow has ended
-        if let Some(coords) = neighbor_to_connect {
-            let neighbor = Point::new(coords[0], coords[1]);
-            self.add_edge(neighbor, point);
+        if let Some(neighbor) = neighbor_to_connect {
+            let (begin, end) = motion(neighbor);
+            let a = self.get_node_index(&begin);
+            let b = self.get_node_index(&end);
+            if self.graph.find_edge(a, b).is_none() {
+                self.add_edge(begin, end);
+            }
         }
     }
 
     /// Applies A* and checks if a solution exists
     fn check_solution(&mut self) {
-        self.add_point_to_graph(self.goal);
-        self.add_point_to_graph(self.start);
-
-        let start: NodeIndex = *self
-            .index_node_lookup
-            .get(&self.start.to_wkt().to_string())
-            .unwrap();
-        let goal: NodeIndex = *self
-            .index_node_lookup
-            .get(&self.goal.to_wkt().to_string())
-            .unwrap();
-        self.solution = astar(
-            &self.graph,
-            start,
-            |finish: NodeIndex| finish == goal,
-            |e: petgraph::graph::EdgeReference<'_, f64>| *e.weight(),
-            |_| f64::default(),
-        );
+        self.add_point_to_graph(self.goal, false);
+        self.add_point_to_graph(self.start, true);
+
+        let (Some(&start), Some(&goal)) = (
+            self.index_node_lookup.get(&self.start.to_wkt().to_string()),
+            self.index_node_lookup.get(&self.goal.to_wkt().to_string()),
+        ) else {
+            return;
+        };
+        self.solution = pg::find_path(&self.graph, start, goal, !self.state_space.is_symmetric());
 
         self.is_solved = self.solution.is_some();
     }
 
-    /// Determines which criteria is used to stop the algorithm. Check the max_size parameter and compares it to the number of nodes in the graph.
-    fn is_termination_criteria_met(&self) -> bool {
-        self.graph.node_count() >= self.config.max_size
+    /// Determines whether the algorithm stops. Uses the termination criteria, if one was set.
+    /// Otherwise the max_size parameter is compared to the number of nodes in the graph.
+    fn is_termination_criteria_met(&mut self, iterations: usize, start_time: Instant) -> bool {
+        let progress: PlannerProgress = PlannerProgress {
+            iterations,
+            node_count: self.graph.node_count(),
+            solution_cost: self.solution.as_ref().map(|(cost, _)| *cost),
+            elapsed: start_time.elapsed(),
+        };
+        self.termination_reason = match &mut self.termination_criteria {
+            Some(criteria) => criteria.check(&progress),
+            None => NodeLimit {
+                max_nodes: self.config.max_size,
+            }
+            .check(&progress),
+        };
+        self.termination_reason.is_some()
     }
 
     /// Returns an Option to the nearest neighbor from the given point
@@ -268,9 +394,13 @@ impl RRT {
     ///
     /// - `None`: If there is no neighbor
     /// - `Some(Point)`: If there is a nearest neighbor, contains the nearest neighbor
-    fn get_nearest_neighbor(&self, node: Point) -> Option<Point> {
-        let neighbor: Option<&[f64; 2]> = self.tree.nearest_neighbor(&[node.get_x(), node.get_y()]);
-        neighbor.map(|coords| Point::new(coords[0], coords[1]))
+    fn get_nearest_neighbor(&self, node: Point<N>) -> Option<Point<N>> {
+        let state_space: &dyn StateSpace<N> = self.state_space.as_ref();
+        self.tree
+            .nearest(state_space, &node, |neighbor| {
+                state_space.distance(neighbor, &node)
+            })
+            .map(|(neighbor, _)| neighbor)
     }
 }
 
@@ -278,6 +408,7 @@ impl RRT {
 mod test {
     use crate::planner::rrt::Config;
     use crate::planner::rrt::RRT;
+    use crate::test_support::BlockedCollisionChecker;
     use crate::{
         boundaries::Boundaries,
         collision_checker::{CollisionChecker, NaiveCollisionChecker},
@@ -313,4 +444,61 @@ mod test {
         let node_index: NodeIndex = NodeIndex::new(0);
         assert_eq!(rrt.graph.node_weight(node_index), Some(&node));
     }
+
+    // Test that a map where no sample can be connected makes the planner give up instead of looping forever.
+    #[test]
+    fn test_blocked_map_no_valid_samples() {
+        use crate::error::PlanningError;
+        use crate::planner::base_planner::Planner;
+
+        let bounds: Boundaries = Boundaries::new(0f64, 3f64, 0f64, 3f64);
+        let mut rrt: RRT = RRT::new(bounds, Box::new(BlockedCollisionChecker {}));
+        rrt.config.max_sampling_attempts = 50;
+        rrt.init();
+        assert!(matches!(
+            rrt.solve(),
+            Err(PlanningError::NoValidSamples { attempts: 50 })
+        ));
+    }
+
+    // Test that the tree grows in 3D and stays inside the boundaries.
+    #[test]
+    fn test_rrt_3d() {
+        use crate::planner::base_planner::Planner;
+
+        let mut bounds: Boundaries<3> = Boundaries::from_limits([0f64; 3], [1f64, 2f64, 3f64]);
+        bounds.set_seed(42);
+        let mut rrt: RRT<3> = RRT::new(bounds.clone(), NaiveCollisionChecker::new_box());
+        rrt.init();
+        rrt.solve().unwrap();
+
+        assert!(rrt.graph.node_count() >= rrt.config.max_size);
+        assert_eq!(rrt.tree.size(), rrt.index_node_lookup.len());
+        assert!(rrt
+            .graph
+            .node_weights()
+            .all(|node| bounds.is_node_inside(node)));
+    }
+
+    // Test that the tree grows in SE(2) and every heading is wrapped.
+    #[test]
+    fn test_rrt_se2() {
+        use crate::planner::base_planner::Planner;
+        use crate::state_space::SE2StateSpace;
+
+        let mut bounds: Boundaries<3> = SE2StateSpace::boundaries(0f64, 3f64, 0f64, 3f64);
+        bounds.set_seed(7);
+        let mut rrt: RRT<3> = RRT::new(bounds.clone(), NaiveCollisionChecker::new_box());
+        rrt.set_state_space(SE2StateSpace::new_box(1f64));
+        rrt.set_start(Point::from_coordinates([1f64, 1f64, 7f64]));
+        rrt.init();
+        rrt.solve().unwrap();
+
+        assert_eq!(rrt.tree.size(), rrt.index_node_lookup.len());
+        assert!(rrt.graph.node_weights().all(|node| {
+            let theta: f64 = node.get_coordinates()[2];
+            (-std::f64::consts::PI..std::f64::consts::PI).contains(&theta)
+                && bounds.is_node_inside(node)
+        }));
+    }
 }
@@ -0,0 +1,674 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use petgraph::graph::{Graph, NodeIndex};
+use petgraph::Undirected;
+
+use crate::boundaries::Boundaries;
+use crate::collision_checker::{CollisionChecker, MotionCheck, NaiveCollisionChecker};
+use crate::error::PlanningError;
+use crate::planner::base_planner::Planner;
+use crate::planner::graph_utils as pg;
+use crate::planner::nearest_neighbors::NearestNeighbors;
+use crate::planner::termination::{
+    NodeLimit, PlannerProgress, TerminationCriteria, TerminationReason,
+};
+use crate::sampler::{StateSampler, UniformSampler};
+use crate::space::Point;
+use crate::state_space::{EuclideanStateSpace, StateSpace};
+
+/// # Holds configuration parameters for RRT-Connect
+/// It does configure:
+/// - max_size: Limits the number of Nodes in both trees before termination of the algorithm
+/// - step_size: Maximum distance a tree grows towards a sample in a single extend step
+/// - max_sampling_attempts: Limits the number of consecutive failed attempts to add a node before the planner gives up
+pub struct Config {
+    pub max_size: usize,
+    pub step_size: f64,
+    pub max_sampling_attempts: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            max_size: 1000usize,
+            step_size: 0.25f64,
+            max_sampling_attempts: 1000usize,
+        }
+    }
+}
+
+/// Identifies one of the two trees grown by RRT-Connect.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum TreeSide {
+    Start,
+    Goal,
+}
+
+impl TreeSide {
+    fn other(&self) -> TreeSide {
+        match self {
+            TreeSide::Start => TreeSide::Goal,
+            TreeSide::Goal => TreeSide::Start,
+        }
+    }
+}
+
+/// Outcome of a single extend step.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum ExtendResult<const N: usize> {
+    /// The target was reached and is now part of the tree.
+    Reached(Point<N>),
+    /// The tree grew by one step towards the target.
+    Advanced(Point<N>),
+    /// The tree could not grow towards the target.
+    Trapped,
+}
+
+/// # Bidirectional Rapidly-Exploring Random Trees - RRT-Connect
+/// It is an algorithm which is:
+/// - probabilistically complete
+/// - not optimal
+/// - Single query
+///
+/// Grows one tree from the start and one from the goal. Each iteration extends one tree towards a random sample
+/// and then greedily tries to connect the other tree to the new node. The trees swap roles after every iteration.
+/// The algorithm stops as soon as both trees are connected.
+/// Distances and motions are defined by the state space, which is Euclidean by default.
+/// For asymmetric state spaces the goal tree is grown backwards, so its motions lead towards the goal.
+///
+/// # Source / Credits
+/// Kuffner, J. J.; LaValle, S. M. (2000), "RRT-connect: An efficient approach to single-query path planning", IEEE International Conference on Robotics and Automation, 2: 995–1001, doi:10.1109/ROBOT.2000.844730
+///
+/// Plans in any dimension `N`. The default is 2D.
+///
+/// # Example
+/// ```
+/// use mpl::boundaries::Boundaries;
+/// use mpl::collision_checker::NaiveCollisionChecker;
+/// use mpl::planner::base_planner::Planner;
+/// use mpl::planner::rrt_connect::RRTConnect;
+/// use mpl::space::Point;
+///
+/// let mut planner: RRTConnect = RRTConnect::new(
+///     Boundaries::new(0f64, 3f64, 0f64, 3f64),
+///     NaiveCollisionChecker::new_box(),
+/// );
+/// planner.set_start(Point::new(0.5, 0.5));
+/// planner.set_goal(Point::new(2.5, 2.5));
+/// planner.init();
+/// planner.solve().unwrap();
+/// assert_eq!(planner.get_solution_path().last(), Some(&Point::new(2.5, 2.5)));
+/// ```
+pub struct RRTConnect<const N: usize = 2> {
+    pub solution: Option<(f64, Vec<NodeIndex>)>,
+    pub is_solved: bool,
+    pub start: Point<N>,
+    pub goal: Point<N>,
+    pub graph: Graph<Point<N>, f64, Undirected>,
+    start_tree: NearestNeighbors<N>,
+    goal_tree: NearestNeighbors<N>,
+    index_node_lookup: HashMap<String, NodeIndex>,
+    termination_criteria: Option<Box<dyn TerminationCriteria>>,
+    termination_reason: Option<TerminationReason>,
+    pub boundaries: Boundaries<N>,
+    pub collision_checker: Box<dyn CollisionChecker<N>>,
+    pub sampler: Box<dyn StateSampler<N>>,
+    pub state_space: Box<dyn StateSpace<N>>,
+    pub config: Config,
+}
+
+impl<const N: usize> Planner<N> for RRTConnect<N> {
+    /// Sets the start point for the RRT-Connect planner.
+    fn set_start(&mut self, start: Point<N>) {
+        self.start = start;
+    }
+
+    /// Sets the goal point for the RRT-Connect planner.
+    fn set_goal(&mut self, goal: Point<N>) {
+        self.goal = goal;
+    }
+
+    /// Sets the boundaries for the RRT-Connect planner.
+    fn set_boundaries(&mut self, boundaries: Boundaries<N>) {
+        self.boundaries = boundaries;
+    }
+
+    /// Sets the collision checker for the RRT-Connect planner.
+    fn set_collision_checker(&mut self, cc: Box<dyn CollisionChecker<N>>) {
+        self.collision_checker = cc;
+    }
+
+    fn get_collision_checker(&self) -> &dyn CollisionChecker<N> {
+        self.collision_checker.as_ref()
+    }
+
+    fn set_sampler(&mut self, sampler: Box<dyn StateSampler<N>>) {
+        self.sampler = sampler;
+    }
+
+    fn set_termination_criteria(&mut self, criteria: Box<dyn TerminationCriteria>) {
+        self.termination_criteria = Some(criteria);
+    }
+
+    /// Initializes the planner by adding the start node as root of the start tree and the goal node as root of the goal tree.
+    fn init(&mut self) {
+        self.start = self.state_space.enforce_bounds(self.start);
+        self.goal = self.state_space.enforce_bounds(self.goal);
+        self.add_node(TreeSide::Start, self.start);
+        self.add_node(TreeSide::Goal, self.goal);
+    }
+
+    /// Alternately grows both trees until they are connected or the termination criteria is met.
+    /// A found solution always stops the planner, because RRT-Connect does not improve it.
+    /// Gives up, if no tree could grow for `config.max_sampling_attempts` consecutive iterations.
+    fn solve(&mut self) -> Result<(), PlanningError<N>> {
+        let start_time: Instant = Instant::now();
+        let mut iterations: usize = 0;
+        let mut failed_attempts: usize = 0;
+        self.termination_reason = None;
+        if let Some(criteria) = &mut self.termination_criteria {
+            criteria.reset();
+        }
+        let mut side: TreeSide = TreeSide::Start;
+        loop {
+            if self.is_solved {
+                self.termination_reason = Some(TerminationReason::SolutionFound);
+                return Ok(());
+            }
+            if self.is_termination_criteria_met(iterations, start_time) {
+                return Ok(());
+            }
+            iterations += 1;
+
+            let result: ExtendResult<N> = match self
+                .sampler
+                .sample(&mut self.boundaries, self.collision_checker.as_ref())
+            {
+                Some(random_node) => {
+                    self.extend(side, self.state_space.enforce_bounds(random_node))
+                }
+                None => ExtendResult::Trapped,
+            };
+            match result {
+                ExtendResult::Trapped => {
+                    failed_attempts += 1;
+                    if failed_attempts >= self.config.max_sampling_attempts {
+                        return Err(PlanningError::NoValidSamples {
+                            attempts: failed_attempts,
+                        });
+                    }
+                }
+                ExtendResult::Advanced(new_node) | ExtendResult::Reached(new_node) => {
+                    failed_attempts = 0;
+                    if let ExtendResult::Reached(_) = self.connect(side.other(), new_node) {
+                        self.check_solution();
+                    }
+                }
+            }
+            side = side.other();
+        }
+    }
+
+    /// Returns the solution cost.
+    /// - f64::MAX: No solution was found
+    /// - cost: f64he cost of the solution. Implies that a solution was found.
+    fn get_solution_cost(&self) -> f64 {
+        match &self.solution {
+            None => f64::MAX,
+            Some((cost, _)) => *cost,
+        }
+    }
+
+    /// Returns the points of the solution path from start to goal.
+    /// - empty: No solution was found
+    fn get_solution_path(&self) -> Vec<Point<N>> {
+        match &self.solution {
+            None => Vec::new(),
+            Some((_, path)) => pg::path_to_points(&self.graph, path),
+        }
+    }
+
+    fn get_termination_reason(&self) -> Option<TerminationReason> {
+        self.termination_reason
+    }
+}
+
+impl<const N: usize> Default for RRTConnect<N> {
+    fn default() -> Self {
+        RRTConnect::new(Boundaries::default(), NaiveCollisionChecker::new_box())
+    }
+}
+
+impl<const N: usize> RRTConnect<N> {
+    /// Constructor
+    pub fn new(boundaries: Boundaries<N>, collision_checker: Box<dyn CollisionChecker<N>>) -> Self {
+        RRTConnect {
+            solution: None,
+            is_solved: false,
+            start: Point::default(),
+            goal: Point::default(),
+            graph: Graph::new_undirected(),
+            start_tree: NearestNeighbors::new(),
+            goal_tree: NearestNeighbors::new(),
+            index_node_lookup: HashMap::new(),
+            termination_criteria: None,
+            termination_reason: None,
+            boundaries,
+            collision_checker,
+            sampler: UniformSampler::new_box(),
+            state_space: EuclideanStateSpace::new_box(),
+            config: Config::default(),
+        }
+    }
+
+    /// Replaces the state space, e.g. by `JointStateSpace` for revolute joints.
+    /// Has to be set before `init`, because the nodes are stored with the keys of the state space.
+    pub fn set_state_space(&mut self, state_space: Box<dyn StateSpace<N>>) {
+        self.state_space = state_space;
+    }
+
+    /// Returns the nearest neighbor structure that holds the nodes of the given tree.
+    fn tree(&self, side: TreeSide) -> &NearestNeighbors<N> {
+        match side {
+            TreeSide::Start => &self.start_tree,
+            TreeSide::Goal => &self.goal_tree,
+        }
+    }
+
+    /// Adds a node to the graph, lookup for nodeindex to point.wkt, and the rtree of the given tree.
+    fn add_node(&mut self, side: TreeSide, node: Point<N>) -> NodeIndex {
+        let index = self.graph.add_node(node);
+        self.index_node_lookup
+            .insert(node.to_wkt().to_string(), index);
+        match side {
+            TreeSide::Start => self.start_tree.insert(self.state_space.as_ref(), node),
+            TreeSide::Goal => self.goal_tree.insert(self.state_space.as_ref(), node),
+        }
+        index
+    }
+
+    /// Returns the motion between a node of the given tree and another point as pair of begin and end.
+    /// The motion leads away from the tree, except for the goal tree of an asymmetric state space.
+    fn get_motion(&self, side: TreeSide, node: Point<N>, other: Point<N>) -> (Point<N>, Point<N>) {
+        if side == TreeSide::Goal && !self.state_space.is_symmetric() {
+            (other, node)
+        } else {
+            (node, other)
+        }
+    }
+
+    /// Returns the nearest node of the given tree to the point.
+    fn get_nearest_neighbor(&self, side: TreeSide, node: Point<N>) -> Option<Point<N>> {
+        let state_space: &dyn StateSpace<N> = self.state_space.as_ref();
+        self.tree(side)
+            .nearest(state_space, &node, |neighbor| {
+                let (begin, end) = self.get_motion(side, *neighbor, node);
+                state_space.distance(&begin, &end)
+            })
+            .map(|(neighbor, _)| neighbor)
+    }
+
+    /// Moves from the node of the given tree towards `to`, but at most `config.step_size` far.
+    /// For a backwards grown tree, the new point lies on the motion from `to` to the node.
+    fn steer(&self, side: TreeSide, from: Point<N>, to: Point<N>) -> Point<N> {
+        let (begin, end) = self.get_motion(side, from, to);
+        let distance: f64 = self.state_space.distance(&begin, &end);
+        if distance <= self.config.step_size {
+            to
+        } else if begin == from {
+            self.state_space
+                .interpolate(&from, &to, self.config.step_size / distance)
+        } else {
+            self.state_space
+                .interpolate(&to, &from, 1f64 - self.config.step_size / distance)
+        }
+    }
+
+    /// Checks the new node and the motion between it and the node of the given tree.
+    fn is_motion_colliding(&self, side: TreeSide, node: Point<N>, new_node: Point<N>) -> bool {
+        let (begin, end) = self.get_motion(side, node, new_node);
+        self.collision_checker.is_node_colliding(&new_node)
+            || self
+                .state_space
+                .is_motion_colliding(self.collision_checker.as_ref(), &begin, &end)
+    }
+
+    /// Returns the last state before the first collision on the motion from the node of the given tree to `to`.
+    /// - None: The collision checker does not report it, e.g. it is no `DiscreteMotionValidator`, or the motion leads towards the tree.
+    fn get_last_valid(&self, side: TreeSide, from: Point<N>, to: Point<N>) -> Option<Point<N>> {
+        if self.get_motion(side, from, to) != (from, to) {
+            return None;
+        }
+        let mut last_valid: Option<Point<N>> = None;
+        for (begin, end) in self.state_space.get_segments(&from, &to) {
+            let motion: MotionCheck<N> = self.collision_checker.check_motion(&begin, &end)?;
+            if !motion.is_valid {
+                return motion
+                    .last_valid
+                    .or(last_valid)
+                    .map(|state| self.state_space.enforce_bounds(state));
+            }
+            last_valid = motion.last_valid;
+        }
+        None
+    }
+
+    /// Grows the given tree one step from its nearest node towards the target.
+    /// If the motion is in collision, the tree advances to the last valid state, if the collision checker reports it.
+    ///
+    /// If the new node already belongs to the other tree, only the edge is added. This joins both trees in the graph.
+    fn extend(&mut self, side: TreeSide, target: Point<N>) -> ExtendResult<N> {
+        let nearest: Point<N> = match self.get_nearest_neighbor(side, target) {
+            Some(point) => point,
+            None => return ExtendResult::Trapped,
+        };
+
+        let mut new_node: Point<N> = self.steer(side, nearest, target);
+        if new_node == nearest {
+            return ExtendResult::Trapped;
+        }
+        if self.is_motion_colliding(side, nearest, new_node) {
+            new_node = match self.get_last_valid(side, nearest, new_node) {
+                Some(state)
+                    if state != nearest && !self.is_motion_colliding(side, nearest, state) =>
+                {
+                    state
+                }
+                _ => return ExtendResult::Trapped,
+            };
+        }
+        let (begin, end) = self.get_motion(side, nearest, new_node);
+
+        let a: NodeIndex = match self.index_node_lookup.get(&nearest.to_wkt().to_string()) {
+            Some(index) => *index,
+            None => return ExtendResult::Trapped,
+        };
+        let b: NodeIndex = match self.index_node_lookup.get(&new_node.to_wkt().to_string()) {
+            Some(index) => *index,
+            None => self.add_node(side, new_node),
+        };
+        let weight: f64 = self.state_space.distance(&begin, &end);
+        match side {
+            TreeSide::Goal if !self.state_space.is_symmetric() => self.graph.add_edge(b, a, weight),
+            _ => self.graph.add_edge(a, b, weight),
+        };
+
+        if new_node == target {
+            ExtendResult::Reached(new_node)
+        } else {
+            ExtendResult::Advanced(new_node)
+        }
+    }
+
+    /// Greedily extends the given tree towards the target until it is reached or the tree gets trapped.
+    fn connect(&mut self, side: TreeSide, target: Point<N>) -> ExtendResult<N> {
+        loop {
+            match self.extend(side, target) {
+                ExtendResult::Advanced(_) => continue,
+                result => return result,
+            }
+        }
+    }
+
+    /// Applies A* on the joined trees and checks if a solution exists.
+    /// The edges of asymmetric state spaces are only followed in the direction of their motion.
+    fn check_solution(&mut self) {
+        let (Some(&start), Some(&goal)) = (
+            self.index_node_lookup.get(&self.start.to_wkt().to_string()),
+            self.index_node_lookup.get(&self.goal.to_wkt().to_string()),
+        ) else {
+            return;
+        };
+        self.solution = pg::find_path(&self.graph, start, goal, !self.state_space.is_symmetric());
+
+        self.is_solved = self.solution.is_some();
+    }
+
+    /// Determines whether the algorithm stops. Uses the termination criteria, if one was set.
+    /// Otherwise the max_size parameter is compared to the number of nodes in the graph.
+    fn is_termination_criteria_met(&mut self, iterations: usize, start_time: Instant) -> bool {
+        let progress: PlannerProgress = PlannerProgress {
+            iterations,
+            node_count: self.graph.node_count(),
+            solution_cost: self.solution.as_ref().map(|(cost, _)| *cost),
+            elapsed: start_time.elapsed(),
+        };
+        self.termination_reason = match &mut self.termination_criteria {
+            Some(criteria) => criteria.check(&progress),
+            None => NodeLimit {
+                max_nodes: self.config.max_size,
+            }
+            .check(&progress),
+        };
+        self.termination_reason.is_some()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ExtendResult, RRTConnect, TreeSide};
+    use crate::boundaries::Boundaries;
+    use crate::collision_checker::{CollisionChecker, NaiveCollisionChecker};
+    use crate::planner::base_planner::Planner;
+    use crate::space::Point;
+    use crate::test_support::{BlockedCollisionChecker, SeamCollisionChecker};
+
+    /// Blocks the vertical corridor 1 < x < 2 except for a narrow gap around y = 1.5.
+    struct WallCollisionChecker {}
+
+    impl CollisionChecker for WallCollisionChecker {
+        fn init(&self) -> bool {
+            true
+        }
+
+        fn is_node_colliding(&self, node: &Point) -> bool {
+            let in_wall = node.get_x() > 1.0 && node.get_x() < 2.0;
+            let in_gap = node.get_y() > 1.3 && node.get_y() < 1.7;
+            in_wall && !in_gap
+        }
+
+        fn is_edge_colliding(&self, begin: &Point, end: &Point) -> bool {
+            (0..=20).any(|i| self.is_node_colliding(&begin.interpolate(end, i as f64 / 20.0)))
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let bounds: Boundaries = Boundaries::new(0f64, 3f64, 0f64, 3f64);
+        let rrt: RRTConnect = RRTConnect::new(bounds, NaiveCollisionChecker::new_box());
+        assert!(!rrt.is_solved);
+        assert_eq!(rrt.graph.node_count(), 0);
+    }
+
+    #[test]
+    fn test_init_adds_roots_to_both_trees() {
+        let mut rrt: RRTConnect = RRTConnect::default();
+        rrt.set_start(Point::new(0f64, 0f64));
+        rrt.set_goal(Point::new(3f64, 3f64));
+        rrt.init();
+        assert_eq!(rrt.graph.node_count(), 2);
+        assert_eq!(rrt.start_tree.size(), 1);
+        assert_eq!(rrt.goal_tree.size(), 1);
+    }
+
+    // Test that a single extend step does not grow further than the step size.
+    #[test]
+    fn test_extend_respects_step_size() {
+        let mut rrt: RRTConnect = RRTConnect::default();
+        rrt.set_start(Point::new(0f64, 0f64));
+        rrt.set_goal(Point::new(3f64, 3f64));
+        rrt.init();
+
+        let result = rrt.extend(TreeSide::Start, Point::new(3f64, 0f64));
+        let expected: Point = Point::new(rrt.config.step_size, 0f64);
+        assert_eq!(result, ExtendResult::Advanced(expected));
+        assert_eq!(rrt.start_tree.size(), 2);
+        assert_eq!(rrt.goal_tree.size(), 1);
+    }
+
+    // Test that a motion validator lets the tree advance up to the obstacle instead of discarding the whole step.
+    #[test]
+    fn test_extend_advances_to_last_valid_state() {
+        use crate::collision_checker::DiscreteMotionValidator;
+
+        let mut rrt: RRTConnect = RRTConnect::new(
+            Boundaries::new(0f64, 3f64, 0f64, 3f64),
+            DiscreteMotionValidator::new_box(Box::new(WallCollisionChecker {}), 0.1),
+        );
+        rrt.config.step_size = 3f64;
+        rrt.set_start(Point::new(0f64, 0.5));
+        rrt.set_goal(Point::new(3f64, 0.5));
+        rrt.init();
+
+        let result = rrt.extend(TreeSide::Start, Point::new(3f64, 0.5));
+        let ExtendResult::Advanced(new_node) = result else {
+            panic!("expected the tree to advance, got {:?}", result);
+        };
+        assert!((new_node.get_x() - 1f64).abs() < 1e-9);
+        assert_eq!(new_node.get_y(), 0.5);
+        assert_eq!(rrt.start_tree.size(), 2);
+
+        // The next step starts at the obstacle and can not advance any further.
+        assert_eq!(
+            rrt.extend(TreeSide::Start, Point::new(3f64, 0.5)),
+            ExtendResult::Trapped
+        );
+
+        // Without the motion validator the whole step is discarded.
+        rrt.set_collision_checker(Box::new(WallCollisionChecker {}));
+        assert_eq!(
+            rrt.extend(TreeSide::Goal, Point::new(0f64, 0.5)),
+            ExtendResult::Trapped
+        );
+        assert_eq!(rrt.goal_tree.size(), 1);
+    }
+
+    #[test]
+    fn test_connect_reaches_close_target() {
+        let mut rrt: RRTConnect = RRTConnect::default();
+        rrt.set_start(Point::new(0f64, 0f64));
+        rrt.set_goal(Point::new(1f64, 0f64));
+        rrt.init();
+
+        let result = rrt.connect(TreeSide::Start, Point::new(1f64, 0f64));
+        assert_eq!(result, ExtendResult::Reached(Point::new(1f64, 0f64)));
+        rrt.check_solution();
+        assert!(rrt.is_solved);
+        assert!((rrt.get_solution_cost() - 1f64).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_solve_free_space() {
+        let start: Point = Point::new(0f64, 0f64);
+        let goal: Point = Point::new(3f64, 3f64);
+        let mut rrt: RRTConnect = RRTConnect::new(
+            Boundaries::new(0f64, 3f64, 0f64, 3f64),
+            NaiveCollisionChecker::new_box(),
+        );
+        rrt.set_start(start);
+        rrt.set_goal(goal);
+        rrt.init();
+        rrt.solve().unwrap();
+
+        assert!(rrt.is_solved);
+        let path: Vec<Point> = rrt.get_solution_path();
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+    }
+
+    // Test that the trees find their way through a narrow gap in a wall.
+    #[test]
+    fn test_solve_narrow_passage() {
+        let start: Point = Point::new(0.5f64, 0.5f64);
+        let goal: Point = Point::new(2.5f64, 2.5f64);
+        let mut rrt: RRTConnect = RRTConnect::new(
+            Boundaries::new(0f64, 3f64, 0f64, 3f64),
+            Box::new(WallCollisionChecker {}),
+        );
+        rrt.config.max_size = 100_000usize;
+        rrt.set_start(start);
+        rrt.set_goal(goal);
+        rrt.init();
+        rrt.solve().unwrap();
+
+        assert!(rrt.is_solved);
+        let path: Vec<Point> = rrt.get_solution_path();
+        for pair in path.windows(2) {
+            assert!(!rrt.collision_checker.is_edge_colliding(&pair[0], &pair[1]));
+        }
+    }
+
+    // Test that the planner gives up once the termination criteria is met, even without a solution.
+    #[test]
+    fn test_solve_stops_without_solution() {
+        use crate::planner::termination::{IterationLimit, TerminationReason};
+
+        let mut rrt: RRTConnect = RRTConnect::new(
+            Boundaries::new(0f64, 3f64, 0f64, 3f64),
+            Box::new(WallCollisionChecker {}),
+        );
+        rrt.set_start(Point::new(0.5f64, 0.5f64));
+        rrt.set_goal(Point::new(2.5f64, 2.5f64));
+        rrt.set_termination_criteria(IterationLimit::new_box(1));
+        rrt.init();
+        rrt.solve().unwrap();
+
+        assert!(!rrt.is_solved);
+        assert_eq!(
+            rrt.get_termination_reason(),
+            Some(TerminationReason::IterationLimit)
+        );
+    }
+
+    // Test that trees that can never grow make the planner give up instead of looping forever.
+    #[test]
+    fn test_blocked_map_no_valid_samples() {
+        use crate::error::PlanningError;
+
+        let mut rrt: RRTConnect = RRTConnect::new(
+            Boundaries::new(0f64, 3f64, 0f64, 3f64),
+            Box::new(BlockedCollisionChecker {}),
+        );
+        rrt.set_start(Point::new(0f64, 0f64));
+        rrt.set_goal(Point::new(3f64, 3f64));
+        rrt.init();
+        assert!(matches!(
+            rrt.solve(),
+            Err(PlanningError::NoValidSamples { attempts: 1000 })
+        ));
+        assert!(!rrt.is_solved);
+    }
+
+    // Test that the trees connect across the seam of a circular axis, because the long way is blocked.
+    #[test]
+    fn test_circular_axis() {
+        use crate::boundaries::Topology;
+        use crate::state_space::{JointStateSpace, StateSpace};
+        use std::f64::consts::PI;
+
+        let mut boundaries: Boundaries = Boundaries::new(-PI, PI, 0f64, 1f64);
+        boundaries.set_topology([Topology::Circular, Topology::Bounded]);
+        boundaries.set_seed(2);
+        let mut rrt: RRTConnect =
+            RRTConnect::new(boundaries.clone(), Box::new(SeamCollisionChecker {}));
+        rrt.set_state_space(JointStateSpace::new_box(&boundaries));
+        rrt.set_start(Point::new(2.8, 0.5));
+        rrt.set_goal(Point::new(-2.8, 0.5));
+        rrt.init();
+        rrt.solve().unwrap();
+        assert!(rrt.is_solved);
+
+        let space: JointStateSpace = JointStateSpace::new(&boundaries);
+        let path: Vec<Point> = rrt.get_solution_path();
+        let length: f64 = path
+            .windows(2)
+            .map(|pair| space.distance(&pair[0], &pair[1]))
+            .sum();
+        assert!((rrt.get_solution_cost() - length).abs() < 1e-9);
+        assert!(length < 2f64);
+        for pair in path.windows(2) {
+            assert!(!space.is_motion_colliding(&SeamCollisionChecker {}, &pair[0], &pair[1]));
+        }
+    }
+}
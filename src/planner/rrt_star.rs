@@ -0,0 +1,712 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use petgraph::graph::{Graph, NodeIndex};
+use petgraph::Undirected;
+
+use crate::boundaries::Boundaries;
+use crate::collision_checker::{CollisionChecker, NaiveCollisionChecker};
+use crate::error::PlanningError;
+use crate::optimizer::{DefaultOptimizer, Optimizer};
+use crate::planner::base_planner::Planner;
+use crate::planner::graph_utils as pg;
+use crate::planner::nearest_neighbors::NearestNeighbors;
+use crate::planner::termination::{
+    NodeLimit, PlannerProgress, TerminationCriteria, TerminationReason,
+};
+use crate::sampler::{StateSampler, UniformSampler};
+use crate::space::Point;
+use crate::state_space::{EuclideanStateSpace, StateSpace};
+
+/// # Holds configuration parameters for RRT*
+/// It does configure:
+/// - max_size: Limits the number of Nodes in the tree before termination of the algorithm
+/// - step_size: Maximum distance a new node is placed away from its nearest neighbor. Also caps the connection radius.
+/// - gamma: Constant of the connection radius gamma * (log(n) / n)^(1/d). If `None`, a value that guarantees asymptotic optimality is derived from the boundaries.
+/// - max_sampling_attempts: Limits the number of consecutive failed attempts to add a node before the planner gives up
+pub struct Config {
+    pub max_size: usize,
+    pub step_size: f64,
+    pub gamma: Option<f64>,
+    pub max_sampling_attempts: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            max_size: 1000usize,
+            step_size: 0.5f64,
+            gamma: None,
+            max_sampling_attempts: 1000usize,
+        }
+    }
+}
+
+/// # Optimal Rapidly-Exploring Random Trees - RRT*
+/// It is an algorithm which is:
+/// - probabilistically complete
+/// - asymptotically optimal
+/// - Single query
+///
+/// Every new node is connected to the neighbor within a shrinking radius that gives the lowest cost-to-come.
+/// Afterwards the neighbors are rewired through the new node, if that lowers their cost-to-come.
+/// Edge costs are provided by the `Optimizer`.
+/// Distances and motions are defined by the state space, which is Euclidean by default.
+///
+/// # Source / Credits
+/// Karaman, S.; Frazzoli, E. (2011), "Sampling-based algorithms for optimal motion planning", The International Journal of Robotics Research, 30 (7): 846–894, doi:10.1177/0278364911406761
+///
+/// Plans in any dimension `N`. The default is 2D.
+///
+/// # Example
+/// ```
+/// use mpl::boundaries::Boundaries;
+/// use mpl::collision_checker::NaiveCollisionChecker;
+/// use mpl::optimizer::DefaultOptimizer;
+/// use mpl::planner::base_planner::Planner;
+/// use mpl::planner::rrt_star::RRTstar;
+/// use mpl::space::Point;
+///
+/// let mut planner: RRTstar = RRTstar::new(
+///     Boundaries::new(0f64, 3f64, 0f64, 3f64),
+///     DefaultOptimizer::new_box(),
+///     NaiveCollisionChecker::new_box(),
+/// );
+/// planner.set_start(Point::new(0.5, 0.5));
+/// planner.set_goal(Point::new(2.5, 2.5));
+/// planner.init();
+/// planner.solve().unwrap();
+/// let straight: f64 = Point::new(0.5, 0.5).euclidean_distance(&Point::new(2.5, 2.5));
+/// assert!(planner.get_solution_cost() >= straight);
+/// ```
+pub struct RRTstar<const N: usize = 2> {
+    pub solution: Option<(f64, Vec<NodeIndex>)>,
+    pub is_solved: bool,
+    pub start: Point<N>,
+    pub goal: Point<N>,
+    pub graph: Graph<Point<N>, f64, Undirected>,
+    tree: NearestNeighbors<N>,
+    index_node_lookup: HashMap<String, NodeIndex>,
+    parents: Vec<Option<NodeIndex>>,
+    costs: Vec<f64>,
+    termination_criteria: Option<Box<dyn TerminationCriteria>>,
+    termination_reason: Option<TerminationReason>,
+    pub boundaries: Boundaries<N>,
+    pub optimizer: Box<dyn Optimizer<N>>,
+    pub collision_checker: Box<dyn CollisionChecker<N>>,
+    pub sampler: Box<dyn StateSampler<N>>,
+    pub state_space: Box<dyn StateSpace<N>>,
+    pub config: Config,
+}
+
+impl<const N: usize> Planner<N> for RRTstar<N> {
+    /// Sets the start point for the RRT* planner.
+    fn set_start(&mut self, start: Point<N>) {
+        self.start = start;
+    }
+
+    /// Sets the goal point for the RRT* planner.
+    fn set_goal(&mut self, goal: Point<N>) {
+        self.goal = goal;
+    }
+
+    /// Sets the boundaries for the RRT* planner.
+    fn set_boundaries(&mut self, boundaries: Boundaries<N>) {
+        self.boundaries = boundaries;
+    }
+
+    /// Sets the collision checker for the RRT* planner.
+    fn set_collision_checker(&mut self, cc: Box<dyn CollisionChecker<N>>) {
+        self.collision_checker = cc;
+    }
+
+    fn get_collision_checker(&self) -> &dyn CollisionChecker<N> {
+        self.collision_checker.as_ref()
+    }
+
+    fn set_sampler(&mut self, sampler: Box<dyn StateSampler<N>>) {
+        self.sampler = sampler;
+    }
+
+    fn set_termination_criteria(&mut self, criteria: Box<dyn TerminationCriteria>) {
+        self.termination_criteria = Some(criteria);
+    }
+
+    /// Initializes the RRT* planner by adding the start node as root of the tree.
+    fn init(&mut self) {
+        self.start = self.state_space.enforce_bounds(self.start);
+        self.goal = self.state_space.enforce_bounds(self.goal);
+        self.add_node(self.start, None, 0f64);
+    }
+
+    /// Grows and rewires the tree until the termination criteria is met.
+    /// Gives up, if no node could be added for `config.max_sampling_attempts` consecutive iterations.
+    fn solve(&mut self) -> Result<(), PlanningError<N>> {
+        let start_time: Instant = Instant::now();
+        let mut iterations: usize = 0;
+        let mut failed_attempts: usize = 0;
+        self.termination_reason = None;
+        if let Some(criteria) = &mut self.termination_criteria {
+            criteria.reset();
+        }
+        loop {
+            iterations += 1;
+            let new_node: Option<NodeIndex> = self
+                .sampler
+                .sample(&mut self.boundaries, self.collision_checker.as_ref())
+                .and_then(|random_node| self.extend(self.state_space.enforce_bounds(random_node)));
+            match new_node {
+                Some(new_index) => {
+                    failed_attempts = 0;
+                    self.try_connect_goal(new_index);
+                    self.check_solution();
+                }
+                None => {
+                    failed_attempts += 1;
+                    if failed_attempts >= self.config.max_sampling_attempts {
+                        return Err(PlanningError::NoValidSamples {
+                            attempts: failed_attempts,
+                        });
+                    }
+                }
+            }
+
+            if self.is_termination_criteria_met(iterations, start_time) {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Returns the solution cost.
+    /// - f64::MAX: No solution was found
+    /// - cost: f64he cost of the solution. Implies that a solution was found.
+    fn get_solution_cost(&self) -> f64 {
+        match &self.solution {
+            None => f64::MAX,
+            Some((cost, _)) => *cost,
+        }
+    }
+
+    /// Returns the points of the solution path from start to goal.
+    /// - empty: No solution was found
+    fn get_solution_path(&self) -> Vec<Point<N>> {
+        match &self.solution {
+            None => Vec::new(),
+            Some((_, path)) => pg::path_to_points(&self.graph, path),
+        }
+    }
+
+    fn get_termination_reason(&self) -> Option<TerminationReason> {
+        self.termination_reason
+    }
+}
+
+impl<const N: usize> Default for RRTstar<N> {
+    fn default() -> Self {
+        RRTstar::new(
+            Boundaries::default(),
+            DefaultOptimizer::new_box(),
+            NaiveCollisionChecker::new_box(),
+        )
+    }
+}
+
+impl<const N: usize> RRTstar<N> {
+    /// Constructor
+    pub fn new(
+        boundaries: Boundaries<N>,
+        optimizer: Box<dyn Optimizer<N>>,
+        collision_checker: Box<dyn CollisionChecker<N>>,
+    ) -> Self {
+        RRTstar {
+            solution: None,
+            is_solved: false,
+            start: Point::default(),
+            goal: Point::default(),
+            graph: Graph::new_undirected(),
+            tree: NearestNeighbors::new(),
+            index_node_lookup: HashMap::new(),
+            parents: Vec::new(),
+            costs: Vec::new(),
+            termination_criteria: None,
+            termination_reason: None,
+            boundaries,
+            optimizer,
+            collision_checker,
+            sampler: UniformSampler::new_box(),
+            state_space: EuclideanStateSpace::new_box(),
+            config: Config::default(),
+        }
+    }
+
+    /// Replaces the state space, e.g. by `JointStateSpace` for revolute joints.
+    /// Has to be set before `init`, because the nodes are stored with the keys of the state space.
+    /// The edge costs still come from the optimizer. Use a `StateSpaceOptimizer` to measure them in the state space.
+    pub fn set_state_space(&mut self, state_space: Box<dyn StateSpace<N>>) {
+        self.state_space = state_space;
+    }
+
+    /// Returns the cost-to-come of a node, i.e. the cost of the tree path from the start to the node.
+    pub fn get_cost_to_come(&self, index: NodeIndex) -> f64 {
+        self.costs[index.index()]
+    }
+
+    /// Returns the parent of a node in the tree. The start node has no parent.
+    pub fn get_parent(&self, index: NodeIndex) -> Option<NodeIndex> {
+        self.parents[index.index()]
+    }
+
+    /// Smallest gamma that guarantees asymptotic optimality for the given boundaries:
+    /// gamma > 2 * (1 + 1/d)^(1/d) * (volume / unit_ball_volume)^(1/d)
+    pub fn optimal_gamma(boundaries: &Boundaries<N>) -> f64 {
+        let dimension: f64 = N as f64;
+        let volume: f64 = boundaries
+            .get_lower()
+            .iter()
+            .zip(boundaries.get_upper().iter())
+            .map(|(lower, upper)| upper - lower)
+            .product();
+        2f64 * (1f64 + 1f64 / dimension).powf(1f64 / dimension)
+            * (volume / pg::unit_ball_volume(N)).powf(1f64 / dimension)
+    }
+
+    /// Connection radius gamma * (log(n) / n)^(1/d), capped by the step size.
+    fn connection_radius(&self) -> f64 {
+        let n: f64 = self.graph.node_count().max(1) as f64;
+        let gamma: f64 = self
+            .config
+            .gamma
+            .unwrap_or_else(|| RRTstar::optimal_gamma(&self.boundaries));
+        let radius: f64 = gamma * (n.ln() / n).powf(1f64 / N as f64);
+        radius.min(self.config.step_size)
+    }
+
+    /// Adds a node to the graph, lookup for nodeindex to point.wkt, and the rtree.
+    /// The node is connected to its parent, if it has one.
+    fn add_node(&mut self, node: Point<N>, parent: Option<NodeIndex>, cost: f64) -> NodeIndex {
+        let index = self.graph.add_node(node);
+        self.index_node_lookup
+            .insert(node.to_wkt().to_string(), index);
+        self.tree.insert(self.state_space.as_ref(), node);
+        self.parents.push(parent);
+        self.costs.push(cost);
+
+        if let Some(parent) = parent {
+            let weight: f64 = self.get_edge_weight(self.graph[parent], node);
+            self.graph.add_edge(parent, index, weight);
+        }
+        index
+    }
+
+    /// Returns the edge cost from the optimizer.
+    fn get_edge_weight(&self, begin: Point<N>, end: Point<N>) -> f64 {
+        self.optimizer.get_edge_weight(begin, end).2
+    }
+
+    /// Returns the index of the node in the graph.
+    fn get_node_index(&self, node: &Point<N>) -> Option<NodeIndex> {
+        self.index_node_lookup
+            .get(&node.to_wkt().to_string())
+            .copied()
+    }
+
+    /// Moves from `from` towards `to`, but at most `config.step_size` far.
+    fn steer(&self, from: Point<N>, to: Point<N>) -> Point<N> {
+        let distance: f64 = self.state_space.distance(&from, &to);
+        if distance <= self.config.step_size {
+            to
+        } else {
+            self.state_space
+                .interpolate(&from, &to, self.config.step_size / distance)
+        }
+    }
+
+    /// Returns the indices of all nodes within the radius around the point.
+    /// - incoming: Measures the motions from the nodes to the point. Otherwise from the point to the nodes.
+    fn get_near_nodes(&self, node: Point<N>, radius: f64, incoming: bool) -> Vec<NodeIndex> {
+        let state_space: &dyn StateSpace<N> = self.state_space.as_ref();
+        self.tree
+            .within_distance(state_space, &node, radius, |neighbor| {
+                if incoming {
+                    state_space.distance(neighbor, &node)
+                } else {
+                    state_space.distance(&node, neighbor)
+                }
+            })
+            .iter()
+            .filter_map(|(neighbor, _)| self.get_node_index(neighbor))
+            .collect()
+    }
+
+    /// Steers towards the random node, chooses the best parent for the new node and rewires its neighbors.
+    ///
+    /// # Returns
+    /// - `Some(index)`: The index of the node that was added to the tree.
+    /// - `None`: No node was added.
+    fn extend(&mut self, random_node: Point<N>) -> Option<NodeIndex> {
+        let state_space: &dyn StateSpace<N> = self.state_space.as_ref();
+        let (nearest, _) = self.tree.nearest(state_space, &random_node, |neighbor| {
+            state_space.distance(neighbor, &random_node)
+        })?;
+        let new_node: Point<N> = self.steer(nearest, random_node);
+
+        if self.get_node_index(&new_node).is_some()
+            || self.collision_checker.is_node_colliding(&new_node)
+        {
+            return None;
+        }
+
+        let radius: f64 = self.connection_radius();
+        let mut parents: Vec<NodeIndex> = self.get_near_nodes(new_node, radius, true);
+        let nearest_index: NodeIndex = self.get_node_index(&nearest)?;
+        if !parents.contains(&nearest_index) {
+            parents.push(nearest_index);
+        }
+        let children: Vec<NodeIndex> = if self.state_space.is_symmetric() {
+            parents.clone()
+        } else {
+            self.get_near_nodes(new_node, radius, false)
+        };
+
+        let (parent, cost) = self.choose_parent(new_node, &parents)?;
+        let new_index: NodeIndex = self.add_node(new_node, Some(parent), cost);
+        self.rewire(new_index, &children);
+        Some(new_index)
+    }
+
+    /// Returns the collision-free neighbor that gives the lowest cost-to-come for the new node, and that cost.
+    fn choose_parent(
+        &self,
+        new_node: Point<N>,
+        near_nodes: &[NodeIndex],
+    ) -> Option<(NodeIndex, f64)> {
+        let mut best: Option<(NodeIndex, f64)> = None;
+        for near in near_nodes {
+            let near_point: Point<N> = self.graph[*near];
+            let cost: f64 =
+                self.get_cost_to_come(*near) + self.get_edge_weight(near_point, new_node);
+            if best.is_some_and(|(_, best_cost)| cost >= best_cost) {
+                continue;
+            }
+            if self.state_space.is_motion_colliding(
+                self.collision_checker.as_ref(),
+                &near_point,
+                &new_node,
+            ) {
+                continue;
+            }
+            best = Some((*near, cost));
+        }
+        best
+    }
+
+    /// Reconnects neighbors through the new node, if that lowers their cost-to-come.
+    fn rewire(&mut self, new_index: NodeIndex, near_nodes: &[NodeIndex]) {
+        let new_node: Point<N> = self.graph[new_index];
+        for near in near_nodes {
+            if Some(*near) == self.get_parent(new_index) {
+                continue;
+            }
+            let near_point: Point<N> = self.graph[*near];
+            let weight: f64 = self.get_edge_weight(new_node, near_point);
+            let cost: f64 = self.get_cost_to_come(new_index) + weight;
+            if cost >= self.get_cost_to_come(*near) {
+                continue;
+            }
+            if self.state_space.is_motion_colliding(
+                self.collision_checker.as_ref(),
+                &new_node,
+                &near_point,
+            ) {
+                continue;
+            }
+            self.set_parent(*near, new_index, weight);
+        }
+    }
+
+    /// Replaces the parent of a node and updates the cost-to-come of the node and all of its descendants.
+    fn set_parent(&mut self, node: NodeIndex, parent: NodeIndex, weight: f64) {
+        if let Some(old_parent) = self.get_parent(node) {
+            if let Some(edge) = self.graph.find_edge(old_parent, node) {
+                self.graph.remove_edge(edge);
+            }
+        }
+        self.graph.add_edge(parent, node, weight);
+        self.parents[node.index()] = Some(parent);
+
+        let delta: f64 = self.get_cost_to_come(parent) + weight - self.get_cost_to_come(node);
+        let mut stack: Vec<NodeIndex> = vec![node];
+        while let Some(current) = stack.pop() {
+            self.costs[current.index()] += delta;
+            for child in self.graph.neighbors(current) {
+                if self.get_parent(child) == Some(current) {
+                    stack.push(child);
+                }
+            }
+        }
+    }
+
+    /// Connects the goal to the tree, if the new node is close enough and the goal is not yet part of the tree.
+    fn try_connect_goal(&mut self, new_index: NodeIndex) {
+        if self.get_node_index(&self.goal).is_some() {
+            return;
+        }
+        let new_node: Point<N> = self.graph[new_index];
+        if self.state_space.distance(&new_node, &self.goal) > self.config.step_size
+            || self.collision_checker.is_node_colliding(&self.goal)
+            || self.state_space.is_motion_colliding(
+                self.collision_checker.as_ref(),
+                &new_node,
+                &self.goal,
+            )
+        {
+            return;
+        }
+        let cost: f64 =
+            self.get_cost_to_come(new_index) + self.get_edge_weight(new_node, self.goal);
+        self.add_node(self.goal, Some(new_index), cost);
+    }
+
+    /// Follows the parents from the goal back to the start and stores the path and its cost.
+    fn check_solution(&mut self) {
+        let goal: NodeIndex = match self.get_node_index(&self.goal) {
+            Some(index) => index,
+            None => return,
+        };
+
+        let mut path: Vec<NodeIndex> = vec![goal];
+        let mut current: NodeIndex = goal;
+        while let Some(parent) = self.get_parent(current) {
+            path.push(parent);
+            current = parent;
+        }
+        path.reverse();
+
+        self.solution = Some((self.get_cost_to_come(goal), path));
+        self.is_solved = true;
+    }
+
+    /// Determines whether the algorithm stops. Uses the termination criteria, if one was set.
+    /// Otherwise the max_size parameter is compared to the number of nodes in the graph.
+    fn is_termination_criteria_met(&mut self, iterations: usize, start_time: Instant) -> bool {
+        let progress: PlannerProgress = PlannerProgress {
+            iterations,
+            node_count: self.graph.node_count(),
+            solution_cost: self.solution.as_ref().map(|(cost, _)| *cost),
+            elapsed: start_time.elapsed(),
+        };
+        self.termination_reason = match &mut self.termination_criteria {
+            Some(criteria) => criteria.check(&progress),
+            None => NodeLimit {
+                max_nodes: self.config.max_size,
+            }
+            .check(&progress),
+        };
+        self.termination_reason.is_some()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RRTstar;
+    use crate::boundaries::Boundaries;
+    use crate::collision_checker::{CollisionChecker, NaiveCollisionChecker};
+    use crate::optimizer::DefaultOptimizer;
+    use crate::planner::base_planner::Planner;
+    use crate::space::Point;
+    use crate::test_support::{BoxCollisionChecker, SeamCollisionChecker};
+    use petgraph::graph::NodeIndex;
+
+    fn create_planner(cc: Box<dyn CollisionChecker>) -> RRTstar {
+        let mut planner: RRTstar = RRTstar::new(
+            Boundaries::new(0f64, 3f64, 0f64, 3f64),
+            DefaultOptimizer::new_box(),
+            cc,
+        );
+        planner.set_start(Point::new(0f64, 0f64));
+        planner.set_goal(Point::new(3f64, 3f64));
+        planner.init();
+        planner
+    }
+
+    #[test]
+    fn test_new() {
+        let planner: RRTstar = RRTstar::default();
+        assert!(!planner.is_solved);
+        assert_eq!(planner.graph.node_count(), 0);
+    }
+
+    #[test]
+    fn test_init_adds_start_as_root() {
+        let planner: RRTstar = create_planner(NaiveCollisionChecker::new_box());
+        assert_eq!(planner.graph.node_count(), 1);
+        assert_eq!(planner.get_parent(NodeIndex::new(0)), None);
+        assert_eq!(planner.get_cost_to_come(NodeIndex::new(0)), 0f64);
+    }
+
+    // Test that the connection radius shrinks as the tree grows.
+    #[test]
+    fn test_connection_radius_shrinks() {
+        let mut planner: RRTstar = create_planner(NaiveCollisionChecker::new_box());
+        planner.config.step_size = f64::MAX;
+        planner.config.max_size = 10;
+        planner.solve().unwrap();
+        let radius_small_tree: f64 = planner.connection_radius();
+        planner.config.max_size = 100;
+        planner.solve().unwrap();
+        let radius_large_tree: f64 = planner.connection_radius();
+        assert!(radius_large_tree < radius_small_tree);
+    }
+
+    // Test that the cost-to-come of every node matches the sum of edge weights along its tree path.
+    #[test]
+    fn test_cost_to_come_is_consistent() {
+        let mut planner: RRTstar = create_planner(Box::new(BoxCollisionChecker {}));
+        planner.config.max_size = 300;
+        planner.solve().unwrap();
+
+        for index in planner.graph.node_indices() {
+            let mut cost: f64 = 0f64;
+            let mut current: NodeIndex = index;
+            while let Some(parent) = planner.get_parent(current) {
+                let edge = planner.graph.find_edge(parent, current).unwrap();
+                cost += planner.graph[edge];
+                current = parent;
+            }
+            assert!((cost - planner.get_cost_to_come(index)).abs() < 1e-9);
+        }
+        assert_eq!(planner.graph.edge_count(), planner.graph.node_count() - 1);
+    }
+
+    // Test that the solution cost never increases and approaches the optimum as the tree grows.
+    #[test]
+    fn test_solution_cost_converges() {
+        let mut planner: RRTstar = create_planner(NaiveCollisionChecker::new_box());
+        planner.config.max_size = 300;
+        planner.solve().unwrap();
+        assert!(planner.is_solved);
+        let cost_small_tree: f64 = planner.get_solution_cost();
+
+        planner.config.max_size = 3000;
+        planner.solve().unwrap();
+        let cost_large_tree: f64 = planner.get_solution_cost();
+
+        let optimum: f64 = Point::new(0f64, 0f64).euclidean_distance(&Point::new(3f64, 3f64));
+        assert!(cost_large_tree <= cost_small_tree);
+        assert!(cost_large_tree < 1.05 * optimum);
+    }
+
+    #[test]
+    fn test_solution_path_avoids_obstacle() {
+        let mut planner: RRTstar = create_planner(Box::new(BoxCollisionChecker {}));
+        planner.config.max_size = 1000;
+        planner.solve().unwrap();
+        assert!(planner.is_solved);
+
+        let path: Vec<Point> = planner.get_solution_path();
+        assert_eq!(path.first(), Some(&Point::new(0f64, 0f64)));
+        assert_eq!(path.last(), Some(&Point::new(3f64, 3f64)));
+        for pair in path.windows(2) {
+            assert!(!planner
+                .collision_checker
+                .is_edge_colliding(&pair[0], &pair[1]));
+        }
+    }
+
+    // Test that a custom termination criteria replaces the default node limit.
+    #[test]
+    fn test_termination_criteria() {
+        use crate::planner::termination::{IterationLimit, Or, TerminationReason, Timeout};
+        use std::time::Duration;
+
+        let mut planner: RRTstar = create_planner(NaiveCollisionChecker::new_box());
+        assert_eq!(planner.get_termination_reason(), None);
+        planner.set_termination_criteria(Or::new_box(
+            Timeout::new_box(Duration::from_secs(60)),
+            IterationLimit::new_box(20),
+        ));
+        planner.solve().unwrap();
+        assert_eq!(
+            planner.get_termination_reason(),
+            Some(TerminationReason::IterationLimit)
+        );
+        assert!(planner.graph.node_count() <= 21);
+
+        // The iterations are counted per call of solve.
+        planner.solve().unwrap();
+        assert!(planner.graph.node_count() > 21);
+    }
+
+    // Test that a tree that can never grow makes the planner give up instead of looping forever.
+    #[test]
+    fn test_zero_area_no_valid_samples() {
+        use crate::error::PlanningError;
+
+        let mut planner: RRTstar = RRTstar::new(
+            Boundaries::new(0f64, 0f64, 0f64, 0f64),
+            DefaultOptimizer::new_box(),
+            NaiveCollisionChecker::new_box(),
+        );
+        planner.init();
+        assert!(matches!(
+            planner.solve(),
+            Err(PlanningError::NoValidSamples { attempts: 1000 })
+        ));
+    }
+
+    // Test that a goal-biased sampler finds a solution around the obstacle.
+    #[test]
+    fn test_rrt_star_goal_biased_sampler() {
+        use crate::sampler::{GoalBiasedSampler, UniformSampler};
+
+        let mut planner: RRTstar = create_planner(Box::new(BoxCollisionChecker {}));
+        planner.set_sampler(GoalBiasedSampler::new_box(
+            Point::new(3f64, 3f64),
+            0.1,
+            UniformSampler::new_box(),
+        ));
+        planner.solve().unwrap();
+        assert!(planner.get_solution_cost() < f64::MAX);
+        assert_eq!(
+            planner.get_solution_path().last(),
+            Some(&Point::new(3f64, 3f64))
+        );
+    }
+
+    // Test that the tree grows across the seam of a circular axis, because the long way is blocked.
+    #[test]
+    fn test_circular_axis() {
+        use crate::boundaries::Topology;
+        use crate::optimizer::StateSpaceOptimizer;
+        use crate::state_space::{JointStateSpace, StateSpace};
+        use std::f64::consts::PI;
+
+        let mut boundaries: Boundaries = Boundaries::new(-PI, PI, 0f64, 1f64);
+        boundaries.set_topology([Topology::Circular, Topology::Bounded]);
+        boundaries.set_seed(2);
+        let mut planner: RRTstar = RRTstar::new(
+            boundaries.clone(),
+            StateSpaceOptimizer::new_box(JointStateSpace::new_box(&boundaries)),
+            Box::new(SeamCollisionChecker {}),
+        );
+        planner.set_state_space(JointStateSpace::new_box(&boundaries));
+        planner.config.max_size = 300;
+        planner.set_start(Point::new(2.8, 0.5));
+        planner.set_goal(Point::new(-2.8, 0.5));
+        planner.init();
+        planner.solve().unwrap();
+        assert!(planner.is_solved);
+
+        let space: JointStateSpace = JointStateSpace::new(&boundaries);
+        let path: Vec<Point> = planner.get_solution_path();
+        let length: f64 = path
+            .windows(2)
+            .map(|pair| space.distance(&pair[0], &pair[1]))
+            .sum();
+        assert!((planner.get_solution_cost() - length).abs() < 1e-9);
+        assert!(length < 2f64);
+        for pair in path.windows(2) {
+            assert!(!space.is_motion_colliding(&SeamCollisionChecker {}, &pair[0], &pair[1]));
+        }
+    }
+}
@@ -0,0 +1,456 @@
+use std::time::Instant;
+
+use petgraph::graph::{Graph, NodeIndex};
+use rand::Rng;
+use rstar::primitives::GeomWithData;
+use rstar::RTree;
+
+use crate::boundaries::Boundaries;
+use crate::collision_checker::{CollisionChecker, DynamicCollisionChecker, DynamicObstacle};
+use crate::error::PlanningError;
+use crate::planner::base_planner::Planner;
+use crate::planner::termination::{
+    NodeLimit, PlannerProgress, TerminationCriteria, TerminationReason,
+};
+use crate::sampler::{StateSampler, UniformSampler};
+use crate::space::{Point, TimedPoint};
+
+/// Node of the tree in space-time. Time is scaled by the maximum speed, so all coordinates have the same unit.
+type IndexedNode = GeomWithData<[f64; 3], NodeIndex>;
+
+/// # Holds configuration parameters for the space-time RRT
+/// It does configure:
+/// - max_size: Limits the number of Nodes in the tree before termination of the algorithm
+/// - max_speed: Maximum speed of the robot. No edge of the tree is faster.
+/// - start_time: Time at which the robot is at the start
+/// - time_horizon: Latest time at which the robot may arrive at the goal
+/// - nearest_neighbors: Number of closest nodes in space-time that are tried as parent of a new node
+/// - max_sampling_attempts: Limits the number of consecutive failed attempts to add a node before the planner gives up
+pub struct Config {
+    pub max_size: usize,
+    pub max_speed: f64,
+    pub start_time: f64,
+    pub time_horizon: f64,
+    pub nearest_neighbors: usize,
+    pub max_sampling_attempts: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            max_size: 1000usize,
+            max_speed: 1f64,
+            start_time: 0f64,
+            time_horizon: 100f64,
+            nearest_neighbors: 10usize,
+            max_sampling_attempts: 1000usize,
+        }
+    }
+}
+
+/// # Space-Time Rapidly-Exploring Random Trees
+/// RRT in (x, y, t) for environments with moving obstacles, whose trajectories are known.
+/// It is an algorithm which is:
+/// - probabilistically complete
+/// - Single query
+///
+/// Every node carries a time. Edges only lead forward in time and never exceed the maximum speed, so waiting is slow motion.
+/// They are checked against the swept motion of the dynamic obstacles with the `DynamicCollisionChecker`.
+/// The goal is a position. It is reached at the earliest possible time from every new node, and the solution with the earliest arrival is kept.
+/// The solution cost is the travel time.
+///
+/// # Source / Credits
+/// Fraichard, T. (1999), "Trajectory planning in a dynamic workspace: a 'state-time space' approach", Advanced Robotics, 13 (1): 75–94
+pub struct SpaceTimeRRT {
+    pub solution: Option<(f64, Vec<NodeIndex>)>,
+    pub is_solved: bool,
+    pub start: Point,
+    pub goal: Point,
+    pub graph: Graph<TimedPoint, f64>,
+    tree: RTree<IndexedNode>,
+    parents: Vec<Option<NodeIndex>>,
+    termination_criteria: Option<Box<dyn TerminationCriteria>>,
+    termination_reason: Option<TerminationReason>,
+    pub boundaries: Boundaries,
+    pub collision_checker: DynamicCollisionChecker,
+    pub sampler: Box<dyn StateSampler>,
+    pub config: Config,
+}
+
+impl Planner for SpaceTimeRRT {
+    fn set_start(&mut self, start: Point) {
+        self.start = start;
+    }
+
+    fn set_goal(&mut self, goal: Point) {
+        self.goal = goal;
+    }
+
+    fn set_boundaries(&mut self, boundaries: Boundaries) {
+        self.boundaries = boundaries;
+    }
+
+    /// Replaces the collision checker of the static obstacles. The dynamic obstacles are kept.
+    fn set_collision_checker(&mut self, cc: Box<dyn CollisionChecker>) {
+        self.collision_checker.set_static_collision_checker(cc);
+    }
+
+    fn get_collision_checker(&self) -> &dyn CollisionChecker {
+        &self.collision_checker
+    }
+
+    fn set_sampler(&mut self, sampler: Box<dyn StateSampler>) {
+        self.sampler = sampler;
+    }
+
+    fn set_termination_criteria(&mut self, criteria: Box<dyn TerminationCriteria>) {
+        self.termination_criteria = Some(criteria);
+    }
+
+    /// Initializes the planner with the start at the start time as root of the tree.
+    fn init(&mut self) {
+        self.graph.clear();
+        self.tree = RTree::new();
+        self.parents.clear();
+        self.solution = None;
+        self.is_solved = false;
+        self.add_tree_node(TimedPoint::new(self.start, self.config.start_time), None);
+    }
+
+    /// Grows the tree until the termination criteria is met.
+    ///
+    /// # Errors
+    /// - `PlanningError::StartInCollision`: A dynamic obstacle is at the start at the start time
+    /// - `PlanningError::NoValidSamples`: No node could be added for `config.max_sampling_attempts` consecutive iterations
+    fn solve(&mut self) -> Result<(), PlanningError> {
+        let start: TimedPoint = TimedPoint::new(self.start, self.config.start_time);
+        if self.collision_checker.is_timed_node_colliding(&start) {
+            return Err(PlanningError::StartInCollision { start: self.start });
+        }
+        let start_time: Instant = Instant::now();
+        let mut iterations: usize = 0;
+        let mut failed_attempts: usize = 0;
+        self.termination_reason = None;
+        if let Some(criteria) = &mut self.termination_criteria {
+            criteria.reset();
+        }
+        loop {
+            iterations += 1;
+            match self.add_random_node() {
+                Some(new_index) => {
+                    failed_attempts = 0;
+                    self.try_connect_goal(new_index);
+                }
+                None => {
+                    failed_attempts += 1;
+                    if failed_attempts >= self.config.max_sampling_attempts {
+                        return Err(PlanningError::NoValidSamples {
+                            attempts: failed_attempts,
+                        });
+                    }
+                }
+            }
+
+            if self.is_termination_criteria_met(iterations, start_time) {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Returns the travel time of the solution.
+    /// - f64::MAX: No solution was found
+    fn get_solution_cost(&self) -> f64 {
+        match &self.solution {
+            None => f64::MAX,
+            Some((cost, _)) => *cost,
+        }
+    }
+
+    /// Returns the positions of the solution path from start to goal, without their times.
+    /// - empty: No solution was found
+    fn get_solution_path(&self) -> Vec<Point> {
+        self.get_solution_trajectory()
+            .iter()
+            .map(TimedPoint::get_point)
+            .collect()
+    }
+
+    fn get_termination_reason(&self) -> Option<TerminationReason> {
+        self.termination_reason
+    }
+}
+
+impl Default for SpaceTimeRRT {
+    fn default() -> Self {
+        SpaceTimeRRT::new(Boundaries::default(), DynamicCollisionChecker::default())
+    }
+}
+
+impl SpaceTimeRRT {
+    /// Constructor
+    pub fn new(boundaries: Boundaries, collision_checker: DynamicCollisionChecker) -> Self {
+        SpaceTimeRRT {
+            solution: None,
+            is_solved: false,
+            start: Point::default(),
+            goal: Point::default(),
+            graph: Graph::new(),
+            tree: RTree::new(),
+            parents: Vec::new(),
+            termination_criteria: None,
+            termination_reason: None,
+            boundaries,
+            collision_checker,
+            sampler: UniformSampler::new_box(),
+            config: Config::default(),
+        }
+    }
+
+    /// Adds a moving obstacle.
+    pub fn add_dynamic_obstacle(&mut self, obstacle: DynamicObstacle) {
+        self.collision_checker.add_obstacle(obstacle);
+    }
+
+    /// Returns the solution with the time at which every point is reached.
+    /// - empty: No solution was found
+    pub fn get_solution_trajectory(&self) -> Vec<TimedPoint> {
+        match &self.solution {
+            None => Vec::new(),
+            Some((_, path)) => path.iter().map(|index| self.graph[*index]).collect(),
+        }
+    }
+
+    /// Returns the parent of a node in the tree. The start node has no parent.
+    pub fn get_parent(&self, index: NodeIndex) -> Option<NodeIndex> {
+        self.parents[index.index()]
+    }
+
+    /// Adds a node to the graph and connects it to its parent, if it has one.
+    /// Only nodes in the tree are used as parents later on. Goal nodes are not added to the tree.
+    fn add_node(&mut self, node: TimedPoint, parent: Option<NodeIndex>) -> NodeIndex {
+        let index: NodeIndex = self.graph.add_node(node);
+        self.parents.push(parent);
+        if let Some(parent) = parent {
+            let duration: f64 = node.get_time() - self.graph[parent].get_time();
+            self.graph.add_edge(parent, index, duration);
+        }
+        index
+    }
+
+    /// Adds a node to the graph and to the tree of possible parents.
+    fn add_tree_node(&mut self, node: TimedPoint, parent: Option<NodeIndex>) -> NodeIndex {
+        let index: NodeIndex = self.add_node(node, parent);
+        self.tree
+            .insert(GeomWithData::new(self.to_coordinates(&node), index));
+        index
+    }
+
+    /// Coordinates of a node in the tree. The time is scaled by the maximum speed.
+    fn to_coordinates(&self, node: &TimedPoint) -> [f64; 3] {
+        [
+            node.get_point().get_x(),
+            node.get_point().get_y(),
+            node.get_time() * self.config.max_speed,
+        ]
+    }
+
+    /// Returns true, if the robot can move from begin to end without exceeding the maximum speed.
+    fn is_reachable(&self, begin: &TimedPoint, end: &TimedPoint) -> bool {
+        end.get_time() > begin.get_time()
+            && begin.speed_to(end) <= self.config.max_speed * (1f64 + 1e-9)
+    }
+
+    /// Samples a position and a time, and connects it to the closest node in space-time that can reach it.
+    ///
+    /// # Returns
+    /// - `Some(index)`: The index of the node that was added to the tree.
+    /// - `None`: No node was added.
+    fn add_random_node(&mut self) -> Option<NodeIndex> {
+        let point: Point = self
+            .sampler
+            .sample(&mut self.boundaries, &self.collision_checker)?;
+        let time: f64 = self
+            .boundaries
+            .get_rng()
+            .random_range(self.config.start_time..=self.config.time_horizon);
+        let random_node: TimedPoint = TimedPoint::new(point, time);
+        if self.collision_checker.is_timed_node_colliding(&random_node) {
+            return None;
+        }
+
+        let parent: NodeIndex = self
+            .tree
+            .nearest_neighbor_iter(&self.to_coordinates(&random_node))
+            .take(self.config.nearest_neighbors)
+            .map(|neighbor| neighbor.data)
+            .find(|parent| {
+                let parent_node: TimedPoint = self.graph[*parent];
+                self.is_reachable(&parent_node, &random_node)
+                    && !self
+                        .collision_checker
+                        .is_timed_edge_colliding(&parent_node, &random_node)
+            })?;
+        Some(self.add_tree_node(random_node, Some(parent)))
+    }
+
+    /// Drives from the new node to the goal as fast as possible.
+    /// Keeps the arrival as new solution, if it is earlier than the current one and the motion is collision free.
+    fn try_connect_goal(&mut self, new_index: NodeIndex) {
+        let new_node: TimedPoint = self.graph[new_index];
+        let arrival: f64 = new_node.get_time()
+            + new_node.get_point().euclidean_distance(&self.goal) / self.config.max_speed;
+        let cost: f64 = arrival - self.config.start_time;
+        if arrival > self.config.time_horizon || cost >= self.get_solution_cost() {
+            return;
+        }
+        let goal: TimedPoint = TimedPoint::new(self.goal, arrival);
+        if self.collision_checker.is_timed_node_colliding(&goal)
+            || self
+                .collision_checker
+                .is_timed_edge_colliding(&new_node, &goal)
+        {
+            return;
+        }
+        let goal_index: NodeIndex = self.add_node(goal, Some(new_index));
+
+        let mut path: Vec<NodeIndex> = vec![goal_index];
+        let mut current: NodeIndex = goal_index;
+        while let Some(parent) = self.get_parent(current) {
+            path.push(parent);
+            current = parent;
+        }
+        path.reverse();
+        self.solution = Some((cost, path));
+        self.is_solved = true;
+    }
+
+    /// Determines whether the algorithm stops. Uses the termination criteria, if one was set.
+    /// Otherwise the max_size parameter is compared to the number of nodes in the graph.
+    fn is_termination_criteria_met(&mut self, iterations: usize, start_time: Instant) -> bool {
+        let progress: PlannerProgress = PlannerProgress {
+            iterations,
+            node_count: self.graph.node_count(),
+            solution_cost: self.solution.as_ref().map(|(cost, _)| *cost),
+            elapsed: start_time.elapsed(),
+        };
+        self.termination_reason = match &mut self.termination_criteria {
+            Some(criteria) => criteria.check(&progress),
+            None => NodeLimit {
+                max_nodes: self.config.max_size,
+            }
+            .check(&progress),
+        };
+        self.termination_reason.is_some()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SpaceTimeRRT;
+    use crate::boundaries::Boundaries;
+    use crate::collision_checker::{
+        DynamicCollisionChecker, DynamicObstacle, Keyframe, NaiveCollisionChecker,
+    };
+    use crate::error::PlanningError;
+    use crate::planner::base_planner::Planner;
+    use crate::space::{Point, TimedPoint};
+    use geo::polygon;
+
+    /// Wall across the corridor y in [0, 4] at x = 2, that moves up and down once per 8 seconds.
+    /// It blocks the whole corridor, except for y in [3, 4] at t = 0 and y in [0, 1] at t = 4.
+    fn moving_wall() -> DynamicObstacle {
+        DynamicObstacle::new(
+            polygon![(x: -0.2, y: -1.5), (x: 0.2, y: -1.5), (x: 0.2, y: 1.5), (x: -0.2, y: 1.5)],
+            vec![
+                Keyframe::new(0f64, Point::new(2f64, 1.5), 0f64),
+                Keyframe::new(4f64, Point::new(2f64, 2.5), 0f64),
+                Keyframe::new(8f64, Point::new(2f64, 1.5), 0f64),
+                Keyframe::new(12f64, Point::new(2f64, 2.5), 0f64),
+                Keyframe::new(16f64, Point::new(2f64, 1.5), 0f64),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn create_planner(seed: u64) -> SpaceTimeRRT {
+        let cc: DynamicCollisionChecker =
+            DynamicCollisionChecker::new(NaiveCollisionChecker::new_box(), vec![moving_wall()]);
+        let mut planner: SpaceTimeRRT =
+            SpaceTimeRRT::new(Boundaries::new_seeded(0f64, 4f64, 0f64, 4f64, seed), cc);
+        planner.config.time_horizon = 16f64;
+        planner.config.max_size = 1500;
+        planner.set_start(Point::new(0.5, 2f64));
+        planner.set_goal(Point::new(3.5, 2f64));
+        planner
+    }
+
+    #[test]
+    fn test_new() {
+        let planner: SpaceTimeRRT = SpaceTimeRRT::default();
+        assert!(!planner.is_solved);
+        assert_eq!(planner.get_solution_cost(), f64::MAX);
+        assert!(planner.get_solution_trajectory().is_empty());
+    }
+
+    #[test]
+    fn test_init_adds_start() {
+        let mut planner: SpaceTimeRRT = create_planner(1);
+        planner.config.start_time = 2f64;
+        planner.init();
+        assert_eq!(planner.graph.node_count(), 1);
+        assert_eq!(
+            planner.graph[petgraph::graph::NodeIndex::new(0)],
+            TimedPoint::new(Point::new(0.5, 2f64), 2f64)
+        );
+    }
+
+    // Test that the trajectory respects the maximum speed, moves forward in time and avoids the moving wall.
+    #[test]
+    fn test_solve_around_moving_wall() {
+        let mut planner: SpaceTimeRRT = create_planner(7);
+        planner.init();
+        planner.solve().unwrap();
+        assert!(planner.is_solved);
+
+        let trajectory: Vec<TimedPoint> = planner.get_solution_trajectory();
+        assert_eq!(trajectory[0], TimedPoint::new(Point::new(0.5, 2f64), 0f64));
+        assert_eq!(
+            trajectory.last().unwrap().get_point(),
+            Point::new(3.5, 2f64)
+        );
+        for pair in trajectory.windows(2) {
+            assert!(pair[0].get_time() < pair[1].get_time());
+            assert!(pair[0].speed_to(&pair[1]) <= planner.config.max_speed + 1e-9);
+            assert!(!planner
+                .collision_checker
+                .is_timed_edge_colliding(&pair[0], &pair[1]));
+        }
+        let arrival: f64 = trajectory.last().unwrap().get_time();
+        assert!((planner.get_solution_cost() - arrival).abs() < 1e-9);
+        assert!(arrival >= 3f64);
+        assert_eq!(planner.get_solution_path().len(), trajectory.len());
+    }
+
+    // Test that the planner does not start, if a dynamic obstacle is at the start at the start time.
+    #[test]
+    fn test_start_in_collision() {
+        let mut planner: SpaceTimeRRT = create_planner(1);
+        planner.set_start(Point::new(2f64, 2f64));
+        planner.init();
+        assert!(matches!(
+            planner.solve(),
+            Err(PlanningError::StartInCollision { .. })
+        ));
+    }
+
+    // Test that a too slow robot does not find a solution within the time horizon.
+    #[test]
+    fn test_time_horizon() {
+        let mut planner: SpaceTimeRRT = create_planner(3);
+        planner.config.max_speed = 0.1;
+        planner.config.max_size = 300;
+        planner.init();
+        planner.solve().unwrap();
+        assert!(!planner.is_solved);
+    }
+}
@@ -1,19 +1,355 @@
-trait TerminationCritera {
-    fn is_met(&self) -> bool;
+use std::time::Duration;
+
+/// Snapshot of the progress of a planner. Termination criteria decide on it whether the planner stops.
+///
+/// - iterations: Number of iterations since `solve` was called
+/// - node_count: Number of nodes in the graph
+/// - solution_cost: Cost of the best solution so far. `None` if no solution was found yet.
+/// - elapsed: Wall-clock time since `solve` was called
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PlannerProgress {
+    pub iterations: usize,
+    pub node_count: usize,
+    pub solution_cost: Option<f64>,
+    pub elapsed: Duration,
+}
+
+/// Describes why a planner stopped.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TerminationReason {
+    Timeout,
+    IterationLimit,
+    NodeLimit,
+    SolutionFound,
+    CostThreshold,
+    NoImprovement,
+}
+
+/// Every termination criteria needs to be based on this trait. Criteria can be composed with `And` and `Or`.
+pub trait TerminationCriteria {
+    /// Is called once at the beginning of every `solve`. Stateful criteria reset themselves here.
+    fn reset(&mut self) {}
+
+    /// Returns:
+    /// - Some(reason): The planner should stop
+    /// - None: The planner should continue
+    fn check(&mut self, progress: &PlannerProgress) -> Option<TerminationReason>;
+
+    /// Returns:
+    /// - true: The planner should stop
+    /// - false: The planner should continue
+    fn is_met(&mut self, progress: &PlannerProgress) -> bool {
+        self.check(progress).is_some()
+    }
+}
+
+/// Stops after a fixed wall-clock duration.
+#[derive(Debug, Copy, Clone)]
+pub struct Timeout {
+    pub duration: Duration,
+}
+
+impl Timeout {
+    pub fn new_box(duration: Duration) -> Box<dyn TerminationCriteria> {
+        Box::new(Timeout { duration })
+    }
+}
+
+impl TerminationCriteria for Timeout {
+    fn check(&mut self, progress: &PlannerProgress) -> Option<TerminationReason> {
+        (progress.elapsed >= self.duration).then_some(TerminationReason::Timeout)
+    }
+}
+
+/// Stops after a number of iterations.
+#[derive(Debug, Copy, Clone)]
+pub struct IterationLimit {
+    pub max_iterations: usize,
+}
+
+impl IterationLimit {
+    pub fn new_box(max_iterations: usize) -> Box<dyn TerminationCriteria> {
+        Box::new(IterationLimit { max_iterations })
+    }
+}
+
+impl TerminationCriteria for IterationLimit {
+    fn check(&mut self, progress: &PlannerProgress) -> Option<TerminationReason> {
+        (progress.iterations >= self.max_iterations).then_some(TerminationReason::IterationLimit)
+    }
 }
 
-pub struct BaseTerminationCriteria {
-    max_size: usize,
+/// Stops as soon as the graph holds a number of nodes.
+#[derive(Debug, Copy, Clone)]
+pub struct NodeLimit {
+    pub max_nodes: usize,
 }
 
-impl BaseTerminationCriteria {
-    pub fn new(max_size: usize) -> self {
-        BaseTerminationCriteria { max_size }
+impl NodeLimit {
+    pub fn new_box(max_nodes: usize) -> Box<dyn TerminationCriteria> {
+        Box::new(NodeLimit { max_nodes })
     }
 }
 
-impl TerminationCritera for BaseTerminationCriteria {
-    fn is_met(&self, graph: &Graph<Point, f64, Undirected>) -> bool{
-        graph.node_count() >= self.max_size
+impl TerminationCriteria for NodeLimit {
+    fn check(&mut self, progress: &PlannerProgress) -> Option<TerminationReason> {
+        (progress.node_count >= self.max_nodes).then_some(TerminationReason::NodeLimit)
     }
-}
\ No newline at end of file
+}
+
+/// Stops as soon as any solution was found.
+#[derive(Debug, Copy, Clone)]
+pub struct SolutionFound {}
+
+impl SolutionFound {
+    pub fn new_box() -> Box<dyn TerminationCriteria> {
+        Box::new(SolutionFound {})
+    }
+}
+
+impl TerminationCriteria for SolutionFound {
+    fn check(&mut self, progress: &PlannerProgress) -> Option<TerminationReason> {
+        progress
+            .solution_cost
+            .map(|_| TerminationReason::SolutionFound)
+    }
+}
+
+/// Stops as soon as a solution with a cost below the threshold was found.
+#[derive(Debug, Copy, Clone)]
+pub struct CostThreshold {
+    pub threshold: f64,
+}
+
+impl CostThreshold {
+    pub fn new_box(threshold: f64) -> Box<dyn TerminationCriteria> {
+        Box::new(CostThreshold { threshold })
+    }
+}
+
+impl TerminationCriteria for CostThreshold {
+    fn check(&mut self, progress: &PlannerProgress) -> Option<TerminationReason> {
+        progress
+            .solution_cost
+            .filter(|cost| *cost < self.threshold)
+            .map(|_| TerminationReason::CostThreshold)
+    }
+}
+
+/// Stops if the solution cost did not improve for a number of iterations.
+/// Iterations before the first solution are not counted.
+#[derive(Debug, Copy, Clone)]
+pub struct NoImprovement {
+    pub max_iterations: usize,
+    best_cost: Option<f64>,
+    last_improvement: usize,
+}
+
+impl NoImprovement {
+    pub fn new(max_iterations: usize) -> Self {
+        NoImprovement {
+            max_iterations,
+            best_cost: None,
+            last_improvement: 0,
+        }
+    }
+
+    pub fn new_box(max_iterations: usize) -> Box<dyn TerminationCriteria> {
+        Box::new(NoImprovement::new(max_iterations))
+    }
+}
+
+impl TerminationCriteria for NoImprovement {
+    fn reset(&mut self) {
+        self.best_cost = None;
+        self.last_improvement = 0;
+    }
+
+    fn check(&mut self, progress: &PlannerProgress) -> Option<TerminationReason> {
+        let cost: f64 = progress.solution_cost?;
+        if self.best_cost.is_none_or(|best| cost < best) {
+            self.best_cost = Some(cost);
+            self.last_improvement = progress.iterations;
+            return None;
+        }
+        (progress.iterations - self.last_improvement >= self.max_iterations)
+            .then_some(TerminationReason::NoImprovement)
+    }
+}
+
+/// Stops if both criteria are met. Returns the reason of the second criteria.
+pub struct And {
+    pub first: Box<dyn TerminationCriteria>,
+    pub second: Box<dyn TerminationCriteria>,
+}
+
+impl And {
+    pub fn new_box(
+        first: Box<dyn TerminationCriteria>,
+        second: Box<dyn TerminationCriteria>,
+    ) -> Box<dyn TerminationCriteria> {
+        Box::new(And { first, second })
+    }
+}
+
+impl TerminationCriteria for And {
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+    }
+
+    /// Both criteria are always evaluated, so stateful criteria see every progress update.
+    fn check(&mut self, progress: &PlannerProgress) -> Option<TerminationReason> {
+        let first = self.first.check(progress);
+        let second = self.second.check(progress);
+        first.and(second)
+    }
+}
+
+/// Stops if at least one of the criteria is met. Returns the reason of the first criteria that is met.
+pub struct Or {
+    pub first: Box<dyn TerminationCriteria>,
+    pub second: Box<dyn TerminationCriteria>,
+}
+
+impl Or {
+    pub fn new_box(
+        first: Box<dyn TerminationCriteria>,
+        second: Box<dyn TerminationCriteria>,
+    ) -> Box<dyn TerminationCriteria> {
+        Box::new(Or { first, second })
+    }
+}
+
+impl TerminationCriteria for Or {
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+    }
+
+    /// Both criteria are always evaluated, so stateful criteria see every progress update.
+    fn check(&mut self, progress: &PlannerProgress) -> Option<TerminationReason> {
+        let first = self.first.check(progress);
+        let second = self.second.check(progress);
+        first.or(second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(
+        iterations: usize,
+        node_count: usize,
+        solution_cost: Option<f64>,
+    ) -> PlannerProgress {
+        PlannerProgress {
+            iterations,
+            node_count,
+            solution_cost,
+            elapsed: Duration::from_millis(iterations as u64),
+        }
+    }
+
+    #[test]
+    fn test_timeout() {
+        let mut criteria = Timeout::new_box(Duration::from_millis(10));
+        assert_eq!(criteria.check(&progress(9, 0, None)), None);
+        assert_eq!(
+            criteria.check(&progress(10, 0, None)),
+            Some(TerminationReason::Timeout)
+        );
+    }
+
+    #[test]
+    fn test_iteration_limit() {
+        let mut criteria = IterationLimit::new_box(5);
+        assert!(!criteria.is_met(&progress(4, 100, Some(1.0))));
+        assert!(criteria.is_met(&progress(5, 0, None)));
+    }
+
+    #[test]
+    fn test_node_limit() {
+        let mut criteria = NodeLimit::new_box(32);
+        assert!(!criteria.is_met(&progress(100, 31, None)));
+        assert_eq!(
+            criteria.check(&progress(0, 32, None)),
+            Some(TerminationReason::NodeLimit)
+        );
+    }
+
+    #[test]
+    fn test_solution_found() {
+        let mut criteria = SolutionFound::new_box();
+        assert!(!criteria.is_met(&progress(100, 100, None)));
+        assert!(criteria.is_met(&progress(0, 0, Some(f64::MAX))));
+    }
+
+    #[test]
+    fn test_cost_threshold() {
+        let mut criteria = CostThreshold::new_box(2.0);
+        assert!(!criteria.is_met(&progress(0, 0, None)));
+        assert!(!criteria.is_met(&progress(0, 0, Some(2.0))));
+        assert!(criteria.is_met(&progress(0, 0, Some(1.9))));
+    }
+
+    // Test that only iterations without a cost improvement are counted.
+    #[test]
+    fn test_no_improvement() {
+        let mut criteria = NoImprovement::new_box(10);
+        assert!(!criteria.is_met(&progress(0, 0, None)));
+        assert!(!criteria.is_met(&progress(5, 0, Some(3.0))));
+        assert!(!criteria.is_met(&progress(14, 0, Some(3.0))));
+        assert!(!criteria.is_met(&progress(14, 0, Some(2.5))));
+        assert!(!criteria.is_met(&progress(23, 0, Some(2.5))));
+        assert_eq!(
+            criteria.check(&progress(24, 0, Some(2.5))),
+            Some(TerminationReason::NoImprovement)
+        );
+
+        criteria.reset();
+        assert!(!criteria.is_met(&progress(24, 0, Some(2.5))));
+    }
+
+    #[test]
+    fn test_and() {
+        let mut criteria = And::new_box(NodeLimit::new_box(10), SolutionFound::new_box());
+        assert!(!criteria.is_met(&progress(0, 10, None)));
+        assert!(!criteria.is_met(&progress(0, 9, Some(1.0))));
+        assert_eq!(
+            criteria.check(&progress(0, 10, Some(1.0))),
+            Some(TerminationReason::SolutionFound)
+        );
+    }
+
+    #[test]
+    fn test_or() {
+        let mut criteria = Or::new_box(
+            Timeout::new_box(Duration::from_millis(50)),
+            CostThreshold::new_box(1.0),
+        );
+        assert!(!criteria.is_met(&progress(0, 0, Some(2.0))));
+        assert_eq!(
+            criteria.check(&progress(0, 0, Some(0.5))),
+            Some(TerminationReason::CostThreshold)
+        );
+        assert_eq!(
+            criteria.check(&progress(50, 0, Some(0.5))),
+            Some(TerminationReason::Timeout)
+        );
+    }
+
+    // Test that nested combinators reset their stateful children.
+    #[test]
+    fn test_nested_reset() {
+        let mut criteria = Or::new_box(
+            IterationLimit::new_box(1000),
+            And::new_box(SolutionFound::new_box(), NoImprovement::new_box(5)),
+        );
+        assert!(!criteria.is_met(&progress(0, 0, Some(1.0))));
+        assert!(criteria.is_met(&progress(5, 0, Some(1.0))));
+        criteria.reset();
+        assert!(!criteria.is_met(&progress(5, 0, Some(1.0))));
+    }
+}
@@ -1,10 +1,15 @@
 use crate::space::Point;
 
 /// The Problem Definition serves as a collector for various planners and problems.
-pub struct ProblemDefinition {
-    pub start: Point,
-    pub goal: Point,
-    pub solution: Vec<Point>,
+///
+/// - snap_tolerance: If set, an invalid start or goal is moved to the nearest valid state within this distance during setup.
+///
+/// The dimension of the points defaults to 2D.
+pub struct ProblemDefinition<const N: usize = 2> {
+    pub start: Point<N>,
+    pub goal: Point<N>,
+    pub solution: Vec<Point<N>>,
+    pub snap_tolerance: Option<f64>,
 }
 
 /// Implements methods for a generic struct called `ProblemDefinition`.
@@ -14,7 +19,7 @@ pub struct ProblemDefinition {
 ///
 /// # Type Parameters
 /// - `T`: A type that implements the `SpaceContinuous` trait.
-impl ProblemDefinition {
+impl<const N: usize> ProblemDefinition<N> {
     /// Creates a new instance of `ProblemDefinition` with the provided start and goal points, and an empty solution vector.
     ///
     /// # Arguments
@@ -23,11 +28,12 @@ impl ProblemDefinition {
     ///
     /// # Returns
     /// A new instance of `ProblemDefinition` with the provided start and goal points.
-    pub fn new(start: Point, goal: Point) -> Self {
+    pub fn new(start: Point<N>, goal: Point<N>) -> Self {
         ProblemDefinition {
             start,
             goal,
             solution: Vec::new(),
+            snap_tolerance: None,
         }
     }
 
@@ -35,7 +41,7 @@ impl ProblemDefinition {
     ///
     /// # Returns
     /// The start point of the problem.
-    pub fn get_start(&self) -> Point {
+    pub fn get_start(&self) -> Point<N> {
         self.start
     }
 
@@ -43,7 +49,7 @@ impl ProblemDefinition {
     ///
     /// # Returns
     /// The goal point of the problem.
-    pub fn get_goal(&self) -> Point {
+    pub fn get_goal(&self) -> Point<N> {
         self.goal
     }
 
@@ -51,7 +57,7 @@ impl ProblemDefinition {
     ///
     /// # Arguments
     /// - `start`: f64he new start point of the problem.
-    pub fn set_start(&mut self, start: Point) {
+    pub fn set_start(&mut self, start: Point<N>) {
         self.start = start;
     }
 
@@ -59,12 +65,26 @@ impl ProblemDefinition {
     ///
     /// # Arguments
     /// - `goal`: f64he new goal point of the problem.
-    pub fn set_goal(&mut self, goal: Point) {
+    pub fn set_goal(&mut self, goal: Point<N>) {
         self.goal = goal;
     }
+
+    /// Returns the distance within which an invalid start or goal is snapped to a valid state.
+    /// - None: No snapping
+    pub fn get_snap_tolerance(&self) -> Option<f64> {
+        self.snap_tolerance
+    }
+
+    /// Allows setup to snap an invalid start or goal to the nearest valid state within the given distance.
+    ///
+    /// # Arguments
+    /// - `tolerance`: Maximum distance between the original and the snapped point.
+    pub fn set_snap_tolerance(&mut self, tolerance: f64) {
+        self.snap_tolerance = Some(tolerance);
+    }
 }
 
-impl Default for ProblemDefinition {
+impl<const N: usize> Default for ProblemDefinition<N> {
     fn default() -> Self {
         ProblemDefinition::new(Point::default(), Point::default())
     }
@@ -172,4 +192,13 @@ mod tests {
         let prd: ProblemDefinition = ProblemDefinition::default();
         assert_eq!(prd.solution.len(), 0);
     }
+
+    // Test that snapping is disabled by default and can be enabled.
+    #[test]
+    fn test_snap_tolerance() {
+        let mut prd: ProblemDefinition = ProblemDefinition::default();
+        assert_eq!(prd.get_snap_tolerance(), None);
+        prd.set_snap_tolerance(0.5);
+        assert_eq!(prd.get_snap_tolerance(), Some(0.5));
+    }
 }
@@ -0,0 +1,691 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::{Distribution, Normal};
+
+use crate::{boundaries::Boundaries, collision_checker::CollisionChecker, space::Point};
+
+/// Every sampling strategy needs to be based on this trait. Planners call it whenever they need a new random configuration.
+/// The dimension defaults to 2D. Samplers for other configuration spaces implement `StateSampler<N>`.
+pub trait StateSampler<const N: usize = 2> {
+    /// Draws a configuration inside the boundaries. The random number generator of the boundaries is used, so that seeded boundaries give reproducible samples.
+    ///
+    /// Returns:
+    /// - Some(point): A candidate configuration. The planner still checks it for collisions.
+    /// - None: The attempt did not produce a candidate. The planner counts it as a failed sampling attempt.
+    fn sample(
+        &mut self,
+        boundaries: &mut Boundaries<N>,
+        collision_checker: &dyn CollisionChecker<N>,
+    ) -> Option<Point<N>>;
+}
+
+/// Uniform sampling in the axis-aligned box of the boundaries. Default sampler of all planners. Works in any dimension.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct UniformSampler {}
+
+impl UniformSampler {
+    pub fn new_box<const N: usize>() -> Box<dyn StateSampler<N>> {
+        Box::new(UniformSampler {})
+    }
+}
+
+impl<const N: usize> StateSampler<N> for UniformSampler {
+    fn sample(
+        &mut self,
+        boundaries: &mut Boundaries<N>,
+        _collision_checker: &dyn CollisionChecker<N>,
+    ) -> Option<Point<N>> {
+        Some(boundaries.generate_random_configuration())
+    }
+}
+
+/// Draws a point with a normal distributed offset along every axis around the given point.
+/// Returns None if the offset point is outside of the boundaries.
+/// Circular axes are not wrapped, so the point can be interpolated with the given one.
+fn sample_near<const N: usize>(
+    boundaries: &mut Boundaries<N>,
+    center: &Point<N>,
+    std_dev: f64,
+) -> Option<Point<N>> {
+    let normal: Normal<f64> = Normal::new(0f64, std_dev).ok()?;
+    let rng = boundaries.get_rng();
+    let mut coordinates: [f64; N] = center.get_coordinates();
+    for coordinate in coordinates.iter_mut() {
+        *coordinate += normal.sample(rng);
+    }
+    let point: Point<N> = Point::from_coordinates(coordinates);
+    boundaries.is_node_inside(&point).then_some(point)
+}
+
+/// Gaussian sampling concentrates samples close to the surface of obstacles.
+/// A pair of points with a normal distributed distance is drawn. A sample is only returned if exactly one of them is in collision. Then the free one is returned.
+///
+/// - std_dev: Standard deviation of the distance between both points.
+#[derive(Debug, Copy, Clone)]
+pub struct GaussianSampler {
+    pub std_dev: f64,
+}
+
+impl GaussianSampler {
+    pub fn new_box<const N: usize>(std_dev: f64) -> Box<dyn StateSampler<N>> {
+        Box::new(GaussianSampler { std_dev })
+    }
+}
+
+impl<const N: usize> StateSampler<N> for GaussianSampler {
+    fn sample(
+        &mut self,
+        boundaries: &mut Boundaries<N>,
+        collision_checker: &dyn CollisionChecker<N>,
+    ) -> Option<Point<N>> {
+        let first: Point<N> = boundaries.generate_random_configuration();
+        let second: Point<N> = sample_near(boundaries, &first, self.std_dev)?;
+        let second: Point<N> = boundaries.enforce_bounds(&second);
+        match (
+            collision_checker.is_node_colliding(&first),
+            collision_checker.is_node_colliding(&second),
+        ) {
+            (false, true) => Some(first),
+            (true, false) => Some(second),
+            _ => None,
+        }
+    }
+}
+
+/// Bridge-test sampling concentrates samples in narrow passages.
+/// A pair of points with a normal distributed distance is drawn. If both are in collision and their midpoint is free, the midpoint is returned.
+///
+/// - std_dev: Standard deviation of the distance between both points. Should be in the order of the width of the passages.
+#[derive(Debug, Copy, Clone)]
+pub struct BridgeTestSampler {
+    pub std_dev: f64,
+}
+
+impl BridgeTestSampler {
+    pub fn new_box<const N: usize>(std_dev: f64) -> Box<dyn StateSampler<N>> {
+        Box::new(BridgeTestSampler { std_dev })
+    }
+}
+
+impl<const N: usize> StateSampler<N> for BridgeTestSampler {
+    fn sample(
+        &mut self,
+        boundaries: &mut Boundaries<N>,
+        collision_checker: &dyn CollisionChecker<N>,
+    ) -> Option<Point<N>> {
+        let first: Point<N> = boundaries.generate_random_configuration();
+        if !collision_checker.is_node_colliding(&first) {
+            return None;
+        }
+        let second: Point<N> = sample_near(boundaries, &first, self.std_dev)?;
+        if !collision_checker.is_node_colliding(&boundaries.enforce_bounds(&second)) {
+            return None;
+        }
+        let midpoint: Point<N> = boundaries.enforce_bounds(&first.interpolate(&second, 0.5));
+        (!collision_checker.is_node_colliding(&midpoint)).then_some(midpoint)
+    }
+}
+
+/// Returns the goal with a fixed probability, otherwise asks the inner sampler.
+/// Mainly useful for tree planners, which grow towards the sample. Graph planners like PRM reject the goal as a duplicate.
+///
+/// - goal: The goal of the problem
+/// - bias: Probability in [0, 1] to return the goal
+/// - inner: Sampler used in all other cases
+pub struct GoalBiasedSampler<const N: usize = 2> {
+    pub goal: Point<N>,
+    pub bias: f64,
+    pub inner: Box<dyn StateSampler<N>>,
+}
+
+impl<const N: usize> GoalBiasedSampler<N> {
+    pub fn new_box(
+        goal: Point<N>,
+        bias: f64,
+        inner: Box<dyn StateSampler<N>>,
+    ) -> Box<dyn StateSampler<N>> {
+        Box::new(GoalBiasedSampler { goal, bias, inner })
+    }
+}
+
+impl<const N: usize> StateSampler<N> for GoalBiasedSampler<N> {
+    fn sample(
+        &mut self,
+        boundaries: &mut Boundaries<N>,
+        collision_checker: &dyn CollisionChecker<N>,
+    ) -> Option<Point<N>> {
+        if boundaries
+            .get_rng()
+            .random_bool(self.bias.clamp(0f64, 1f64))
+        {
+            return Some(self.goal);
+        }
+        self.inner.sample(boundaries, collision_checker)
+    }
+}
+
+/// Maps a point of the unit cube into the boundaries, axis by axis.
+fn scale_to_boundaries<const N: usize>(boundaries: &Boundaries<N>, unit: [f64; N]) -> Point<N> {
+    let lower: [f64; N] = boundaries.get_lower();
+    let upper: [f64; N] = boundaries.get_upper();
+    Point::from_coordinates(std::array::from_fn(|axis| {
+        lower[axis] + unit[axis] * (upper[axis] - lower[axis])
+    }))
+}
+
+/// Returns the first N prime numbers.
+fn first_primes<const N: usize>() -> [usize; N] {
+    let mut primes: [usize; N] = [0; N];
+    let mut candidate: usize = 2;
+    for axis in 0..N {
+        while primes[..axis]
+            .iter()
+            .any(|prime| candidate.is_multiple_of(*prime))
+        {
+            candidate += 1;
+        }
+        primes[axis] = candidate;
+        candidate += 1;
+    }
+    primes
+}
+
+/// Radical inverse of the index in the given base. The digits of the index are mirrored at the decimal point.
+fn radical_inverse(mut index: usize, base: usize) -> f64 {
+    let inverse_base: f64 = 1f64 / base as f64;
+    let mut factor: f64 = inverse_base;
+    let mut result: f64 = 0f64;
+    while index > 0 {
+        result += (index % base) as f64 * factor;
+        index /= base;
+        factor *= inverse_base;
+    }
+    result
+}
+
+/// Quasi-random sampling with the Halton sequence. Axis i uses the i-th prime as base, i.e. 2 and 3 in 2D.
+/// The samples cover the boundaries evenly and are deterministic, so no seed is needed.
+/// Works in any dimension, but the coverage of the higher axes gets worse with their larger bases.
+///
+/// - skip: Number of leading elements of the sequence that are skipped. The first element is always the lower corner of the boundaries.
+/// - scramble: Seed of a random shift (Cranley-Patterson rotation) of the sequence. `None` gives the plain sequence.
+#[derive(Debug, Copy, Clone)]
+pub struct HaltonSampler<const N: usize = 2> {
+    pub skip: usize,
+    pub scramble: Option<u64>,
+    index: usize,
+    bases: [usize; N],
+    shift: [f64; N],
+}
+
+impl<const N: usize> HaltonSampler<N> {
+    pub fn new(skip: usize, scramble: Option<u64>) -> Self {
+        let shift: [f64; N] = match scramble {
+            Some(seed) => {
+                let mut rng: StdRng = StdRng::seed_from_u64(seed);
+                std::array::from_fn(|_| rng.random())
+            }
+            None => [0f64; N],
+        };
+        HaltonSampler {
+            skip,
+            scramble,
+            index: skip,
+            bases: first_primes(),
+            shift,
+        }
+    }
+
+    pub fn new_box(skip: usize, scramble: Option<u64>) -> Box<dyn StateSampler<N>> {
+        Box::new(HaltonSampler::new(skip, scramble))
+    }
+
+    /// Returns the next element of the sequence in the unit cube.
+    fn next_unit(&mut self) -> [f64; N] {
+        let unit: [f64; N] = std::array::from_fn(|axis| {
+            (radical_inverse(self.index, self.bases[axis]) + self.shift[axis]).fract()
+        });
+        self.index += 1;
+        unit
+    }
+}
+
+impl<const N: usize> StateSampler<N> for HaltonSampler<N> {
+    fn sample(
+        &mut self,
+        boundaries: &mut Boundaries<N>,
+        _collision_checker: &dyn CollisionChecker<N>,
+    ) -> Option<Point<N>> {
+        let unit: [f64; N] = self.next_unit();
+        Some(scale_to_boundaries(boundaries, unit))
+    }
+}
+
+/// Number of bits of the Sobol direction numbers.
+const SOBOL_BITS: usize = 32;
+
+/// Highest dimension of the Sobol sequence, i.e. the first dimension plus one per primitive polynomial.
+pub const SOBOL_MAX_DIMENSION: usize = 1 + SOBOL_POLYNOMIALS.len();
+
+/// Primitive polynomials and initial direction numbers of the Sobol dimensions after the first one.
+/// Each entry is the degree s, the coefficients a and the initial numbers m_1 .. m_s.
+/// Source: Joe, S.; Kuo, F. Y. (2008), "Constructing Sobol sequences with better two-dimensional projections", SIAM Journal on Scientific Computing, 30 (5): 2635–2654, doi:10.1137/070709359
+const SOBOL_POLYNOMIALS: [(usize, u32, [u32; 6]); 15] = [
+    (1, 0, [1, 0, 0, 0, 0, 0]),
+    (2, 1, [1, 3, 0, 0, 0, 0]),
+    (3, 1, [1, 3, 1, 0, 0, 0]),
+    (3, 2, [1, 1, 1, 0, 0, 0]),
+    (4, 1, [1, 1, 3, 3, 0, 0]),
+    (4, 4, [1, 3, 5, 13, 0, 0]),
+    (5, 2, [1, 1, 5, 5, 17, 0]),
+    (5, 4, [1, 1, 5, 5, 5, 0]),
+    (5, 7, [1, 1, 7, 11, 19, 0]),
+    (5, 11, [1, 1, 5, 1, 1, 0]),
+    (5, 13, [1, 1, 1, 3, 11, 0]),
+    (5, 14, [1, 3, 5, 5, 31, 0]),
+    (6, 1, [1, 3, 3, 9, 7, 49]),
+    (6, 13, [1, 1, 1, 15, 21, 21]),
+    (6, 16, [1, 3, 1, 13, 27, 49]),
+];
+
+/// Direction numbers of the first N Sobol dimensions.
+/// The first dimension is the van der Corput sequence. The others follow the recurrence of their primitive polynomial.
+fn sobol_direction_numbers<const N: usize>() -> [[u32; SOBOL_BITS]; N] {
+    let mut directions: [[u32; SOBOL_BITS]; N] = [[0; SOBOL_BITS]; N];
+    for (dimension, directions) in directions.iter_mut().enumerate() {
+        if dimension == 0 {
+            for (bit, direction) in directions.iter_mut().enumerate() {
+                *direction = 1 << (SOBOL_BITS - 1 - bit);
+            }
+            continue;
+        }
+        let (degree, coefficients, initial) = SOBOL_POLYNOMIALS[dimension - 1];
+        for bit in 0..SOBOL_BITS {
+            directions[bit] = if bit < degree {
+                initial[bit] << (SOBOL_BITS - 1 - bit)
+            } else {
+                let mut direction: u32 =
+                    directions[bit - degree] ^ (directions[bit - degree] >> degree);
+                for k in 1..degree {
+                    if coefficients >> (degree - 1 - k) & 1 == 1 {
+                        direction ^= directions[bit - k];
+                    }
+                }
+                direction
+            };
+        }
+    }
+    directions
+}
+
+/// Quasi-random sampling with the Sobol sequence, using the direction numbers of Joe and Kuo.
+/// The samples cover the boundaries evenly and are deterministic, so no seed is needed.
+/// Each block of 2^m samples puts exactly one sample into every cell of a 2^a x 2^b grid with a + b = m in 2D.
+/// Supports up to `SOBOL_MAX_DIMENSION` dimensions. A sampler with more dimensions does not compile.
+///
+/// - skip: Number of leading elements of the sequence that are skipped. The first element is always the lower corner of the boundaries.
+/// - scramble: Seed of a random digital shift (XOR) of the sequence. `None` gives the plain sequence. The shift keeps the even coverage.
+#[derive(Debug, Copy, Clone)]
+pub struct SobolSampler<const N: usize = 2> {
+    pub skip: usize,
+    pub scramble: Option<u64>,
+    index: usize,
+    shift: [u32; N],
+    directions: [[u32; SOBOL_BITS]; N],
+}
+
+impl<const N: usize> SobolSampler<N> {
+    pub fn new(skip: usize, scramble: Option<u64>) -> Self {
+        const {
+            assert!(
+                N <= SOBOL_MAX_DIMENSION,
+                "the Sobol sequence supports at most SOBOL_MAX_DIMENSION dimensions"
+            )
+        };
+        let shift: [u32; N] = match scramble {
+            Some(seed) => {
+                let mut rng: StdRng = StdRng::seed_from_u64(seed);
+                std::array::from_fn(|_| rng.random())
+            }
+            None => [0; N],
+        };
+        SobolSampler {
+            skip,
+            scramble,
+            index: skip,
+            shift,
+            directions: sobol_direction_numbers(),
+        }
+    }
+
+    pub fn new_box(skip: usize, scramble: Option<u64>) -> Box<dyn StateSampler<N>> {
+        Box::new(SobolSampler::new(skip, scramble))
+    }
+
+    /// Returns the next element of the sequence in the unit cube.
+    /// The element is computed directly from the gray code of the index, so skipping is free.
+    fn next_unit(&mut self) -> [f64; N] {
+        let gray: usize = self.index ^ (self.index >> 1);
+        let mut coordinates: [u32; N] = self.shift;
+        for (coordinate, directions) in coordinates.iter_mut().zip(self.directions.iter()) {
+            for (bit, direction) in directions.iter().enumerate() {
+                if gray >> bit & 1 == 1 {
+                    *coordinate ^= direction;
+                }
+            }
+        }
+        self.index += 1;
+        let scale: f64 = 1f64 / (1u64 << SOBOL_BITS) as f64;
+        coordinates.map(|coordinate| coordinate as f64 * scale)
+    }
+}
+
+impl<const N: usize> StateSampler<N> for SobolSampler<N> {
+    fn sample(
+        &mut self,
+        boundaries: &mut Boundaries<N>,
+        _collision_checker: &dyn CollisionChecker<N>,
+    ) -> Option<Point<N>> {
+        let unit: [f64; N] = self.next_unit();
+        Some(scale_to_boundaries(boundaries, unit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision_checker::NaiveCollisionChecker;
+    use crate::test_support::SeamCollisionChecker;
+
+    /// Obstacles are the two half planes x < 1 and x > 2, so there is a free corridor of width 1.
+    struct CorridorCollisionChecker {}
+
+    impl CollisionChecker for CorridorCollisionChecker {
+        fn init(&self) -> bool {
+            true
+        }
+
+        fn is_node_colliding(&self, node: &Point) -> bool {
+            node.get_x() < 1f64 || node.get_x() > 2f64
+        }
+
+        fn is_edge_colliding(&self, begin: &Point, end: &Point) -> bool {
+            self.is_node_colliding(begin) || self.is_node_colliding(end)
+        }
+    }
+
+    /// Draws a number of samples and returns all successful ones.
+    fn draw(sampler: &mut dyn StateSampler, n: usize) -> Vec<Point> {
+        let mut boundaries: Boundaries = Boundaries::new_seeded(0f64, 3f64, 0f64, 3f64, 42);
+        let collision_checker = CorridorCollisionChecker {};
+        (0..n)
+            .filter_map(|_| sampler.sample(&mut boundaries, &collision_checker))
+            .collect()
+    }
+
+    #[test]
+    fn test_uniform_always_samples_inside() {
+        let boundaries: Boundaries = Boundaries::new(0f64, 3f64, 0f64, 3f64);
+        let samples: Vec<Point> = draw(&mut UniformSampler {}, 100);
+        assert_eq!(samples.len(), 100);
+        assert!(samples.iter().all(|p| boundaries.is_node_inside(p)));
+    }
+
+    // Test that gaussian samples are free and close to the obstacle surface.
+    #[test]
+    fn test_gaussian_samples_near_obstacles() {
+        let collision_checker = CorridorCollisionChecker {};
+        let samples: Vec<Point> = draw(&mut GaussianSampler { std_dev: 0.1 }, 1000);
+        assert!(!samples.is_empty());
+        assert!(samples
+            .iter()
+            .all(|p| !collision_checker.is_node_colliding(p)));
+        let near: usize = samples
+            .iter()
+            .filter(|p| p.get_x() < 1.3 || p.get_x() > 1.7)
+            .count();
+        assert!(near > samples.len() * 9 / 10);
+    }
+
+    // Test that bridge-test samples are free midpoints between two colliding points.
+    #[test]
+    fn test_bridge_test_samples_in_passage() {
+        let collision_checker = CorridorCollisionChecker {};
+        let samples: Vec<Point> = draw(&mut BridgeTestSampler { std_dev: 1f64 }, 1000);
+        assert!(!samples.is_empty());
+        assert!(samples
+            .iter()
+            .all(|p| !collision_checker.is_node_colliding(p)));
+    }
+
+    // Test that samples around a circular axis wrap around and find the passage across the seam.
+    #[test]
+    fn test_samples_wrap_around() {
+        use crate::boundaries::Topology;
+        use std::f64::consts::PI;
+
+        let collision_checker = SeamCollisionChecker {};
+        let mut boundaries: Boundaries = Boundaries::new_seeded(-PI, PI, 0f64, 1f64, 42);
+        boundaries.set_topology([Topology::Circular, Topology::Bounded]);
+        let samplers: Vec<Box<dyn StateSampler>> = vec![
+            GaussianSampler::new_box(0.5),
+            BridgeTestSampler::new_box(1f64),
+        ];
+        for mut sampler in samplers {
+            let samples: Vec<Point> = (0..1000)
+                .filter_map(|_| sampler.sample(&mut boundaries, &collision_checker))
+                .collect();
+            assert!(!samples.is_empty());
+            assert!(samples
+                .iter()
+                .all(|p| !collision_checker.is_node_colliding(p)
+                    && (-PI..PI).contains(&p.get_x())
+                    && boundaries.is_node_inside(p)));
+        }
+    }
+
+    #[test]
+    fn test_goal_biased() {
+        let goal: Point = Point::new(3f64, 3f64);
+        let always: Vec<Point> = draw(
+            &mut GoalBiasedSampler {
+                goal,
+                bias: 1f64,
+                inner: UniformSampler::new_box(),
+            },
+            10,
+        );
+        assert!(always.iter().all(|p| *p == goal));
+
+        let never: Vec<Point> = draw(
+            &mut GoalBiasedSampler {
+                goal,
+                bias: 0f64,
+                inner: UniformSampler::new_box(),
+            },
+            10,
+        );
+        assert!(never.iter().all(|p| *p != goal));
+    }
+
+    // Test that seeded boundaries make every sampler reproducible.
+    #[test]
+    fn test_seeded_samplers_are_reproducible() {
+        assert_eq!(
+            draw(&mut GaussianSampler { std_dev: 0.1 }, 100),
+            draw(&mut GaussianSampler { std_dev: 0.1 }, 100)
+        );
+        assert_eq!(
+            draw(&mut BridgeTestSampler { std_dev: 1f64 }, 100),
+            draw(&mut BridgeTestSampler { std_dev: 1f64 }, 100)
+        );
+    }
+
+    /// Counts the samples in every cell of a grid over the unit square.
+    /// A small tolerance keeps samples on a cell border, like 1/3, in the upper cell despite rounding.
+    fn count_cells(samples: &[Point], columns: usize, rows: usize) -> Vec<usize> {
+        let mut counts: Vec<usize> = vec![0; columns * rows];
+        for p in samples {
+            let column: usize = (p.get_x() * columns as f64 + 1e-9) as usize;
+            let row: usize = (p.get_y() * rows as f64 + 1e-9) as usize;
+            counts[row * columns + column] += 1;
+        }
+        counts
+    }
+
+    fn draw_unit(sampler: &mut dyn StateSampler, n: usize) -> Vec<Point> {
+        let mut boundaries: Boundaries = Boundaries::new(0f64, 1f64, 0f64, 1f64);
+        let collision_checker = CorridorCollisionChecker {};
+        (0..n)
+            .filter_map(|_| sampler.sample(&mut boundaries, &collision_checker))
+            .collect()
+    }
+
+    #[test]
+    fn test_halton_sequence() {
+        let samples: Vec<Point> = draw_unit(&mut HaltonSampler::new(1, None), 3);
+        assert_eq!(
+            samples,
+            vec![
+                Point::new(0.5, 1f64 / 3f64),
+                Point::new(0.25, 2f64 / 3f64),
+                Point::new(0.75, 1f64 / 9f64)
+            ]
+        );
+    }
+
+    // Test that 2^4 * 3^2 Halton samples put exactly one sample into every cell of a 16 x 9 grid.
+    #[test]
+    fn test_halton_even_coverage() {
+        let samples: Vec<Point> = draw_unit(&mut HaltonSampler::new(0, None), 144);
+        assert!(count_cells(&samples, 16, 9).iter().all(|count| *count == 1));
+    }
+
+    #[test]
+    fn test_sobol_sequence() {
+        let samples: Vec<Point> = draw_unit(&mut SobolSampler::new(0, None), 5);
+        assert_eq!(
+            samples,
+            vec![
+                Point::new(0f64, 0f64),
+                Point::new(0.5, 0.5),
+                Point::new(0.75, 0.25),
+                Point::new(0.25, 0.75),
+                Point::new(0.375, 0.375)
+            ]
+        );
+    }
+
+    // Test that 256 Sobol samples put exactly one sample into every cell of a 16 x 16 grid, also when scrambled.
+    #[test]
+    fn test_sobol_even_coverage() {
+        for scramble in [None, Some(42)] {
+            let samples: Vec<Point> = draw_unit(&mut SobolSampler::new(0, scramble), 256);
+            assert!(count_cells(&samples, 16, 16)
+                .iter()
+                .all(|count| *count == 1));
+            assert!(count_cells(&samples, 64, 4).iter().all(|count| *count == 1));
+        }
+    }
+
+    #[test]
+    fn test_quasi_random_skip() {
+        let skipped: Vec<Point> = draw_unit(&mut SobolSampler::new(3, None), 5);
+        assert_eq!(skipped, draw_unit(&mut SobolSampler::new(0, None), 8)[3..]);
+        let skipped: Vec<Point> = draw_unit(&mut HaltonSampler::new(3, None), 5);
+        assert_eq!(skipped, draw_unit(&mut HaltonSampler::new(0, None), 8)[3..]);
+    }
+
+    // Test that scrambling changes the sequence, is reproducible and keeps the samples inside the boundaries.
+    #[test]
+    fn test_quasi_random_scramble() {
+        let boundaries: Boundaries = Boundaries::new(0f64, 3f64, 0f64, 3f64);
+        for (plain, scrambled, scrambled_again) in [
+            (
+                draw(&mut HaltonSampler::new(0, None), 100),
+                draw(&mut HaltonSampler::new(0, Some(7)), 100),
+                draw(&mut HaltonSampler::new(0, Some(7)), 100),
+            ),
+            (
+                draw(&mut SobolSampler::new(0, None), 100),
+                draw(&mut SobolSampler::new(0, Some(7)), 100),
+                draw(&mut SobolSampler::new(0, Some(7)), 100),
+            ),
+        ] {
+            assert_ne!(plain, scrambled);
+            assert_eq!(scrambled, scrambled_again);
+            assert!(scrambled.iter().all(|p| boundaries.is_node_inside(p)));
+        }
+    }
+
+    /// Draws samples of a sampler in 3D inside the box [0, 1] x [0, 2] x [-1, 1].
+    fn draw_3d(sampler: &mut dyn StateSampler<3>, n: usize) -> Vec<Point<3>> {
+        let mut boundaries: Boundaries<3> =
+            Boundaries::from_limits([0f64, 0f64, -1f64], [1f64, 2f64, 1f64]);
+        let collision_checker = NaiveCollisionChecker {
+            phantom: std::marker::PhantomData,
+        };
+        (0..n)
+            .filter_map(|_| sampler.sample(&mut boundaries, &collision_checker))
+            .collect()
+    }
+
+    // Test that the quasi-random samplers are deterministic in 3D and scale every axis into the boundaries.
+    #[test]
+    fn test_quasi_random_3d() {
+        let boundaries: Boundaries<3> =
+            Boundaries::from_limits([0f64, 0f64, -1f64], [1f64, 2f64, 1f64]);
+        let halton: Vec<Point<3>> = draw_3d(&mut HaltonSampler::<3>::new(1, None), 2);
+        assert_eq!(
+            halton,
+            vec![
+                Point::from_coordinates([0.5, 2f64 / 3f64, -0.6]),
+                Point::from_coordinates([0.25, 4f64 / 3f64, -0.2])
+            ]
+        );
+        let sobol: Vec<Point<3>> = draw_3d(&mut SobolSampler::<3>::new(0, None), 8);
+        let unit: Vec<[f64; 3]> = sobol
+            .iter()
+            .map(|p| {
+                let [x, y, z] = p.get_coordinates();
+                [x, y / 2f64, (z + 1f64) / 2f64]
+            })
+            .collect();
+        assert_eq!(
+            unit,
+            vec![
+                [0f64, 0f64, 0f64],
+                [0.5, 0.5, 0.5],
+                [0.75, 0.25, 0.25],
+                [0.25, 0.75, 0.75],
+                [0.375, 0.375, 0.625],
+                [0.875, 0.875, 0.125],
+                [0.625, 0.125, 0.875],
+                [0.125, 0.625, 0.375]
+            ]
+        );
+
+        for scramble in [None, Some(7)] {
+            for new_box in [HaltonSampler::<3>::new_box, SobolSampler::<3>::new_box] {
+                let samples: Vec<Point<3>> = draw_3d(new_box(0, scramble).as_mut(), 100);
+                assert_eq!(samples, draw_3d(new_box(0, scramble).as_mut(), 100));
+                assert!(samples.iter().all(|p| boundaries.is_node_inside(p)));
+            }
+        }
+    }
+
+    // Test that every axis of the Sobol sequence puts exactly one of the first 2^m samples into every interval of length 2^-m.
+    #[test]
+    fn test_sobol_all_dimensions() {
+        let mut sampler: SobolSampler<SOBOL_MAX_DIMENSION> = SobolSampler::new(0, None);
+        let samples: Vec<[f64; SOBOL_MAX_DIMENSION]> =
+            (0..1024).map(|_| sampler.next_unit()).collect();
+        for axis in 0..SOBOL_MAX_DIMENSION {
+            let mut cells: Vec<f64> = samples
+                .iter()
+                .map(|unit| (unit[axis] * 1024f64).floor())
+                .collect();
+            cells.sort_by(f64::total_cmp);
+            assert_eq!(cells, (0..1024).map(f64::from).collect::<Vec<f64>>());
+        }
+    }
+}
@@ -1,73 +1,188 @@
-use crate::{boundaries::Boundaries, planner::base_planner::Planner, problem::ProblemDefinition};
+use crate::{
+    boundaries::Boundaries,
+    collision_checker::CollisionStatistics,
+    error::PlanningError,
+    planner::{base_planner::Planner, termination::TerminationReason},
+    problem::ProblemDefinition,
+    space::Point,
+};
+
+/// Number of rings with increasing radius that are searched when snapping a start or goal.
+const SNAP_RINGS: usize = 16;
+
+/// Number of directions searched on every ring when snapping a start or goal.
+const SNAP_DIRECTIONS: usize = 32;
 
 /// Defines a struct called `PlanningSetup` with generic type `T` that has four fields: `planner`, `problem`, `boundaries`, and `ready`.
-pub struct PlanningSetup {
-    pub planner: Box<dyn Planner>,
-    pub problem: ProblemDefinition,
-    pub boundaries: Boundaries,
+/// The dimension of the configuration space defaults to 2D.
+pub struct PlanningSetup<const N: usize = 2> {
+    pub planner: Box<dyn Planner<N>>,
+    pub problem: ProblemDefinition<N>,
+    pub boundaries: Boundaries<N>,
     pub ready: bool,
 }
 
-impl PlanningSetup {
+impl<const N: usize> PlanningSetup<N> {
     /// Sets up the planner by configuring the start point, goal point, boundaries, and performing a sanity check.
-    pub fn setup(&mut self) {
+    /// Start and goal are validated against the boundaries and the collision checker of the planner.
+    /// If the problem has a snap tolerance, an invalid start or goal is replaced by the nearest valid state within the tolerance.
+    ///
+    /// # Errors
+    /// The setup is not ready afterwards, if any check fails.
+    /// - `PlanningError::InvalidBounds`: The sanity check failed
+    /// - `PlanningError::StartOutOfBounds` / `PlanningError::GoalOutOfBounds`: The endpoint is outside of the boundaries
+    /// - `PlanningError::StartInCollision` / `PlanningError::GoalInCollision`: The endpoint is in collision
+    pub fn setup(&mut self) -> Result<(), PlanningError<N>> {
+        self.ready = false;
+        if !self.sanity_check() {
+            return Err(PlanningError::InvalidBounds);
+        }
+        let start: Point<N> = self.problem.get_start();
+        let start: Point<N> = self.validate_endpoint(
+            start,
+            PlanningError::StartOutOfBounds { start },
+            PlanningError::StartInCollision { start },
+        )?;
+        let goal: Point<N> = self.problem.get_goal();
+        let goal: Point<N> = self.validate_endpoint(
+            goal,
+            PlanningError::GoalOutOfBounds { goal },
+            PlanningError::GoalInCollision { goal },
+        )?;
+        self.problem.set_start(start);
+        self.problem.set_goal(goal);
+
         self.planner.set_start(self.problem.get_start());
         self.planner.set_goal(self.problem.get_goal());
         self.planner.set_boundaries(self.boundaries.clone());
-        if self.sanity_check() {
-            self.planner.init();
-            self.ready = true;
-        } else {
-            self.ready = false;
-        }
+        self.planner.init();
+        self.ready = true;
+        Ok(())
     }
 
-    /// Checks if the lower boundaries are less than or equal to the upper boundaries for both the x and y axes.
+    /// Checks if the lower boundaries are less than or equal to the upper boundaries for every axis.
     ///
     /// # Returns
     ///
     /// A boolean value indicating whether the boundaries are valid or not. `true` if the boundaries are valid, `false` otherwise.
     fn sanity_check(&self) -> bool {
-        if self.boundaries.get_x_lower() > self.boundaries.get_x_upper() {
-            return false;
-        }
-        if self.boundaries.get_y_lower() > self.boundaries.get_y_upper() {
-            return false;
-        }
-        true
+        self.boundaries
+            .get_lower()
+            .iter()
+            .zip(self.boundaries.get_upper().iter())
+            .all(|(lower, upper)| lower <= upper)
     }
 
-    /// Solves the planning problem.
-    ///
-    /// # Panics
-    /// Panics if the `PlanningSetup` component is not ready for solving.
-    pub fn solve(&mut self) {
-        if self.ready {
-            self.planner.solve();
+    /// Returns the point, if it is inside the boundaries and not in collision.
+    /// Otherwise it is snapped, if the problem has a snap tolerance. If that fails as well, the error of the failed check is returned.
+    fn validate_endpoint(
+        &self,
+        point: Point<N>,
+        out_of_bounds: PlanningError<N>,
+        in_collision: PlanningError<N>,
+    ) -> Result<Point<N>, PlanningError<N>> {
+        let error: PlanningError<N> = if !self.boundaries.is_node_inside(&point) {
+            out_of_bounds
+        } else if self
+            .planner
+            .get_collision_checker()
+            .is_node_colliding(&point)
+        {
+            in_collision
         } else {
-            panic!("PlanningSetup is not ready for solving.")
+            return Ok(point);
+        };
+        self.problem
+            .get_snap_tolerance()
+            .and_then(|tolerance| self.snap(point, tolerance))
+            .ok_or(error)
+    }
+
+    /// Searches the nearest valid state within the tolerance.
+    /// The point is first clamped into the boundaries, circular axes are wrapped. Then rings with increasing radius around it are searched.
+    /// In more than two dimensions there is a ring in the plane of every pair of axes.
+    ///
+    /// # Returns
+    /// - Some(point): A valid state with a distance of at most `tolerance` to the original point
+    /// - None: There is no valid state within the searched candidates
+    fn snap(&self, point: Point<N>, tolerance: f64) -> Option<Point<N>> {
+        let clamped: Point<N> = self.boundaries.enforce_bounds(&point);
+        let planes: Vec<(usize, usize)> = (0..N)
+            .flat_map(|first| (first + 1..N).map(move |second| (first, second)))
+            .collect();
+        let rings = (1..=SNAP_RINGS).map(|ring| tolerance * ring as f64 / SNAP_RINGS as f64);
+        let candidates = std::iter::once(clamped).chain(rings.flat_map(|radius| {
+            let planes: Vec<(usize, usize)> = planes.clone();
+            planes.into_iter().flat_map(move |(first, second)| {
+                (0..SNAP_DIRECTIONS).map(move |direction| {
+                    let angle: f64 =
+                        std::f64::consts::TAU * direction as f64 / SNAP_DIRECTIONS as f64;
+                    let mut coordinates: [f64; N] = clamped.get_coordinates();
+                    coordinates[first] += radius * angle.cos();
+                    coordinates[second] += radius * angle.sin();
+                    Point::from_coordinates(coordinates)
+                })
+            })
+        }));
+
+        let collision_checker = self.planner.get_collision_checker();
+        candidates
+            .filter(|candidate| self.boundaries.distance(&point, candidate) <= tolerance)
+            .find(|candidate| {
+                self.boundaries.is_node_inside(candidate)
+                    && !collision_checker.is_node_colliding(candidate)
+            })
+            .map(|candidate| self.boundaries.enforce_bounds(&candidate))
+    }
+
+    /// Solves the planning problem and writes the found path into `problem.solution`.
+    ///
+    /// # Errors
+    /// - `PlanningError::NotReady`: `setup` was not called or failed
+    /// - The error of the planner, if it could not grow anymore
+    /// - `PlanningError::Timeout`: The planner stopped because of a timeout without finding a solution
+    /// - `PlanningError::NoSolution`: The planner stopped for any other reason without finding a solution
+    pub fn solve(&mut self) -> Result<(), PlanningError<N>> {
+        if !self.ready {
+            return Err(PlanningError::NotReady);
+        }
+        let result = self.planner.solve();
+        self.problem.solution = self.planner.get_solution_path();
+        result?;
+        if !self.problem.solution.is_empty() {
+            return Ok(());
+        }
+        match self.planner.get_termination_reason() {
+            Some(TerminationReason::Timeout) => Err(PlanningError::Timeout),
+            _ => Err(PlanningError::NoSolution),
         }
     }
 
-    /// Retrieves the solution cost from the `planner` object and prints it to the console.
+    /// Retrieves the solution cost from the `planner` object.
     ///
     /// # Returns
     ///
     /// The solution cost.
     pub fn get_statistics(&self) -> f64 {
-        let cost = self.planner.get_solution_cost();
-        println!("Cost: {}", cost);
-        cost
+        self.planner.get_solution_cost()
+    }
+
+    /// Returns the counters of the collision checks of the planner.
+    /// - None: The collision checker does not count its checks. Wrap it in a `CachingCollisionChecker`.
+    pub fn get_collision_statistics(&self) -> Option<CollisionStatistics> {
+        self.planner.get_collision_statistics()
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod test {
     use crate::boundaries::Boundaries;
     use crate::planner::prm::PRM;
     use crate::problem::ProblemDefinition;
     use crate::setup::PlanningSetup;
     use crate::space::Point;
+    use crate::test_support::BoxCollisionChecker;
 
     #[test]
     fn test_setup_with_prm_new() {
@@ -91,7 +206,7 @@ mod test {
         };
 
         assert!(!setup.ready);
-        setup.setup();
+        setup.setup().unwrap();
         assert!(setup.ready);
     }
 
@@ -104,7 +219,7 @@ mod test {
             boundaries: Boundaries::default(),
             ready: false,
         };
-        setup.solve();
+        setup.solve().unwrap();
     }
 
     #[test]
@@ -119,21 +234,103 @@ mod test {
             ready: false,
         };
 
-        setup.setup();
-        setup.solve();
+        setup.setup().unwrap();
+        setup.solve().unwrap();
     }
 
-    // Test that the 'solve' method panics if the 'ready' field is false.
+    // Test that solving writes the solution path from start to goal into the problem definition.
     #[test]
-    #[should_panic]
-    fn test_solve_panics_if_not_ready() {
+    fn test_setup_with_prm_solve_writes_solution() {
+        let start: Point = Point::new(1f64, 1f64);
+        let goal: Point = Point::new(2f64, 2f64);
+
+        let mut setup: PlanningSetup = PlanningSetup {
+            planner: Box::<PRM>::default(),
+            problem: ProblemDefinition::new(start, goal),
+            boundaries: Boundaries::new(0f64, 3f64, 0f64, 3f64),
+            ready: false,
+        };
+
+        setup.setup().unwrap();
+        setup.solve().unwrap();
+        assert_eq!(setup.problem.solution, setup.planner.get_solution_path());
+        assert_eq!(setup.problem.solution.first(), Some(&start));
+        assert_eq!(setup.problem.solution.last(), Some(&goal));
+    }
+
+    // Test that the 'solve' method returns an error if the 'ready' field is false.
+    #[test]
+    fn test_solve_returns_error_if_not_ready() {
+        use crate::error::PlanningError;
+
         let mut setup: PlanningSetup = PlanningSetup {
             planner: Box::<PRM>::default(),
             problem: ProblemDefinition::default(),
             boundaries: Boundaries::default(),
             ready: false,
         };
-        setup.solve();
+        assert!(matches!(setup.solve(), Err(PlanningError::NotReady)));
+    }
+
+    // Test that invalid boundaries are reported by 'setup' and leave the setup not ready.
+    #[test]
+    fn test_setup_invalid_bounds() {
+        use crate::error::PlanningError;
+
+        let mut setup: PlanningSetup = PlanningSetup {
+            planner: Box::<PRM>::default(),
+            problem: ProblemDefinition::default(),
+            boundaries: Boundaries::new(2f64, 0f64, 0f64, 1f64),
+            ready: true,
+        };
+        assert!(matches!(setup.setup(), Err(PlanningError::InvalidBounds)));
+        assert!(!setup.ready);
+        assert!(matches!(setup.solve(), Err(PlanningError::NotReady)));
+    }
+
+    // Test that a planner which terminates without a solution is reported as such.
+    #[test]
+    fn test_solve_no_solution() {
+        use crate::collision_checker::CollisionChecker;
+        use crate::error::PlanningError;
+        use crate::planner::termination::{IterationLimit, Timeout};
+        use std::time::Duration;
+
+        /// Blocks every edge, so the roadmap never connects start and goal.
+        struct NoEdgesCollisionChecker {}
+
+        impl CollisionChecker for NoEdgesCollisionChecker {
+            fn init(&self) -> bool {
+                true
+            }
+
+            fn is_node_colliding(&self, _node: &Point) -> bool {
+                false
+            }
+
+            fn is_edge_colliding(&self, _begin: &Point, _end: &Point) -> bool {
+                true
+            }
+        }
+
+        let mut setup: PlanningSetup = PlanningSetup {
+            planner: Box::new(PRM::new(Box::new(NoEdgesCollisionChecker {}))),
+            problem: ProblemDefinition::new(Point::new(0f64, 0f64), Point::new(3f64, 3f64)),
+            boundaries: Boundaries::new(0f64, 3f64, 0f64, 3f64),
+            ready: false,
+        };
+        setup
+            .planner
+            .set_termination_criteria(IterationLimit::new_box(20));
+        setup.setup().unwrap();
+        assert!(matches!(setup.solve(), Err(PlanningError::NoSolution)));
+        assert!(setup.problem.solution.is_empty());
+
+        setup
+            .planner
+            .set_termination_criteria(Timeout::new_box(Duration::from_millis(1)));
+        setup.setup().unwrap();
+        assert!(matches!(setup.solve(), Err(PlanningError::Timeout)));
     }
 
     // Test the 'get_statistics' method of the 'test_setup_with_prm_new' function
@@ -149,8 +346,8 @@ mod test {
             ready: false,
         };
 
-        setup.setup();
-        setup.solve();
+        setup.setup().unwrap();
+        setup.solve().unwrap();
         let cost: f64 = setup.get_statistics();
         let expected_cost: f64 = f64::INFINITY;
         assert_eq!(cost, expected_cost);
@@ -218,4 +415,143 @@ mod test {
 
         assert_eq!(result, false);
     }
+
+    // Test that the error of the planner is propagated, if the boundaries do not allow any new sample.
+    #[test]
+    fn test_solve_propagates_no_valid_samples() {
+        use crate::error::PlanningError;
+
+        let point: Point = Point::new(1f64, 1f64);
+        let mut setup: PlanningSetup = PlanningSetup {
+            planner: Box::<PRM>::default(),
+            problem: ProblemDefinition::new(point, point),
+            boundaries: Boundaries::new(1f64, 1f64, 1f64, 1f64),
+            ready: false,
+        };
+        setup.setup().unwrap();
+        assert!(matches!(
+            setup.solve(),
+            Err(PlanningError::NoValidSamples { attempts: 1000 })
+        ));
+    }
+
+    fn create_box_setup(start: Point, goal: Point) -> PlanningSetup {
+        PlanningSetup {
+            planner: Box::new(PRM::new(Box::new(BoxCollisionChecker {}))),
+            problem: ProblemDefinition::new(start, goal),
+            boundaries: Boundaries::new_seeded(0f64, 3f64, 0f64, 3f64, 42),
+            ready: false,
+        }
+    }
+
+    // Test that setup reports exactly which endpoint check failed.
+    #[test]
+    fn test_setup_validates_endpoints() {
+        use crate::error::PlanningError;
+
+        let inside: Point = Point::new(0.5, 0.5);
+        let outside: Point = Point::new(-1f64, 0.5);
+        let colliding: Point = Point::new(1.5, 1.5);
+
+        let mut setup: PlanningSetup = create_box_setup(outside, inside);
+        assert!(matches!(
+            setup.setup(),
+            Err(PlanningError::StartOutOfBounds { start }) if start == outside
+        ));
+        assert!(!setup.ready);
+
+        let mut setup: PlanningSetup = create_box_setup(colliding, inside);
+        assert!(matches!(
+            setup.setup(),
+            Err(PlanningError::StartInCollision { start }) if start == colliding
+        ));
+
+        let mut setup: PlanningSetup = create_box_setup(inside, outside);
+        assert!(matches!(
+            setup.setup(),
+            Err(PlanningError::GoalOutOfBounds { goal }) if goal == outside
+        ));
+
+        let mut setup: PlanningSetup = create_box_setup(inside, colliding);
+        assert!(matches!(
+            setup.setup(),
+            Err(PlanningError::GoalInCollision { goal }) if goal == colliding
+        ));
+        assert!(!setup.ready);
+    }
+
+    // Test that invalid endpoints are snapped to a nearby valid state and used for planning.
+    #[test]
+    fn test_setup_snaps_endpoints() {
+        let start: Point = Point::new(-0.2, 0.5);
+        let goal: Point = Point::new(1.9, 1.5);
+        let mut setup: PlanningSetup = create_box_setup(start, goal);
+        setup.problem.set_snap_tolerance(0.3);
+        setup.setup().unwrap();
+
+        let snapped_start: Point = setup.problem.get_start();
+        let snapped_goal: Point = setup.problem.get_goal();
+        assert_eq!(snapped_start, Point::new(0f64, 0.5));
+        assert!(goal.euclidean_distance(&snapped_goal) <= 0.3);
+        assert!(setup.boundaries.is_node_inside(&snapped_goal));
+        assert!(!setup
+            .planner
+            .get_collision_checker()
+            .is_node_colliding(&snapped_goal));
+
+        setup.solve().unwrap();
+        assert_eq!(setup.problem.solution.first(), Some(&snapped_start));
+        assert_eq!(setup.problem.solution.last(), Some(&snapped_goal));
+    }
+
+    // Test that the original error is reported, if no valid state is within the snap tolerance.
+    #[test]
+    fn test_setup_snap_tolerance_too_small() {
+        use crate::error::PlanningError;
+
+        let colliding: Point = Point::new(1.5, 1.5);
+        let mut setup: PlanningSetup = create_box_setup(Point::new(0.5, 0.5), colliding);
+        setup.problem.set_snap_tolerance(0.4);
+        assert!(matches!(
+            setup.setup(),
+            Err(PlanningError::GoalInCollision { goal }) if goal == colliding
+        ));
+    }
+
+    // Test that a goal in collision is snapped along the third axis in 3D.
+    #[test]
+    fn test_setup_snaps_endpoints_3d() {
+        use crate::collision_checker::CollisionChecker;
+
+        /// Blocks the floor below a height of 0.2.
+        struct FloorCollisionChecker {}
+
+        impl CollisionChecker<3> for FloorCollisionChecker {
+            fn init(&self) -> bool {
+                true
+            }
+
+            fn is_node_colliding(&self, node: &Point<3>) -> bool {
+                node.get_coordinates()[2] < 0.2
+            }
+
+            fn is_edge_colliding(&self, begin: &Point<3>, end: &Point<3>) -> bool {
+                self.is_node_colliding(begin) || self.is_node_colliding(end)
+            }
+        }
+
+        let goal: Point<3> = Point::from_coordinates([1f64, 1f64, 0.1]);
+        let mut setup: PlanningSetup<3> = PlanningSetup {
+            planner: Box::new(PRM::new(Box::new(FloorCollisionChecker {}))),
+            problem: ProblemDefinition::new(Point::from_coordinates([2f64; 3]), goal),
+            boundaries: Boundaries::from_limits([0f64; 3], [3f64; 3]),
+            ready: false,
+        };
+        setup.problem.set_snap_tolerance(0.3);
+        setup.setup().unwrap();
+
+        let snapped_goal: Point<3> = setup.problem.get_goal();
+        assert!(goal.euclidean_distance(&snapped_goal) <= 0.3);
+        assert!(snapped_goal.get_coordinates()[2] >= 0.2);
+    }
 }
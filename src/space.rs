@@ -1,11 +1,11 @@
-/// Defines a struct called `Point` with two generic fields `x` and `y`.
+/// A point in the N-dimensional configuration space, e.g. the position of a 3D drone or the joint angles of a 6-DOF arm.
+/// The dimension defaults to 2D, where the coordinates are called `x` and `y`.
 #[derive(Debug, Clone, Copy)]
-pub struct Point {
-    x: f64,
-    y: f64,
+pub struct Point<const N: usize = 2> {
+    coordinates: [f64; N],
 }
 
-/// Implements the `PartialEq` trait for the `Point` struct. This trait allows for the equality comparison between two `Point` instances based on the difference between their x and y coordinates.
+/// Implements the `PartialEq` trait for the `Point` struct. This trait allows for the equality comparison between two `Point` instances based on the difference between their coordinates.
 ///
 /// # Inputs
 ///
@@ -14,18 +14,19 @@ pub struct Point {
 ///
 /// # Outputs
 ///
-/// - `true` if the x and y coordinates of `self` and `other` are equal within the epsilon value, indicating that the two points are equal.
-/// - `false` if the x and y coordinates of `self` and `other` are not equal within the epsilon value, indicating that the two points are not equal.
-impl PartialEq for Point {
+/// - `true` if all coordinates of `self` and `other` are equal within the epsilon value, indicating that the two points are equal.
+/// - `false` if any coordinate of `self` and `other` is not equal within the epsilon value, indicating that the two points are not equal.
+impl<const N: usize> PartialEq for Point<N> {
     fn eq(&self, other: &Self) -> bool {
-        let eq_x: bool = (self.x - other.x).abs() < f64::EPSILON;
-        let eq_y: bool = (self.y - other.y).abs() < f64::EPSILON;
-        eq_x && eq_y
+        self.coordinates
+            .iter()
+            .zip(other.coordinates.iter())
+            .all(|(a, b)| (a - b).abs() < f64::EPSILON)
     }
 }
 
 impl Point {
-    /// Creates a new `Point` instance with the given coordinates.
+    /// Creates a new 2D `Point` instance with the given coordinates.
     ///
     /// # Parameters
     /// - `x`: f64he x-coordinate of the point.
@@ -34,15 +35,51 @@ impl Point {
     /// # Returns
     /// A new `Point` instance with the given coordinates.
     pub fn new(x: f64, y: f64) -> Self {
-        Point { x, y }
+        Point {
+            coordinates: [x, y],
+        }
+    }
+
+    /// Retrieves the x-coordinate of a Point instance.
+    pub fn get_x(&self) -> f64 {
+        self.coordinates[0]
+    }
+
+    /// Retrieves the y-coordinate of a Point instance.
+    pub fn get_y(&self) -> f64 {
+        self.coordinates[1]
+    }
+}
+
+impl<const N: usize> Point<N> {
+    /// Creates a new `Point` instance with the given coordinates in any dimension.
+    ///
+    /// # Parameters
+    /// - `coordinates`: One value for every axis of the configuration space.
+    ///
+    /// # Returns
+    /// A new `Point` instance with the given coordinates.
+    pub fn from_coordinates(coordinates: [f64; N]) -> Self {
+        Point { coordinates }
+    }
+
+    /// Retrieves the coordinates of a Point instance. Also used as key of the nearest neighbor structures.
+    pub fn get_coordinates(&self) -> [f64; N] {
+        self.coordinates
     }
 
     /// Formats the point as a well-known text (WKT) string.
+    /// All coordinates are listed, so a 3D point gives `POINT(1 2 3)`.
     ///
     /// # Returns
     /// The WKT representation of the point.
     pub fn to_wkt(&self) -> String {
-        format!("POINT({} {})", self.x, self.x)
+        let coordinates: Vec<String> = self
+            .coordinates
+            .iter()
+            .map(|coordinate| coordinate.to_string())
+            .collect();
+        format!("POINT({})", coordinates.join(" "))
     }
 
     /// Calculates the Euclidean distance between the current point and another point.
@@ -52,24 +89,33 @@ impl Point {
     ///
     /// # Returns
     /// The Euclidean distance between the two points.
-    pub fn euclidean_distance(&self, other: &Point) -> f64 {
-        let x_diff = self.get_x() - other.get_x();
-        let y_diff = self.get_y() - other.get_y();
-        f64::sqrt(x_diff * x_diff + y_diff * y_diff)
-    }
-
-    /// Retrieves the x-coordinate of a Point instance.
-    pub fn get_x(&self) -> f64 {
-        self.x
+    pub fn euclidean_distance(&self, other: &Point<N>) -> f64 {
+        self.coordinates
+            .iter()
+            .zip(other.coordinates.iter())
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum::<f64>()
+            .sqrt()
     }
 
-    /// Retrieves the y-coordinate of a Point instance.
-    pub fn get_y(&self) -> f64 {
-        self.y
+    /// Linearly interpolates between the current point and another point.
+    ///
+    /// # Parameters
+    /// - `other`: The point to interpolate towards.
+    /// - `t`: The interpolation parameter. `0.0` returns `self`, `1.0` returns `other`.
+    ///
+    /// # Returns
+    /// The point at fraction `t` on the straight segment from `self` to `other`.
+    pub fn interpolate(&self, other: &Point<N>, t: f64) -> Point<N> {
+        let mut coordinates: [f64; N] = self.coordinates;
+        for (coordinate, target) in coordinates.iter_mut().zip(other.coordinates.iter()) {
+            *coordinate += (target - *coordinate) * t;
+        }
+        Point { coordinates }
     }
 }
 
-/// Implements the `Default` trait for the `Point` struct. This implementation provides a default value for the `Point` struct by setting the `x` and `y` coordinates to the default value defined in the `SpaceContinuous` trait for the generic type `T`.
+/// Implements the `Default` trait for the `Point` struct. This implementation provides a default value for the `Point` struct by setting all coordinates to the default value of f64.
 ///
 /// # Inputs
 ///
@@ -77,19 +123,78 @@ impl Point {
 ///
 /// # Outputs
 ///
-/// - A new `Point` instance with the `x` and `y` coordinates set to the default value.
-impl Default for Point {
+/// - A new `Point` instance at the origin.
+impl<const N: usize> Default for Point<N> {
     fn default() -> Self {
         Point {
-            x: f64::default(),
-            y: f64::default(),
+            coordinates: [f64::default(); N],
+        }
+    }
+}
+
+/// A point in space and time. Used by planners, that plan around moving obstacles.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TimedPoint {
+    point: Point,
+    time: f64,
+}
+
+impl TimedPoint {
+    /// Creates a new `TimedPoint`, which is at the point at the given time.
+    pub fn new(point: Point, time: f64) -> Self {
+        TimedPoint { point, time }
+    }
+
+    /// Retrieves the position of a TimedPoint instance.
+    pub fn get_point(&self) -> Point {
+        self.point
+    }
+
+    /// Retrieves the time of a TimedPoint instance.
+    pub fn get_time(&self) -> f64 {
+        self.time
+    }
+
+    /// Returns the speed, that is needed to move from this point to the other one.
+    /// - infinity: The other point is not later, but at another position
+    pub fn speed_to(&self, other: &TimedPoint) -> f64 {
+        let distance: f64 = self.point.euclidean_distance(&other.point);
+        let duration: f64 = other.time - self.time;
+        if distance == 0f64 && duration >= 0f64 {
+            0f64
+        } else if duration <= 0f64 {
+            f64::INFINITY
+        } else {
+            distance / duration
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::space::Point;
+    use crate::space::{Point, TimedPoint};
+
+    // Test the speed between points in space and time, including waiting and moving backwards in time.
+    #[test]
+    fn test_timed_point_speed() {
+        let begin: TimedPoint = TimedPoint::new(Point::new(0.0, 0.0), 1.0);
+        assert_eq!(
+            begin.speed_to(&TimedPoint::new(Point::new(3.0, 4.0), 3.0)),
+            2.5
+        );
+        assert_eq!(
+            begin.speed_to(&TimedPoint::new(Point::new(0.0, 0.0), 2.0)),
+            0.0
+        );
+        assert_eq!(
+            begin.speed_to(&TimedPoint::new(Point::new(1.0, 0.0), 1.0)),
+            f64::INFINITY
+        );
+        assert_eq!(
+            begin.speed_to(&TimedPoint::new(Point::new(1.0, 0.0), 0.0)),
+            f64::INFINITY
+        );
+    }
 
     // Test the calculation of the Euclidean distance between two points with positive coordinates.
     #[test]
@@ -182,4 +287,35 @@ mod tests {
         let distance: f64 = point1.euclidean_distance(&point2);
         assert_eq!(distance, 1.0);
     }
+
+    // Test that interpolating returns the end points for t = 0 and t = 1 and the midpoint for t = 0.5.
+    #[test]
+    fn test_interpolate() {
+        let point1: Point = Point::new(0.0, 0.0);
+        let point2: Point = Point::new(2.0, 4.0);
+        assert_eq!(point1.interpolate(&point2, 0.0), point1);
+        assert_eq!(point1.interpolate(&point2, 1.0), point2);
+        assert_eq!(point1.interpolate(&point2, 0.5), Point::new(1.0, 2.0));
+    }
+
+    // Test that the WKT representation contains both coordinates.
+    #[test]
+    fn test_to_wkt() {
+        let point: Point = Point::new(1.0, 2.0);
+        assert_eq!(point.to_wkt(), "POINT(1 2)");
+    }
+
+    // Test distance, interpolation and WKT of a point in 3D.
+    #[test]
+    fn test_point_3d() {
+        let point1: Point<3> = Point::from_coordinates([1.0, 2.0, 2.0]);
+        let point2: Point<3> = Point::from_coordinates([0.0, 0.0, 0.0]);
+        assert_eq!(point1.euclidean_distance(&point2), 3.0);
+        assert_eq!(
+            point2.interpolate(&point1, 0.5),
+            Point::from_coordinates([0.5, 1.0, 1.0])
+        );
+        assert_eq!(point1.to_wkt(), "POINT(1 2 2)");
+        assert_eq!(point2, Point::default());
+    }
 }
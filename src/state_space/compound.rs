@@ -0,0 +1,516 @@
+use std::ops::Range;
+
+use crate::boundaries::Boundaries;
+use crate::error::PlanningError;
+use crate::space::Point;
+use crate::state_space::StateSpace;
+
+/// Object safe view of a `StateSpace<M>` on its slice of the compound state.
+/// The sub-spaces have different dimensions, so they are stored behind this trait.
+trait SubSpace {
+    fn distance(&self, from: &[f64], to: &[f64]) -> f64;
+    fn interpolate(&self, from: &[f64], to: &[f64], t: f64) -> Vec<f64>;
+    fn enforce_bounds(&self, state: &[f64]) -> Vec<f64>;
+    fn get_key(&self, state: &[f64]) -> Vec<f64>;
+    fn get_key_images(&self, key: &[f64]) -> Vec<Vec<f64>>;
+    fn get_segments(&self, from: &[f64], to: &[f64]) -> Vec<(Vec<f64>, Vec<f64>)>;
+    fn is_symmetric(&self) -> bool;
+}
+
+/// Converts the slice of a sub-space into its state. The slice is always `M` long.
+fn to_point<const M: usize>(slice: &[f64]) -> Point<M> {
+    Point::from_coordinates(std::array::from_fn(|axis| slice[axis]))
+}
+
+impl<const M: usize> SubSpace for Box<dyn StateSpace<M>> {
+    fn distance(&self, from: &[f64], to: &[f64]) -> f64 {
+        self.as_ref().distance(&to_point(from), &to_point(to))
+    }
+
+    fn interpolate(&self, from: &[f64], to: &[f64], t: f64) -> Vec<f64> {
+        self.as_ref()
+            .interpolate(&to_point(from), &to_point(to), t)
+            .get_coordinates()
+            .to_vec()
+    }
+
+    fn enforce_bounds(&self, state: &[f64]) -> Vec<f64> {
+        self.as_ref()
+            .enforce_bounds(to_point(state))
+            .get_coordinates()
+            .to_vec()
+    }
+
+    fn get_key(&self, state: &[f64]) -> Vec<f64> {
+        self.as_ref().get_key(&to_point(state)).to_vec()
+    }
+
+    fn get_key_images(&self, key: &[f64]) -> Vec<Vec<f64>> {
+        self.as_ref()
+            .get_key_images(std::array::from_fn(|axis| key[axis]))
+            .iter()
+            .map(|image| image.to_vec())
+            .collect()
+    }
+
+    fn get_segments(&self, from: &[f64], to: &[f64]) -> Vec<(Vec<f64>, Vec<f64>)> {
+        self.as_ref()
+            .get_segments(&to_point(from), &to_point(to))
+            .iter()
+            .map(|(begin, end)| {
+                (
+                    begin.get_coordinates().to_vec(),
+                    end.get_coordinates().to_vec(),
+                )
+            })
+            .collect()
+    }
+
+    fn is_symmetric(&self) -> bool {
+        self.as_ref().is_symmetric()
+    }
+}
+
+/// Sub-space with the axes of the compound state it covers and its distance weight.
+struct Part {
+    sub_space: Box<dyn SubSpace>,
+    axes: Range<usize>,
+    weight: f64,
+}
+
+/// Composes sub-spaces into one state space, e.g. SE(2) for the base of a mobile manipulator and the joint angles of its arm.
+/// Every sub-space covers the next axes of the compound state, in the order they were added.
+///
+/// - Distance: Sum of the distances of the sub-spaces, multiplied by their weights
+/// - Interpolation: All sub-spaces move at the same time, each with its own motion
+/// - Bounds: Every sub-space enforces the bounds of its axes
+/// - Sampling: `get_boundaries` combines the boundaries of the sub-spaces, so every sampler works on the compound state
+///
+/// Axes that are not covered by a sub-space do not count for the distance and keep the value of the state the motion starts from.
+///
+/// # Example
+/// A base in SE(2) with an arm of two joints is a `CompoundStateSpace<5>`:
+/// `add_subspace(SE2StateSpace::new_box(1.0), &SE2StateSpace::boundaries(0.0, 10.0, 0.0, 10.0), 1.0)` for the axes (x, y, theta),
+/// then `add_subspace(EuclideanStateSpace::new_box(), &Boundaries::from_limits([-1.5; 2], [1.5; 2]), 0.5)` for the two joints.
+pub struct CompoundStateSpace<const N: usize = 2> {
+    parts: Vec<Part>,
+    lower: [f64; N],
+    upper: [f64; N],
+}
+
+impl<const N: usize> Default for CompoundStateSpace<N> {
+    fn default() -> Self {
+        CompoundStateSpace::new()
+    }
+}
+
+impl<const N: usize> CompoundStateSpace<N> {
+    pub fn new() -> Self {
+        CompoundStateSpace {
+            parts: Vec::new(),
+            lower: [f64::default(); N],
+            upper: [f64::default(); N],
+        }
+    }
+
+    /// Adds a sub-space, that covers the next `M` axes of the compound state.
+    ///
+    /// # Arguments
+    /// - `state_space`: State space of the sub-space
+    /// - `boundaries`: Limits of the axes of the sub-space, used by `get_boundaries`
+    /// - `weight`: Factor of the distance of the sub-space in the distance of the compound state
+    ///
+    /// # Errors
+    /// Returns `PlanningError::InvalidStateSpace`, if the sub-space does not fit into the remaining axes or the weight is not positive.
+    pub fn add_subspace<const M: usize>(
+        &mut self,
+        state_space: Box<dyn StateSpace<M>>,
+        boundaries: &Boundaries<M>,
+        weight: f64,
+    ) -> Result<(), PlanningError<N>> {
+        let axes: Range<usize> = self.get_dimension()..self.get_dimension() + M;
+        if axes.end > N {
+            return Err(PlanningError::InvalidStateSpace {
+                message: format!(
+                    "sub-space with {M} axes exceeds the {} remaining axes",
+                    N - axes.start
+                ),
+            });
+        }
+        if !(weight > 0f64 && weight.is_finite()) {
+            return Err(PlanningError::InvalidStateSpace {
+                message: format!("weight {weight} is not positive"),
+            });
+        }
+        self.lower[axes.clone()].copy_from_slice(&boundaries.get_lower());
+        self.upper[axes.clone()].copy_from_slice(&boundaries.get_upper());
+        self.parts.push(Part {
+            sub_space: Box::new(state_space),
+            axes,
+            weight,
+        });
+        Ok(())
+    }
+
+    /// Returns the number of axes covered by the sub-spaces.
+    pub fn get_dimension(&self) -> usize {
+        self.parts.last().map_or(0, |part| part.axes.end)
+    }
+
+    /// Returns the number of sub-spaces.
+    pub fn len(&self) -> usize {
+        self.parts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    /// Returns the boundaries of all sub-spaces combined. Axes without sub-space are limited to 0.
+    pub fn get_boundaries(&self) -> Boundaries<N> {
+        Boundaries::from_limits(self.lower, self.upper)
+    }
+}
+
+impl<const N: usize> StateSpace<N> for CompoundStateSpace<N> {
+    fn distance(&self, from: &Point<N>, to: &Point<N>) -> f64 {
+        let (from, to) = (from.get_coordinates(), to.get_coordinates());
+        self.parts
+            .iter()
+            .map(|part| {
+                part.weight
+                    * part
+                        .sub_space
+                        .distance(&from[part.axes.clone()], &to[part.axes.clone()])
+            })
+            .sum()
+    }
+
+    fn interpolate(&self, from: &Point<N>, to: &Point<N>, t: f64) -> Point<N> {
+        let (from, to) = (from.get_coordinates(), to.get_coordinates());
+        let mut coordinates: [f64; N] = from;
+        for part in &self.parts {
+            coordinates[part.axes.clone()].copy_from_slice(&part.sub_space.interpolate(
+                &from[part.axes.clone()],
+                &to[part.axes.clone()],
+                t,
+            ));
+        }
+        Point::from_coordinates(coordinates)
+    }
+
+    fn enforce_bounds(&self, state: Point<N>) -> Point<N> {
+        let mut coordinates: [f64; N] = state.get_coordinates();
+        for part in &self.parts {
+            let bounded: Vec<f64> = part
+                .sub_space
+                .enforce_bounds(&coordinates[part.axes.clone()]);
+            coordinates[part.axes.clone()].copy_from_slice(&bounded);
+        }
+        Point::from_coordinates(coordinates)
+    }
+
+    /// The keys of the sub-spaces multiplied by their weights, so the key distance stays below the weighted sum of the distances.
+    fn get_key(&self, state: &Point<N>) -> [f64; N] {
+        let coordinates: [f64; N] = state.get_coordinates();
+        let mut key: [f64; N] = [f64::default(); N];
+        for part in &self.parts {
+            let sub_key: Vec<f64> = part.sub_space.get_key(&coordinates[part.axes.clone()]);
+            for (axis, value) in part.axes.clone().zip(sub_key) {
+                key[axis] = part.weight * value;
+            }
+        }
+        key
+    }
+
+    /// Every combination of the key images of the sub-spaces.
+    fn get_key_images(&self, key: [f64; N]) -> Vec<[f64; N]> {
+        let mut images: Vec<[f64; N]> = vec![key];
+        for part in &self.parts {
+            let sub_key: Vec<f64> = key[part.axes.clone()]
+                .iter()
+                .map(|value| value / part.weight)
+                .collect();
+            let sub_images: Vec<Vec<f64>> = part.sub_space.get_key_images(&sub_key);
+            images = images
+                .iter()
+                .flat_map(|image| {
+                    sub_images.iter().map(move |sub_image| {
+                        let mut combined: [f64; N] = *image;
+                        for (axis, value) in part.axes.clone().zip(sub_image) {
+                            combined[axis] = part.weight * value;
+                        }
+                        combined
+                    })
+                })
+                .collect();
+        }
+        images
+    }
+
+    /// Combines the segments of the sub-spaces. The motion is split wherever one of the sub-spaces splits it.
+    /// Within a segment of a sub-space, the progress is assumed to be proportional to the length of the segment.
+    fn get_segments(&self, from: &Point<N>, to: &Point<N>) -> Vec<(Point<N>, Point<N>)> {
+        let (from, to) = (from.get_coordinates(), to.get_coordinates());
+        let pieces: Vec<Pieces> = self
+            .parts
+            .iter()
+            .map(|part| {
+                let (sub_from, sub_to) = (&from[part.axes.clone()], &to[part.axes.clone()]);
+                let segments: Vec<(Vec<f64>, Vec<f64>)> =
+                    part.sub_space.get_segments(sub_from, sub_to);
+                if segments.is_empty() {
+                    Pieces::new(vec![(sub_from.to_vec(), sub_to.to_vec())])
+                } else {
+                    Pieces::new(segments)
+                }
+            })
+            .collect();
+
+        let mut breaks: Vec<f64> = pieces
+            .iter()
+            .flat_map(|pieces| pieces.breaks.iter().copied())
+            .chain([0f64, 1f64])
+            .collect();
+        breaks.sort_by(f64::total_cmp);
+        breaks.dedup_by(|a, b| (*a - *b).abs() < 1e-12);
+
+        breaks
+            .windows(2)
+            .map(|interval| {
+                let (mut begin, mut end): ([f64; N], [f64; N]) = (from, from);
+                for (part, pieces) in self.parts.iter().zip(&pieces) {
+                    let (sub_begin, sub_end) = pieces.get_segment(interval[0], interval[1]);
+                    begin[part.axes.clone()].copy_from_slice(&sub_begin);
+                    end[part.axes.clone()].copy_from_slice(&sub_end);
+                }
+                (Point::from_coordinates(begin), Point::from_coordinates(end))
+            })
+            .collect()
+    }
+
+    fn is_symmetric(&self) -> bool {
+        self.parts.iter().all(|part| part.sub_space.is_symmetric())
+    }
+}
+
+/// Segments of one sub-space with the fraction of the motion at which each of them begins and ends.
+struct Pieces {
+    segments: Vec<(Vec<f64>, Vec<f64>)>,
+    breaks: Vec<f64>,
+}
+
+impl Pieces {
+    /// The fractions are the lengths of the segments relative to their sum. Segments without length share the motion equally.
+    fn new(segments: Vec<(Vec<f64>, Vec<f64>)>) -> Self {
+        let lengths: Vec<f64> = segments
+            .iter()
+            .map(|(begin, end)| {
+                begin
+                    .iter()
+                    .zip(end)
+                    .map(|(a, b)| (b - a) * (b - a))
+                    .sum::<f64>()
+                    .sqrt()
+            })
+            .collect();
+        let total: f64 = lengths.iter().sum();
+        let mut breaks: Vec<f64> = vec![0f64];
+        let mut length: f64 = 0f64;
+        for (index, piece) in lengths.iter().enumerate() {
+            length += piece;
+            breaks.push(if total > 0f64 {
+                length / total
+            } else {
+                (index + 1) as f64 / lengths.len() as f64
+            });
+        }
+        if let Some(last) = breaks.last_mut() {
+            *last = 1f64;
+        }
+        Pieces { segments, breaks }
+    }
+
+    /// Returns the part of the motion between the fractions `t0` and `t1`, which lie within one segment.
+    fn get_segment(&self, t0: f64, t1: f64) -> (Vec<f64>, Vec<f64>) {
+        let middle: f64 = 0.5 * (t0 + t1);
+        let index: usize = self
+            .breaks
+            .iter()
+            .rposition(|fraction| *fraction <= middle)
+            .unwrap_or(0)
+            .min(self.segments.len() - 1);
+        let (begin, end) = &self.segments[index];
+        let width: f64 = self.breaks[index + 1] - self.breaks[index];
+        let point_at = |t: f64| -> Vec<f64> {
+            let s: f64 = if width > 0f64 {
+                (t - self.breaks[index]) / width
+            } else {
+                0f64
+            };
+            if s <= 1e-12 {
+                begin.clone()
+            } else if s >= 1f64 - 1e-12 {
+                end.clone()
+            } else {
+                begin
+                    .iter()
+                    .zip(end)
+                    .map(|(a, b)| a + (b - a) * s)
+                    .collect()
+            }
+        };
+        (point_at(t0), point_at(t1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::{DubinsStateSpace, EuclideanStateSpace, SE2StateSpace};
+    use std::f64::consts::PI;
+
+    /// Base in SE(2) with an arm of two joints, the arm counts half.
+    fn get_manipulator() -> CompoundStateSpace<5> {
+        let mut space: CompoundStateSpace<5> = CompoundStateSpace::new();
+        space
+            .add_subspace(
+                SE2StateSpace::new_box(1.0),
+                &SE2StateSpace::boundaries(0.0, 10.0, 0.0, 10.0),
+                1.0,
+            )
+            .unwrap();
+        space
+            .add_subspace(
+                EuclideanStateSpace::new_box(),
+                &Boundaries::from_limits([-1.5; 2], [1.5; 2]),
+                0.5,
+            )
+            .unwrap();
+        space
+    }
+
+    #[test]
+    fn test_add_subspace() {
+        let mut space: CompoundStateSpace<5> = get_manipulator();
+        assert_eq!(space.len(), 2);
+        assert_eq!(space.get_dimension(), 5);
+        let boundaries: Boundaries<5> = space.get_boundaries();
+        assert_eq!(boundaries.get_lower(), [0.0, 0.0, -PI, -1.5, -1.5]);
+        assert_eq!(boundaries.get_upper(), [10.0, 10.0, PI, 1.5, 1.5]);
+
+        let result = space.add_subspace(
+            EuclideanStateSpace::new_box::<1>(),
+            &Boundaries::default(),
+            1.0,
+        );
+        assert!(matches!(
+            result,
+            Err(PlanningError::InvalidStateSpace { .. })
+        ));
+        assert_eq!(space.len(), 2);
+
+        let mut space: CompoundStateSpace<5> = CompoundStateSpace::default();
+        assert!(space.is_empty());
+        let result = space.add_subspace(
+            EuclideanStateSpace::new_box::<1>(),
+            &Boundaries::default(),
+            0.0,
+        );
+        assert!(matches!(
+            result,
+            Err(PlanningError::InvalidStateSpace { .. })
+        ));
+    }
+
+    #[test]
+    fn test_distance_and_interpolate() {
+        let space: CompoundStateSpace<5> = get_manipulator();
+        let from: Point<5> = Point::from_coordinates([0.0, 0.0, 3.0, 0.0, 0.0]);
+        let to: Point<5> = Point::from_coordinates([3.0, 4.0, -3.0, 0.6, 0.8]);
+        let base: f64 = SE2StateSpace::new(1.0).distance(
+            &Point::from_coordinates([0.0, 0.0, 3.0]),
+            &Point::from_coordinates([3.0, 4.0, -3.0]),
+        );
+        assert!((space.distance(&from, &to) - (base + 0.5)).abs() < 1e-12);
+
+        let [x, y, theta, a, b] = space.interpolate(&from, &to, 0.5).get_coordinates();
+        assert_eq!((x, y, a, b), (1.5, 2.0, 0.3, 0.4));
+        assert!((theta - (-PI)).abs() < 1e-12);
+
+        let bounded: Point<5> = space.enforce_bounds(Point::from_coordinates([
+            1.0,
+            1.0,
+            2.0 * PI + 0.5,
+            1.0,
+            1.0,
+        ]));
+        assert!((bounded.get_coordinates()[2] - 0.5).abs() < 1e-12);
+    }
+
+    // Test that the closest key image is not farther away than the distance of the states.
+    #[test]
+    fn test_key_images() {
+        let space: CompoundStateSpace<5> = get_manipulator();
+        let a: Point<5> = Point::from_coordinates([0.0, 1.0, 3.0, 1.0, -1.0]);
+        let b: Point<5> = Point::from_coordinates([1.0, 0.0, -3.0, 0.0, 1.0]);
+        let key: Point<5> = Point::from_coordinates(space.get_key(&a));
+        let images: Vec<[f64; 5]> = space.get_key_images(space.get_key(&b));
+        assert_eq!(images.len(), 3);
+        let closest: f64 = images
+            .iter()
+            .map(|image| Point::from_coordinates(*image).euclidean_distance(&key))
+            .fold(f64::INFINITY, f64::min);
+        assert!(closest <= space.distance(&a, &b) + 1e-12);
+    }
+
+    // Test that the motion is split where the heading wraps, and the arm moves on at the split.
+    #[test]
+    fn test_segments() {
+        let space: CompoundStateSpace<5> = get_manipulator();
+        let from: Point<5> = Point::from_coordinates([0.0, 0.0, 0.0, 0.0, 0.0]);
+        let to: Point<5> = Point::from_coordinates([2.0, 0.0, 1.0, 1.0, 1.0]);
+        assert_eq!(space.get_segments(&from, &to), vec![(from, to)]);
+
+        let from: Point<5> = Point::from_coordinates([0.0, 0.0, PI - 0.1, 0.0, 0.0]);
+        let to: Point<5> = Point::from_coordinates([2.0, 0.0, -PI + 0.1, 1.0, 1.0]);
+        let segments: Vec<(Point<5>, Point<5>)> = space.get_segments(&from, &to);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].0, from);
+        assert_eq!(segments[1].1, to);
+        let [x, _, theta, a, _] = segments[0].1.get_coordinates();
+        assert!((x - 1.0).abs() < 1e-9);
+        assert!((a - 0.5).abs() < 1e-9);
+        assert_eq!(theta, PI);
+        assert_eq!(segments[1].0.get_coordinates()[2], -PI);
+        assert!(space.is_symmetric());
+    }
+
+    #[test]
+    fn test_asymmetric_sub_space() {
+        let mut space: CompoundStateSpace<4> = CompoundStateSpace::new();
+        space
+            .add_subspace(
+                DubinsStateSpace::new_box(1.0),
+                &SE2StateSpace::boundaries(0.0, 10.0, 0.0, 10.0),
+                1.0,
+            )
+            .unwrap();
+        space
+            .add_subspace(
+                EuclideanStateSpace::new_box::<1>(),
+                &Boundaries::from_limits([0.0], [1.0]),
+                1.0,
+            )
+            .unwrap();
+        assert!(!space.is_symmetric());
+        let from: Point<4> = Point::from_coordinates([0.0, 0.0, 0.0, 0.0]);
+        let to: Point<4> = Point::from_coordinates([0.0, 2.0, PI, 1.0]);
+        let segments: Vec<(Point<4>, Point<4>)> = space.get_segments(&from, &to);
+        assert!(segments.len() > 10);
+        assert_eq!(segments[segments.len() - 1].1, to);
+        assert!(segments
+            .windows(2)
+            .all(|pair| pair[0].1.get_coordinates()[3] <= pair[1].1.get_coordinates()[3]));
+    }
+}
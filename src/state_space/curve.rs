@@ -0,0 +1,110 @@
+use crate::space::Point;
+use crate::state_space::{wrap_angle, SE2StateSpace, StateSpace};
+
+/// Piece of a curve of a car-like robot.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum Turn {
+    Left,
+    Right,
+    Straight,
+}
+
+/// Sequence of arcs with the minimum turning radius and straight lines.
+/// The lengths are normalized by the turning radius, so a length of PI is a half circle. Negative lengths drive in reverse.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Curve {
+    pub pieces: Vec<(Turn, f64)>,
+}
+
+impl Curve {
+    pub fn new(pieces: Vec<(Turn, f64)>) -> Self {
+        Curve { pieces }
+    }
+
+    /// Normalized length of the curve. Reverse pieces count as positive.
+    pub fn length(&self) -> f64 {
+        self.pieces.iter().map(|(_, length)| length.abs()).sum()
+    }
+
+    /// Returns the state after driving the normalized length `s` along the curve from `from`.
+    pub fn state_at(&self, from: &Point<3>, turning_radius: f64, s: f64) -> Point<3> {
+        let [x0, y0, theta0] = from.get_coordinates();
+        let (mut x, mut y, mut theta): (f64, f64, f64) = (0f64, 0f64, theta0);
+        let mut remaining: f64 = s;
+        for (turn, length) in &self.pieces {
+            if remaining <= 0f64 {
+                break;
+            }
+            let v: f64 = length.abs().min(remaining).copysign(*length);
+            remaining -= v.abs();
+            match turn {
+                Turn::Left => {
+                    x += (theta + v).sin() - theta.sin();
+                    y += -(theta + v).cos() + theta.cos();
+                    theta += v;
+                }
+                Turn::Right => {
+                    x += -(theta - v).sin() + theta.sin();
+                    y += (theta - v).cos() - theta.cos();
+                    theta -= v;
+                }
+                Turn::Straight => {
+                    x += v * theta.cos();
+                    y += v * theta.sin();
+                }
+            }
+        }
+        Point::from_coordinates([
+            x0 + x * turning_radius,
+            y0 + y * turning_radius,
+            wrap_angle(theta),
+        ])
+    }
+
+    /// Approximates the curve by straight segments, which are at most `segment_length` long.
+    /// Segments are split where the heading passes from PI to -PI, like in `SE2StateSpace`.
+    pub fn get_segments(
+        &self,
+        from: &Point<3>,
+        to: &Point<3>,
+        turning_radius: f64,
+        segment_length: f64,
+    ) -> Vec<(Point<3>, Point<3>)> {
+        let length: f64 = self.length();
+        let steps: f64 = (length * turning_radius / segment_length).ceil();
+        let steps: usize = if steps.is_finite() && steps >= 1f64 {
+            steps as usize
+        } else {
+            1usize
+        };
+        let mut states: Vec<Point<3>> = (0..steps)
+            .map(|step| self.state_at(from, turning_radius, length * step as f64 / steps as f64))
+            .collect();
+        states[0] = *from;
+        states.push(*to);
+
+        let se2: SE2StateSpace = SE2StateSpace::default();
+        states
+            .windows(2)
+            .flat_map(|pair| se2.get_segments(&pair[0], &pair[1]))
+            .collect()
+    }
+}
+
+/// Transforms the goal into the frame of the start and scales it by the turning radius.
+/// Returns (x, y, phi), where phi is the change of the heading.
+pub(crate) fn to_local_frame(
+    from: &Point<3>,
+    to: &Point<3>,
+    turning_radius: f64,
+) -> (f64, f64, f64) {
+    let [x1, y1, theta1] = from.get_coordinates();
+    let [x2, y2, theta2] = to.get_coordinates();
+    let (dx, dy): (f64, f64) = (x2 - x1, y2 - y1);
+    let (sin, cos): (f64, f64) = theta1.sin_cos();
+    (
+        (cos * dx + sin * dy) / turning_radius,
+        (-sin * dx + cos * dy) / turning_radius,
+        theta2 - theta1,
+    )
+}
@@ -0,0 +1,392 @@
+use std::f64::consts::PI;
+
+use crate::space::Point;
+use crate::state_space::curve::{Curve, Turn};
+use crate::state_space::{wrap_angle, StateSpace};
+
+/// Tolerance below which start and goal are treated as the same state.
+const DUBINS_EPS: f64 = 1e-6;
+
+/// Tolerance of the square roots. Slightly negative values are rounding errors of a valid curve.
+const DUBINS_ZERO: f64 = -1e-7;
+
+/// Car-like robot that only drives forward with a minimum turning radius. The states are `Point<3>` with the coordinates (x, y, theta).
+/// The motion between two states is the shortest Dubins curve, which consists of three arcs or lines.
+/// The distance is the length of that curve. It is not symmetric, so planners follow the edges only in the direction they were created.
+///
+/// - turning_radius: Minimum turning radius of the robot
+/// - segment_length: Maximum length of the straight segments, that approximate the curves for collision checking
+///
+/// # Source / Credits
+/// Dubins, L. E. (1957), "On Curves of Minimal Length with a Constraint on Average Curvature, and with Prescribed Initial and Terminal Positions and Tangents", American Journal of Mathematics, 79 (3): 497–516, doi:10.2307/2372560
+#[derive(Debug, Copy, Clone)]
+pub struct DubinsStateSpace {
+    pub turning_radius: f64,
+    pub segment_length: f64,
+}
+
+impl Default for DubinsStateSpace {
+    fn default() -> Self {
+        DubinsStateSpace::new(1f64)
+    }
+}
+
+impl DubinsStateSpace {
+    /// The curves are approximated by segments of a tenth of the turning radius.
+    pub fn new(turning_radius: f64) -> Self {
+        DubinsStateSpace {
+            turning_radius,
+            segment_length: 0.1f64 * turning_radius,
+        }
+    }
+
+    pub fn new_box(turning_radius: f64) -> Box<dyn StateSpace<3>> {
+        Box::new(DubinsStateSpace::new(turning_radius))
+    }
+
+    /// Returns the shortest Dubins curve from `from` to `to`.
+    fn curve(&self, from: &Point<3>, to: &Point<3>) -> Curve {
+        let [x1, y1, theta1] = from.get_coordinates();
+        let [x2, y2, theta2] = to.get_coordinates();
+        let (dx, dy): (f64, f64) = (x2 - x1, y2 - y1);
+        let d: f64 = (dx * dx + dy * dy).sqrt() / self.turning_radius;
+        let direction: f64 = dy.atan2(dx);
+        shortest_curve(d, mod2pi(theta1 - direction), mod2pi(theta2 - direction))
+    }
+}
+
+impl StateSpace<3> for DubinsStateSpace {
+    fn distance(&self, from: &Point<3>, to: &Point<3>) -> f64 {
+        self.turning_radius * self.curve(from, to).length()
+    }
+
+    fn interpolate(&self, from: &Point<3>, to: &Point<3>, t: f64) -> Point<3> {
+        let curve: Curve = self.curve(from, to);
+        curve.state_at(from, self.turning_radius, t * curve.length())
+    }
+
+    fn enforce_bounds(&self, state: Point<3>) -> Point<3> {
+        let [x, y, theta] = state.get_coordinates();
+        Point::from_coordinates([x, y, wrap_angle(theta)])
+    }
+
+    /// Only the position. A curve is never shorter than the straight line between the positions.
+    fn get_key(&self, state: &Point<3>) -> [f64; 3] {
+        let [x, y, _] = state.get_coordinates();
+        [x, y, 0f64]
+    }
+
+    fn get_segments(&self, from: &Point<3>, to: &Point<3>) -> Vec<(Point<3>, Point<3>)> {
+        self.curve(from, to)
+            .get_segments(from, to, self.turning_radius, self.segment_length)
+    }
+
+    fn is_symmetric(&self) -> bool {
+        false
+    }
+}
+
+/// Maps an angle into [0, 2 * PI). Values that are close to a full turn are rounded to 0.
+fn mod2pi(angle: f64) -> f64 {
+    if angle < 0f64 && angle > DUBINS_ZERO {
+        return 0f64;
+    }
+    let wrapped: f64 = angle.rem_euclid(2f64 * PI);
+    if 2f64 * PI - wrapped < 0.5 * DUBINS_EPS {
+        0f64
+    } else {
+        wrapped
+    }
+}
+
+/// Returns the shortest of the six Dubins curves for the normalized distance `d` and the headings `alpha` and `beta` relative to the line from start to goal.
+fn shortest_curve(d: f64, alpha: f64, beta: f64) -> Curve {
+    if d < DUBINS_EPS && (alpha - beta).abs() < DUBINS_EPS {
+        return Curve::new(vec![
+            (Turn::Left, 0f64),
+            (Turn::Straight, d),
+            (Turn::Left, 0f64),
+        ]);
+    }
+    let (sa, ca): (f64, f64) = alpha.sin_cos();
+    let (sb, cb): (f64, f64) = beta.sin_cos();
+    let c: Configuration = Configuration {
+        d,
+        alpha,
+        beta,
+        sa,
+        ca,
+        sb,
+        cb,
+    };
+    let words: [Word; 6] = [lsl, rsr, rsl, lsr, rlr, lrl];
+    words
+        .iter()
+        .filter_map(|word| word(&c))
+        .min_by(|a, b| a.length().total_cmp(&b.length()))
+        .unwrap_or_else(|| Curve::new(vec![(Turn::Straight, f64::INFINITY)]))
+}
+
+/// Goal relative to the start with the sine and cosine of both headings.
+#[derive(Copy, Clone)]
+struct Configuration {
+    d: f64,
+    alpha: f64,
+    beta: f64,
+    sa: f64,
+    ca: f64,
+    sb: f64,
+    cb: f64,
+}
+
+/// One of the six Dubins curves. None, if it does not exist for the configuration.
+type Word = fn(&Configuration) -> Option<Curve>;
+
+fn lsl(c: &Configuration) -> Option<Curve> {
+    let Configuration {
+        d,
+        alpha,
+        beta,
+        sa,
+        ca,
+        sb,
+        cb,
+    } = *c;
+    let tmp: f64 = 2f64 + d * d - 2f64 * (ca * cb + sa * sb - d * (sa - sb));
+    if tmp < DUBINS_ZERO {
+        return None;
+    }
+    let theta: f64 = (cb - ca).atan2(d + sa - sb);
+    Some(Curve::new(vec![
+        (Turn::Left, mod2pi(-alpha + theta)),
+        (Turn::Straight, tmp.max(0f64).sqrt()),
+        (Turn::Left, mod2pi(beta - theta)),
+    ]))
+}
+
+fn rsr(c: &Configuration) -> Option<Curve> {
+    let Configuration {
+        d,
+        alpha,
+        beta,
+        sa,
+        ca,
+        sb,
+        cb,
+    } = *c;
+    let tmp: f64 = 2f64 + d * d - 2f64 * (ca * cb + sa * sb - d * (sb - sa));
+    if tmp < DUBINS_ZERO {
+        return None;
+    }
+    let theta: f64 = (ca - cb).atan2(d - sa + sb);
+    Some(Curve::new(vec![
+        (Turn::Right, mod2pi(alpha - theta)),
+        (Turn::Straight, tmp.max(0f64).sqrt()),
+        (Turn::Right, mod2pi(-beta + theta)),
+    ]))
+}
+
+fn rsl(c: &Configuration) -> Option<Curve> {
+    let Configuration {
+        d,
+        alpha,
+        beta,
+        sa,
+        ca,
+        sb,
+        cb,
+    } = *c;
+    let tmp: f64 = d * d - 2f64 + 2f64 * (ca * cb + sa * sb - d * (sa + sb));
+    if tmp < DUBINS_ZERO {
+        return None;
+    }
+    let p: f64 = tmp.max(0f64).sqrt();
+    let theta: f64 = (ca + cb).atan2(d - sa - sb) - 2f64.atan2(p);
+    Some(Curve::new(vec![
+        (Turn::Right, mod2pi(alpha - theta)),
+        (Turn::Straight, p),
+        (Turn::Left, mod2pi(beta - theta)),
+    ]))
+}
+
+fn lsr(c: &Configuration) -> Option<Curve> {
+    let Configuration {
+        d,
+        alpha,
+        beta,
+        sa,
+        ca,
+        sb,
+        cb,
+    } = *c;
+    let tmp: f64 = -2f64 + d * d + 2f64 * (ca * cb + sa * sb + d * (sa + sb));
+    if tmp < DUBINS_ZERO {
+        return None;
+    }
+    let p: f64 = tmp.max(0f64).sqrt();
+    let theta: f64 = (-ca - cb).atan2(d + sa + sb) - (-2f64).atan2(p);
+    Some(Curve::new(vec![
+        (Turn::Left, mod2pi(-alpha + theta)),
+        (Turn::Straight, p),
+        (Turn::Right, mod2pi(-beta + theta)),
+    ]))
+}
+
+fn rlr(c: &Configuration) -> Option<Curve> {
+    let Configuration {
+        d,
+        alpha,
+        beta,
+        sa,
+        ca,
+        sb,
+        cb,
+    } = *c;
+    let tmp: f64 = 0.125 * (6f64 - d * d + 2f64 * (ca * cb + sa * sb + d * (sa - sb)));
+    if tmp.abs() >= 1f64 {
+        return None;
+    }
+    let p: f64 = 2f64 * PI - tmp.acos();
+    let theta: f64 = (ca - cb).atan2(d - sa + sb);
+    let t: f64 = mod2pi(alpha - theta + 0.5 * p);
+    Some(Curve::new(vec![
+        (Turn::Right, t),
+        (Turn::Left, p),
+        (Turn::Right, mod2pi(alpha - beta - t + p)),
+    ]))
+}
+
+fn lrl(c: &Configuration) -> Option<Curve> {
+    let Configuration {
+        d,
+        alpha,
+        beta,
+        sa,
+        ca,
+        sb,
+        cb,
+    } = *c;
+    let tmp: f64 = 0.125 * (6f64 - d * d + 2f64 * (ca * cb + sa * sb - d * (sa - sb)));
+    if tmp.abs() >= 1f64 {
+        return None;
+    }
+    let p: f64 = 2f64 * PI - tmp.acos();
+    let theta: f64 = (-ca + cb).atan2(d + sa - sb);
+    let t: f64 = mod2pi(-alpha + theta + 0.5 * p);
+    Some(Curve::new(vec![
+        (Turn::Left, t),
+        (Turn::Right, p),
+        (Turn::Left, mod2pi(beta - alpha - t + p)),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::angle_difference;
+
+    /// Pairs of states in all directions, with different distances and headings.
+    fn get_pairs() -> Vec<(Point<3>, Point<3>)> {
+        let mut pairs: Vec<(Point<3>, Point<3>)> = Vec::new();
+        for (x, y) in [
+            (0.0, 0.0),
+            (3.0, 0.5),
+            (-0.4, 0.3),
+            (-2.0, -5.0),
+            (0.1, 1.9),
+        ] {
+            for theta1 in [-3.0, -1.2, 0.0, 0.7, 2.5] {
+                for theta2 in [-2.2, 0.0, 1.5, 3.1] {
+                    pairs.push((
+                        Point::from_coordinates([0.5, -0.5, theta1]),
+                        Point::from_coordinates([0.5 + x, -0.5 + y, theta2]),
+                    ));
+                }
+            }
+        }
+        pairs
+    }
+
+    fn is_close(a: &Point<3>, b: &Point<3>) -> bool {
+        let [x1, y1, theta1] = a.get_coordinates();
+        let [x2, y2, theta2] = b.get_coordinates();
+        (x1 - x2).abs() < 1e-6
+            && (y1 - y2).abs() < 1e-6
+            && angle_difference(theta1, theta2).abs() < 1e-6
+    }
+
+    #[test]
+    fn test_straight() {
+        let space: DubinsStateSpace = DubinsStateSpace::new(2.0);
+        let from: Point<3> = Point::from_coordinates([0.0, 0.0, 0.0]);
+        let to: Point<3> = Point::from_coordinates([3.0, 0.0, 0.0]);
+        assert!((space.distance(&from, &to) - 3.0).abs() < 1e-9);
+        assert!(is_close(
+            &space.interpolate(&from, &to, 0.5),
+            &Point::from_coordinates([1.5, 0.0, 0.0])
+        ));
+        assert!(space.distance(&from, &from).abs() < 1e-9);
+    }
+
+    // Test that a half circle to the left has the length PI * turning_radius.
+    #[test]
+    fn test_half_circle() {
+        let space: DubinsStateSpace = DubinsStateSpace::new(2.0);
+        let from: Point<3> = Point::from_coordinates([0.0, 0.0, 0.0]);
+        let to: Point<3> = Point::from_coordinates([0.0, 4.0, PI]);
+        assert!((space.distance(&from, &to) - 2.0 * PI).abs() < 1e-9);
+        assert!(is_close(
+            &space.interpolate(&from, &to, 0.5),
+            &Point::from_coordinates([2.0, 2.0, 0.5 * PI])
+        ));
+    }
+
+    // Test that driving backwards needs a detour, because the car only drives forward.
+    #[test]
+    fn test_asymmetric() {
+        let space: Box<dyn StateSpace<3>> = DubinsStateSpace::new_box(1.0);
+        let a: Point<3> = Point::from_coordinates([0.0, 0.0, 0.0]);
+        let b: Point<3> = Point::from_coordinates([1.0, 0.0, 0.0]);
+        assert!((space.distance(&a, &b) - 1.0).abs() < 1e-9);
+        assert!(space.distance(&b, &a) > 2.0 * PI);
+        assert!(!space.is_symmetric());
+    }
+
+    // Test that the curves end at the goal and are never shorter than the straight line.
+    #[test]
+    fn test_reaches_goal() {
+        let space: DubinsStateSpace = DubinsStateSpace::new(1.5);
+        for (from, to) in get_pairs() {
+            let distance: f64 = space.distance(&from, &to);
+            assert!(distance.is_finite());
+            let [x1, y1, _] = from.get_coordinates();
+            let [x2, y2, _] = to.get_coordinates();
+            assert!(distance >= ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt() - 1e-9);
+            assert!(is_close(&space.interpolate(&from, &to, 0.0), &from));
+            assert!(is_close(&space.interpolate(&from, &to, 1.0), &to));
+        }
+    }
+
+    // Test that the segments are connected, short, and go from start to goal.
+    #[test]
+    fn test_segments() {
+        let space: DubinsStateSpace = DubinsStateSpace::new(1.0);
+        for (from, to) in get_pairs() {
+            let segments: Vec<(Point<3>, Point<3>)> = space.get_segments(&from, &to);
+            assert_eq!(segments[0].0, from);
+            assert_eq!(segments[segments.len() - 1].1, to);
+            for (begin, end) in &segments {
+                let [x1, y1, _] = begin.get_coordinates();
+                let [x2, y2, _] = end.get_coordinates();
+                assert!(
+                    ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt() <= space.segment_length + 1e-9
+                );
+            }
+            for pair in segments.windows(2) {
+                let [x1, y1, _] = pair[0].1.get_coordinates();
+                let [x2, y2, _] = pair[1].0.get_coordinates();
+                assert!((x1 - x2).abs() < 1e-12 && (y1 - y2).abs() < 1e-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use std::f64::consts::PI;
+
+use crate::boundaries::{Boundaries, Topology};
+use crate::space::Point;
+use crate::state_space::StateSpace;
+
+/// Joint angles and positions of a robot arm, or any other configuration space with a topology per axis.
+/// The limits and the topology of every axis are taken from the boundaries.
+///
+/// - Linear: Straight motion without limits
+/// - Bounded: Straight motion, states are clamped into the limits
+/// - Circular: Continuous revolute joint. States wrap around into [lower, upper), and motions take the short way around.
+///
+/// The distance is the Euclidean distance of the differences of all axes.
+#[derive(Debug, Copy, Clone)]
+pub struct JointStateSpace<const N: usize = 2> {
+    lower: [f64; N],
+    upper: [f64; N],
+    topology: [Topology; N],
+}
+
+impl<const N: usize> JointStateSpace<N> {
+    pub fn new(boundaries: &Boundaries<N>) -> Self {
+        JointStateSpace {
+            lower: boundaries.get_lower(),
+            upper: boundaries.get_upper(),
+            topology: boundaries.get_topology(),
+        }
+    }
+
+    pub fn new_box(boundaries: &Boundaries<N>) -> Box<dyn StateSpace<N>> {
+        Box::new(JointStateSpace::new(boundaries))
+    }
+
+    /// Returns the signed difference of every axis from `from` to `to`.
+    fn get_differences(&self, from: &Point<N>, to: &Point<N>) -> [f64; N] {
+        let (from, to) = (from.get_coordinates(), to.get_coordinates());
+        std::array::from_fn(|axis| {
+            self.topology[axis].difference(from[axis], to[axis], self.lower[axis], self.upper[axis])
+        })
+    }
+
+    /// Returns the length of the circle of every circular axis.
+    fn get_periods(&self) -> Vec<(usize, f64)> {
+        (0..N)
+            .filter(|axis| self.topology[*axis] == Topology::Circular)
+            .map(|axis| (axis, self.upper[axis] - self.lower[axis]))
+            .filter(|(_, period)| *period > 0f64)
+            .collect()
+    }
+}
+
+impl<const N: usize> StateSpace<N> for JointStateSpace<N> {
+    fn distance(&self, from: &Point<N>, to: &Point<N>) -> f64 {
+        self.get_differences(from, to)
+            .iter()
+            .map(|difference| difference * difference)
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    fn interpolate(&self, from: &Point<N>, to: &Point<N>, t: f64) -> Point<N> {
+        let differences: [f64; N] = self.get_differences(from, to);
+        let mut coordinates: [f64; N] = from.get_coordinates();
+        for (coordinate, difference) in coordinates.iter_mut().zip(differences) {
+            *coordinate += difference * t;
+        }
+        self.enforce_bounds(Point::from_coordinates(coordinates))
+    }
+
+    fn enforce_bounds(&self, state: Point<N>) -> Point<N> {
+        let mut coordinates: [f64; N] = state.get_coordinates();
+        for (axis, coordinate) in coordinates.iter_mut().enumerate() {
+            *coordinate =
+                self.topology[axis].enforce(*coordinate, self.lower[axis], self.upper[axis]);
+        }
+        Point::from_coordinates(coordinates)
+    }
+
+    fn get_key(&self, state: &Point<N>) -> [f64; N] {
+        self.enforce_bounds(*state).get_coordinates()
+    }
+
+    /// Shifts every circular axis by a full turn in both directions, in all combinations.
+    fn get_key_images(&self, key: [f64; N]) -> Vec<[f64; N]> {
+        let mut images: Vec<[f64; N]> = vec![key];
+        for (axis, period) in self.get_periods() {
+            images = images
+                .iter()
+                .flat_map(|image| {
+                    [0f64, -period, period].map(|shift| {
+                        let mut shifted: [f64; N] = *image;
+                        shifted[axis] += shift;
+                        shifted
+                    })
+                })
+                .collect();
+        }
+        images
+    }
+
+    /// Splits the motion wherever a circular axis passes from the upper to the lower limit, so no segment turns the long way around.
+    fn get_segments(&self, from: &Point<N>, to: &Point<N>) -> Vec<(Point<N>, Point<N>)> {
+        let start: [f64; N] = self.enforce_bounds(*from).get_coordinates();
+        let differences: [f64; N] = self.get_differences(from, to);
+        let position = |t: f64| -> [f64; N] {
+            std::array::from_fn(|axis| start[axis] + differences[axis] * t)
+        };
+
+        let periods: Vec<(usize, f64)> = self.get_periods();
+        let mut breaks: Vec<f64> = vec![0f64, 1f64];
+        for (axis, period) in &periods {
+            if differences[*axis] == 0f64 {
+                continue;
+            }
+            let end: f64 = start[*axis] + differences[*axis];
+            let (low, high): (f64, f64) = (start[*axis].min(end), start[*axis].max(end));
+            let first: f64 = ((low - self.lower[*axis]) / period).floor() + 1f64;
+            let mut seam: f64 = self.lower[*axis] + first * period;
+            while seam < high {
+                breaks.push((seam - start[*axis]) / differences[*axis]);
+                seam += period;
+            }
+        }
+        breaks.sort_by(f64::total_cmp);
+        breaks.dedup_by(|a, b| (*a - *b).abs() < 1e-12);
+
+        let mut segments: Vec<(Point<N>, Point<N>)> = breaks
+            .windows(2)
+            .map(|interval| {
+                let (mut begin, mut end): ([f64; N], [f64; N]) =
+                    (position(interval[0]), position(interval[1]));
+                let middle: [f64; N] = position(0.5 * (interval[0] + interval[1]));
+                for (axis, period) in &periods {
+                    let shift: f64 =
+                        ((middle[*axis] - self.lower[*axis]) / period).floor() * period;
+                    begin[*axis] -= shift;
+                    end[*axis] -= shift;
+                }
+                (Point::from_coordinates(begin), Point::from_coordinates(end))
+            })
+            .collect();
+        if let Some(first) = segments.first_mut() {
+            first.0 = *from;
+        }
+        if let Some(last) = segments.last_mut() {
+            last.1 = *to;
+        }
+        segments
+    }
+}
+
+/// Rotation in the plane, e.g. a continuous revolute joint. The states are `Point<1>` with an angle in [-PI, PI).
+#[derive(Debug, Copy, Clone)]
+pub struct SO2StateSpace {
+    joint: JointStateSpace<1>,
+}
+
+impl Default for SO2StateSpace {
+    fn default() -> Self {
+        SO2StateSpace {
+            joint: JointStateSpace::new(&SO2StateSpace::boundaries()),
+        }
+    }
+}
+
+impl SO2StateSpace {
+    pub fn new_box() -> Box<dyn StateSpace<1>> {
+        Box::new(SO2StateSpace::default())
+    }
+
+    /// Returns circular boundaries from -PI to PI.
+    pub fn boundaries() -> Boundaries<1> {
+        let mut boundaries: Boundaries<1> = Boundaries::from_limits([-PI], [PI]);
+        boundaries.set_topology([Topology::Circular]);
+        boundaries
+    }
+}
+
+impl StateSpace<1> for SO2StateSpace {
+    fn distance(&self, from: &Point<1>, to: &Point<1>) -> f64 {
+        self.joint.distance(from, to)
+    }
+
+    fn interpolate(&self, from: &Point<1>, to: &Point<1>, t: f64) -> Point<1> {
+        self.joint.interpolate(from, to, t)
+    }
+
+    fn enforce_bounds(&self, state: Point<1>) -> Point<1> {
+        self.joint.enforce_bounds(state)
+    }
+
+    fn get_key(&self, state: &Point<1>) -> [f64; 1] {
+        self.joint.get_key(state)
+    }
+
+    fn get_key_images(&self, key: [f64; 1]) -> Vec<[f64; 1]> {
+        self.joint.get_key_images(key)
+    }
+
+    fn get_segments(&self, from: &Point<1>, to: &Point<1>) -> Vec<(Point<1>, Point<1>)> {
+        self.joint.get_segments(from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Arm with a prismatic joint in [0, 1] and two continuous revolute joints.
+    fn get_arm() -> JointStateSpace<3> {
+        let mut boundaries: Boundaries<3> = Boundaries::from_limits([0.0, -PI, -PI], [1.0, PI, PI]);
+        boundaries.set_topology([Topology::Bounded, Topology::Circular, Topology::Circular]);
+        JointStateSpace::new(&boundaries)
+    }
+
+    #[test]
+    fn test_distance() {
+        let space: JointStateSpace<3> = get_arm();
+        let from: Point<3> = Point::from_coordinates([0.0, 3.0, 0.0]);
+        let to: Point<3> = Point::from_coordinates([0.0, -3.0, 0.0]);
+        assert!((space.distance(&from, &to) - (2.0 * PI - 6.0)).abs() < 1e-12);
+        assert!((space.distance(&to, &from) - (2.0 * PI - 6.0)).abs() < 1e-12);
+        let to: Point<3> = Point::from_coordinates([0.3, 0.0, 0.4]);
+        assert!((space.distance(&Point::from_coordinates([0.0; 3]), &to) - 0.5).abs() < 1e-12);
+    }
+
+    // Test that the joints turn the short way around and stay wrapped.
+    #[test]
+    fn test_interpolate() {
+        let space: JointStateSpace<3> = get_arm();
+        let from: Point<3> = Point::from_coordinates([0.0, 3.0, 1.0]);
+        let to: Point<3> = Point::from_coordinates([1.0, -3.0, 2.0]);
+        let [d, a, b] = space.interpolate(&from, &to, 0.5).get_coordinates();
+        assert_eq!(d, 0.5);
+        assert!((a - (-PI)).abs() < 1e-12);
+        assert_eq!(b, 1.5);
+        assert!(space.distance(&space.interpolate(&from, &to, 1.0), &to) < 1e-12);
+    }
+
+    #[test]
+    fn test_enforce_bounds() {
+        let space: JointStateSpace<3> = get_arm();
+        let [d, a, b] = space
+            .enforce_bounds(Point::from_coordinates([2.0, PI, -3.0 * PI + 0.5]))
+            .get_coordinates();
+        assert_eq!(d, 1.0);
+        assert_eq!(a, -PI);
+        assert!((b - (-PI + 0.5)).abs() < 1e-12);
+    }
+
+    // Test that one of the key images is exactly as far away as the states.
+    #[test]
+    fn test_key_images() {
+        let space: JointStateSpace<3> = get_arm();
+        let a: Point<3> = Point::from_coordinates([0.2, 3.0, -3.0]);
+        let b: Point<3> = Point::from_coordinates([0.7, -3.0, 3.0]);
+        let images: Vec<[f64; 3]> = space.get_key_images(space.get_key(&b));
+        assert_eq!(images.len(), 9);
+        let key: Point<3> = Point::from_coordinates(space.get_key(&a));
+        let closest: f64 = images
+            .iter()
+            .map(|image| Point::from_coordinates(*image).euclidean_distance(&key))
+            .fold(f64::INFINITY, f64::min);
+        assert!((closest - space.distance(&a, &b)).abs() < 1e-12);
+    }
+
+    // Test that a motion is split at every seam of the circular joints.
+    #[test]
+    fn test_segments() {
+        let space: JointStateSpace<3> = get_arm();
+        let from: Point<3> = Point::from_coordinates([0.0, 0.0, 0.0]);
+        let to: Point<3> = Point::from_coordinates([1.0, 1.0, -1.0]);
+        assert_eq!(space.get_segments(&from, &to), vec![(from, to)]);
+
+        let from: Point<3> = Point::from_coordinates([0.0, PI - 0.1, -PI + 0.3]);
+        let to: Point<3> = Point::from_coordinates([1.0, -PI + 0.1, PI - 0.1]);
+        let segments: Vec<(Point<3>, Point<3>)> = space.get_segments(&from, &to);
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[0].0, from);
+        assert_eq!(segments[2].1, to);
+        let [d, a, _] = segments[0].1.get_coordinates();
+        assert!((d - 0.5).abs() < 1e-9);
+        assert!((a - PI).abs() < 1e-9);
+        let [d, a, b] = segments[1].1.get_coordinates();
+        assert!((d - 0.75).abs() < 1e-9);
+        assert!((a - (-PI + 0.05)).abs() < 1e-9);
+        assert!((b - (-PI)).abs() < 1e-9);
+        assert!((segments[2].0.get_coordinates()[2] - PI).abs() < 1e-9);
+        for (begin, end) in &segments {
+            assert!(begin.euclidean_distance(end) <= space.distance(&from, &to) + 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_so2() {
+        let space: Box<dyn StateSpace<1>> = SO2StateSpace::new_box();
+        let from: Point<1> = Point::from_coordinates([3.0]);
+        let to: Point<1> = Point::from_coordinates([-3.0]);
+        assert!((space.distance(&from, &to) - (2.0 * PI - 6.0)).abs() < 1e-12);
+        assert_eq!(space.get_segments(&from, &to).len(), 2);
+        assert_eq!(space.get_key_images([1.0]).len(), 3);
+        assert!(space.is_symmetric());
+        assert_eq!(
+            SO2StateSpace::boundaries().get_topology(),
+            [Topology::Circular]
+        );
+    }
+}
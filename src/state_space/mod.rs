@@ -0,0 +1,184 @@
+use std::f64::consts::PI;
+
+use crate::collision_checker::CollisionChecker;
+use crate::space::Point;
+
+mod compound;
+mod curve;
+mod dubins;
+mod joint;
+mod reeds_shepp;
+mod se2;
+
+pub use compound::CompoundStateSpace;
+pub use dubins::DubinsStateSpace;
+pub use joint::{JointStateSpace, SO2StateSpace};
+pub use reeds_shepp::ReedsSheppStateSpace;
+pub use se2::SE2StateSpace;
+
+/// Describes how the states of a configuration space are connected: the distance between two states and the motion from one to the other.
+/// PRM, PRM*, RRT, RRT* and RRT-Connect use it for nearest neighbor queries, edge weights, steering and edge collision checks.
+/// The dimension defaults to 2D. State spaces for other configuration spaces implement `StateSpace<N>`.
+pub trait StateSpace<const N: usize = 2> {
+    /// Returns the length of the motion from `from` to `to`.
+    fn distance(&self, from: &Point<N>, to: &Point<N>) -> f64;
+
+    /// Returns the state at fraction `t` of the motion from `from` to `to`. `0.0` returns `from`, `1.0` returns `to`.
+    fn interpolate(&self, from: &Point<N>, to: &Point<N>, t: f64) -> Point<N>;
+
+    /// Returns the state in its canonical form, e.g. with wrapped angles. The default returns the state unchanged.
+    fn enforce_bounds(&self, state: Point<N>) -> Point<N> {
+        state
+    }
+
+    /// Returns the coordinates of the state in the nearest neighbor structure.
+    /// The Euclidean distance between the keys of two states, or the closest of their images, must not be larger than the distance between the states.
+    /// The default are the coordinates of the state.
+    fn get_key(&self, state: &Point<N>) -> [f64; N] {
+        state.get_coordinates()
+    }
+
+    /// Returns all keys that describe the same state as the given key, including the key itself.
+    /// E.g. the key shifted by a full turn on an angle axis. The default is only the key itself.
+    fn get_key_images(&self, key: [f64; N]) -> Vec<[f64; N]> {
+        vec![key]
+    }
+
+    /// Returns the motion from `from` to `to` as straight segments, which are checked with `is_edge_colliding`.
+    /// The default is the straight segment from `from` to `to`.
+    fn get_segments(&self, from: &Point<N>, to: &Point<N>) -> Vec<(Point<N>, Point<N>)> {
+        vec![(*from, *to)]
+    }
+
+    /// Checks every segment of the motion and stops at the first collision.
+    fn is_motion_colliding(
+        &self,
+        collision_checker: &dyn CollisionChecker<N>,
+        from: &Point<N>,
+        to: &Point<N>,
+    ) -> bool {
+        self.get_segments(from, to)
+            .iter()
+            .any(|(begin, end)| collision_checker.is_edge_colliding(begin, end))
+    }
+
+    /// Returns true, if the distance and the motion are the same in both directions.
+    /// Planners only follow the edges of asymmetric state spaces, e.g. `DubinsStateSpace`, in the direction they were created.
+    fn is_symmetric(&self) -> bool {
+        true
+    }
+
+    /// Returns the path with `steps` interpolated states between each pair of waypoints, e.g. to follow a solution path with curves.
+    fn interpolate_path(&self, path: &[Point<N>], steps: usize) -> Vec<Point<N>> {
+        let mut result: Vec<Point<N>> = path.iter().take(1).copied().collect();
+        for pair in path.windows(2) {
+            for step in 1..=steps {
+                let t: f64 = step as f64 / (steps + 1) as f64;
+                result.push(self.interpolate(&pair[0], &pair[1], t));
+            }
+            result.push(pair[1]);
+        }
+        result
+    }
+}
+
+/// Straight motions and the Euclidean distance. Default state space of the planners. Works in any dimension.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct EuclideanStateSpace {}
+
+impl EuclideanStateSpace {
+    pub fn new_box<const N: usize>() -> Box<dyn StateSpace<N>> {
+        Box::new(EuclideanStateSpace {})
+    }
+}
+
+impl<const N: usize> StateSpace<N> for EuclideanStateSpace {
+    fn distance(&self, from: &Point<N>, to: &Point<N>) -> f64 {
+        from.euclidean_distance(to)
+    }
+
+    fn interpolate(&self, from: &Point<N>, to: &Point<N>, t: f64) -> Point<N> {
+        from.interpolate(to, t)
+    }
+}
+
+/// Wraps an angle into [-PI, PI).
+pub fn wrap_angle(angle: f64) -> f64 {
+    (angle + PI).rem_euclid(2f64 * PI) - PI
+}
+
+/// Returns the signed angle of the shortest rotation from `from` to `to`, in [-PI, PI).
+pub fn angle_difference(from: f64, to: f64) -> f64 {
+    wrap_angle(to - from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision_checker::NaiveCollisionChecker;
+
+    #[test]
+    fn test_euclidean() {
+        let space: Box<dyn StateSpace<3>> = EuclideanStateSpace::new_box();
+        let from: Point<3> = Point::from_coordinates([0.0, 0.0, 0.0]);
+        let to: Point<3> = Point::from_coordinates([1.0, 2.0, 2.0]);
+        assert_eq!(space.distance(&from, &to), 3.0);
+        assert_eq!(
+            space.interpolate(&from, &to, 0.5),
+            Point::from_coordinates([0.5, 1.0, 1.0])
+        );
+        assert_eq!(space.enforce_bounds(to), to);
+        assert_eq!(
+            space.get_key_images(space.get_key(&to)),
+            vec![[1.0, 2.0, 2.0]]
+        );
+        assert_eq!(space.get_segments(&from, &to), vec![(from, to)]);
+        assert!(!space.is_motion_colliding(
+            &NaiveCollisionChecker {
+                phantom: std::marker::PhantomData
+            },
+            &from,
+            &to
+        ));
+    }
+
+    #[test]
+    fn test_interpolate_path() {
+        let space: Box<dyn StateSpace> = EuclideanStateSpace::new_box();
+        let path: Vec<Point> = vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+        ];
+        let result: Vec<Point> = space.interpolate_path(&path, 1);
+        assert_eq!(
+            result,
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(1.0, 0.0),
+                Point::new(2.0, 0.0),
+                Point::new(2.0, 1.0),
+                Point::new(2.0, 2.0)
+            ]
+        );
+        assert!(space.is_symmetric());
+        assert!(space.interpolate_path(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn test_wrap_angle() {
+        assert_eq!(wrap_angle(0.5), 0.5);
+        assert_eq!(wrap_angle(PI), -PI);
+        assert_eq!(wrap_angle(-PI), -PI);
+        assert!((wrap_angle(3f64 * PI + 0.5) - (-PI + 0.5)).abs() < 1e-12);
+        assert!((wrap_angle(-2f64 * PI - 0.5) - (-0.5)).abs() < 1e-12);
+    }
+
+    // Test that the difference takes the short way around.
+    #[test]
+    fn test_angle_difference() {
+        assert!((angle_difference(0.1, 0.3) - 0.2).abs() < 1e-12);
+        assert!((angle_difference(3.0, -3.0) - (2f64 * PI - 6.0)).abs() < 1e-12);
+        assert!((angle_difference(-3.0, 3.0) - (6.0 - 2f64 * PI)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,473 @@
+use std::f64::consts::PI;
+
+use crate::space::Point;
+use crate::state_space::curve::{to_local_frame, Curve, Turn};
+use crate::state_space::{wrap_angle, StateSpace};
+
+/// Tolerance of the signs of the lengths of the pieces.
+const ZERO: f64 = 10f64 * f64::EPSILON;
+
+use Turn::{Left as L, Right as R, Straight as S};
+
+/// Car-like robot that drives forward and in reverse with a minimum turning radius. The states are `Point<3>` with the coordinates (x, y, theta).
+/// The motion between two states is the shortest Reeds-Shepp curve, which consists of up to five arcs or lines and may change the direction of travel.
+/// The distance is the length of that curve. It is symmetric.
+///
+/// - turning_radius: Minimum turning radius of the robot
+/// - segment_length: Maximum length of the straight segments, that approximate the curves for collision checking
+///
+/// # Source / Credits
+/// Reeds, J. A.; Shepp, L. A. (1990), "Optimal paths for a car that goes both forwards and backwards", Pacific Journal of Mathematics, 145 (2): 367–393, doi:10.2140/pjm.1990.145.367
+#[derive(Debug, Copy, Clone)]
+pub struct ReedsSheppStateSpace {
+    pub turning_radius: f64,
+    pub segment_length: f64,
+}
+
+impl Default for ReedsSheppStateSpace {
+    fn default() -> Self {
+        ReedsSheppStateSpace::new(1f64)
+    }
+}
+
+impl ReedsSheppStateSpace {
+    /// The curves are approximated by segments of a tenth of the turning radius.
+    pub fn new(turning_radius: f64) -> Self {
+        ReedsSheppStateSpace {
+            turning_radius,
+            segment_length: 0.1f64 * turning_radius,
+        }
+    }
+
+    pub fn new_box(turning_radius: f64) -> Box<dyn StateSpace<3>> {
+        Box::new(ReedsSheppStateSpace::new(turning_radius))
+    }
+
+    /// Returns the shortest Reeds-Shepp curve from `from` to `to`.
+    fn curve(&self, from: &Point<3>, to: &Point<3>) -> Curve {
+        let (x, y, phi) = to_local_frame(from, to, self.turning_radius);
+        let mut shortest: Shortest = Shortest {
+            curve: Curve::new(vec![(S, f64::INFINITY)]),
+        };
+        csc(x, y, phi, &mut shortest);
+        ccc(x, y, phi, &mut shortest);
+        cccc(x, y, phi, &mut shortest);
+        ccsc(x, y, phi, &mut shortest);
+        ccscc(x, y, phi, &mut shortest);
+        shortest.curve
+    }
+}
+
+impl StateSpace<3> for ReedsSheppStateSpace {
+    fn distance(&self, from: &Point<3>, to: &Point<3>) -> f64 {
+        self.turning_radius * self.curve(from, to).length()
+    }
+
+    fn interpolate(&self, from: &Point<3>, to: &Point<3>, t: f64) -> Point<3> {
+        let curve: Curve = self.curve(from, to);
+        curve.state_at(from, self.turning_radius, t * curve.length())
+    }
+
+    fn enforce_bounds(&self, state: Point<3>) -> Point<3> {
+        let [x, y, theta] = state.get_coordinates();
+        Point::from_coordinates([x, y, wrap_angle(theta)])
+    }
+
+    /// Only the position. A curve is never shorter than the straight line between the positions.
+    fn get_key(&self, state: &Point<3>) -> [f64; 3] {
+        let [x, y, _] = state.get_coordinates();
+        [x, y, 0f64]
+    }
+
+    fn get_segments(&self, from: &Point<3>, to: &Point<3>) -> Vec<(Point<3>, Point<3>)> {
+        self.curve(from, to)
+            .get_segments(from, to, self.turning_radius, self.segment_length)
+    }
+}
+
+/// Keeps the shortest of the candidate curves.
+struct Shortest {
+    curve: Curve,
+}
+
+impl Shortest {
+    fn offer(&mut self, turns: &[Turn], lengths: &[f64]) {
+        let curve: Curve = Curve::new(turns.iter().copied().zip(lengths.iter().copied()).collect());
+        if curve.length() < self.curve.length() {
+            self.curve = curve;
+        }
+    }
+}
+
+/// Maps an angle into [-PI, PI].
+fn mod2pi(angle: f64) -> f64 {
+    let v: f64 = angle % (2f64 * PI);
+    if v < -PI {
+        v + 2f64 * PI
+    } else if v > PI {
+        v - 2f64 * PI
+    } else {
+        v
+    }
+}
+
+/// Returns the polar coordinates (r, theta) of (x, y).
+fn polar(x: f64, y: f64) -> (f64, f64) {
+    ((x * x + y * y).sqrt(), y.atan2(x))
+}
+
+fn tau_omega(u: f64, v: f64, xi: f64, eta: f64, phi: f64) -> (f64, f64) {
+    let delta: f64 = mod2pi(u - v);
+    let a: f64 = u.sin() - delta.sin();
+    let b: f64 = u.cos() - delta.cos() - 1f64;
+    let t1: f64 = (eta * a - xi * b).atan2(xi * a + eta * b);
+    let t2: f64 = 2f64 * (delta.cos() - v.cos() - u.cos()) + 3f64;
+    let tau: f64 = if t2 < 0f64 {
+        mod2pi(t1 + PI)
+    } else {
+        mod2pi(t1)
+    };
+    (tau, mod2pi(tau - u + v - phi))
+}
+
+/// Formula 8.1 of Reeds and Shepp.
+fn lp_sp_lp(x: f64, y: f64, phi: f64) -> Option<(f64, f64, f64)> {
+    let (u, t) = polar(x - phi.sin(), y - 1f64 + phi.cos());
+    if t >= -ZERO {
+        let v: f64 = mod2pi(phi - t);
+        if v >= -ZERO {
+            return Some((t, u, v));
+        }
+    }
+    None
+}
+
+/// Formula 8.2 of Reeds and Shepp.
+fn lp_sp_rp(x: f64, y: f64, phi: f64) -> Option<(f64, f64, f64)> {
+    let (u1, t1) = polar(x + phi.sin(), y - 1f64 - phi.cos());
+    let u1: f64 = u1 * u1;
+    if u1 < 4f64 {
+        return None;
+    }
+    let u: f64 = (u1 - 4f64).sqrt();
+    let t: f64 = mod2pi(t1 + 2f64.atan2(u));
+    let v: f64 = mod2pi(t - phi);
+    (t >= -ZERO && v >= -ZERO).then_some((t, u, v))
+}
+
+fn csc(x: f64, y: f64, phi: f64, shortest: &mut Shortest) {
+    for (formula, turns, reflected) in [
+        (lp_sp_lp as Formula, [L, S, L], [R, S, R]),
+        (lp_sp_rp as Formula, [L, S, R], [R, S, L]),
+    ] {
+        if let Some((t, u, v)) = formula(x, y, phi) {
+            shortest.offer(&turns, &[t, u, v]);
+        }
+        // timeflip
+        if let Some((t, u, v)) = formula(-x, y, -phi) {
+            shortest.offer(&turns, &[-t, -u, -v]);
+        }
+        // reflect
+        if let Some((t, u, v)) = formula(x, -y, -phi) {
+            shortest.offer(&reflected, &[t, u, v]);
+        }
+        // timeflip and reflect
+        if let Some((t, u, v)) = formula(-x, -y, phi) {
+            shortest.offer(&reflected, &[-t, -u, -v]);
+        }
+    }
+}
+
+/// Formula 8.3 of Reeds and Shepp, with the typo of the paper fixed.
+fn lp_rm_l(x: f64, y: f64, phi: f64) -> Option<(f64, f64, f64)> {
+    let (u1, theta) = polar(x - phi.sin(), y - 1f64 + phi.cos());
+    if u1 > 4f64 {
+        return None;
+    }
+    let u: f64 = -2f64 * (0.25 * u1).asin();
+    let t: f64 = mod2pi(theta + 0.5 * u + PI);
+    let v: f64 = mod2pi(phi - t + u);
+    (t >= -ZERO && u <= ZERO).then_some((t, u, v))
+}
+
+fn ccc(x: f64, y: f64, phi: f64, shortest: &mut Shortest) {
+    let (lrl, rlr): ([Turn; 3], [Turn; 3]) = ([L, R, L], [R, L, R]);
+    if let Some((t, u, v)) = lp_rm_l(x, y, phi) {
+        shortest.offer(&lrl, &[t, u, v]);
+    }
+    if let Some((t, u, v)) = lp_rm_l(-x, y, -phi) {
+        shortest.offer(&lrl, &[-t, -u, -v]);
+    }
+    if let Some((t, u, v)) = lp_rm_l(x, -y, -phi) {
+        shortest.offer(&rlr, &[t, u, v]);
+    }
+    if let Some((t, u, v)) = lp_rm_l(-x, -y, phi) {
+        shortest.offer(&rlr, &[-t, -u, -v]);
+    }
+
+    // backwards
+    let xb: f64 = x * phi.cos() + y * phi.sin();
+    let yb: f64 = x * phi.sin() - y * phi.cos();
+    if let Some((t, u, v)) = lp_rm_l(xb, yb, phi) {
+        shortest.offer(&lrl, &[v, u, t]);
+    }
+    if let Some((t, u, v)) = lp_rm_l(-xb, yb, -phi) {
+        shortest.offer(&lrl, &[-v, -u, -t]);
+    }
+    if let Some((t, u, v)) = lp_rm_l(xb, -yb, -phi) {
+        shortest.offer(&rlr, &[v, u, t]);
+    }
+    if let Some((t, u, v)) = lp_rm_l(-xb, -yb, phi) {
+        shortest.offer(&rlr, &[-v, -u, -t]);
+    }
+}
+
+/// Formula 8.7 of Reeds and Shepp.
+fn lp_rup_lum_rm(x: f64, y: f64, phi: f64) -> Option<(f64, f64, f64)> {
+    let xi: f64 = x + phi.sin();
+    let eta: f64 = y - 1f64 - phi.cos();
+    let rho: f64 = 0.25 * (2f64 + (xi * xi + eta * eta).sqrt());
+    if rho > 1f64 {
+        return None;
+    }
+    let u: f64 = rho.acos();
+    let (t, v) = tau_omega(u, -u, xi, eta, phi);
+    (t >= -ZERO && v <= ZERO).then_some((t, u, v))
+}
+
+/// Formula 8.8 of Reeds and Shepp.
+fn lp_rum_lum_rp(x: f64, y: f64, phi: f64) -> Option<(f64, f64, f64)> {
+    let xi: f64 = x + phi.sin();
+    let eta: f64 = y - 1f64 - phi.cos();
+    let rho: f64 = (20f64 - xi * xi - eta * eta) / 16f64;
+    if !(0f64..=1f64).contains(&rho) {
+        return None;
+    }
+    let u: f64 = -rho.acos();
+    if u < -0.5 * PI {
+        return None;
+    }
+    let (t, v) = tau_omega(u, u, xi, eta, phi);
+    (t >= -ZERO && v >= -ZERO).then_some((t, u, v))
+}
+
+fn cccc(x: f64, y: f64, phi: f64, shortest: &mut Shortest) {
+    let (lrlr, rlrl): ([Turn; 4], [Turn; 4]) = ([L, R, L, R], [R, L, R, L]);
+    if let Some((t, u, v)) = lp_rup_lum_rm(x, y, phi) {
+        shortest.offer(&lrlr, &[t, u, -u, v]);
+    }
+    if let Some((t, u, v)) = lp_rup_lum_rm(-x, y, -phi) {
+        shortest.offer(&lrlr, &[-t, -u, u, -v]);
+    }
+    if let Some((t, u, v)) = lp_rup_lum_rm(x, -y, -phi) {
+        shortest.offer(&rlrl, &[t, u, -u, v]);
+    }
+    if let Some((t, u, v)) = lp_rup_lum_rm(-x, -y, phi) {
+        shortest.offer(&rlrl, &[-t, -u, u, -v]);
+    }
+
+    if let Some((t, u, v)) = lp_rum_lum_rp(x, y, phi) {
+        shortest.offer(&lrlr, &[t, u, u, v]);
+    }
+    if let Some((t, u, v)) = lp_rum_lum_rp(-x, y, -phi) {
+        shortest.offer(&lrlr, &[-t, -u, -u, -v]);
+    }
+    if let Some((t, u, v)) = lp_rum_lum_rp(x, -y, -phi) {
+        shortest.offer(&rlrl, &[t, u, u, v]);
+    }
+    if let Some((t, u, v)) = lp_rum_lum_rp(-x, -y, phi) {
+        shortest.offer(&rlrl, &[-t, -u, -u, -v]);
+    }
+}
+
+/// Formula 8.9 of Reeds and Shepp.
+fn lp_rm_sm_lm(x: f64, y: f64, phi: f64) -> Option<(f64, f64, f64)> {
+    let (rho, theta) = polar(x - phi.sin(), y - 1f64 + phi.cos());
+    if rho < 2f64 {
+        return None;
+    }
+    let r: f64 = (rho * rho - 4f64).sqrt();
+    let u: f64 = 2f64 - r;
+    let t: f64 = mod2pi(theta + r.atan2(-2f64));
+    let v: f64 = mod2pi(phi - 0.5 * PI - t);
+    (t >= -ZERO && u <= ZERO && v <= ZERO).then_some((t, u, v))
+}
+
+/// Formula 8.10 of Reeds and Shepp.
+fn lp_rm_sm_rm(x: f64, y: f64, phi: f64) -> Option<(f64, f64, f64)> {
+    let xi: f64 = x + phi.sin();
+    let eta: f64 = y - 1f64 - phi.cos();
+    let (rho, theta) = polar(-eta, xi);
+    if rho < 2f64 {
+        return None;
+    }
+    let t: f64 = theta;
+    let u: f64 = 2f64 - rho;
+    let v: f64 = mod2pi(t + 0.5 * PI - phi);
+    (t >= -ZERO && u <= ZERO && v <= ZERO).then_some((t, u, v))
+}
+
+fn ccsc(x: f64, y: f64, phi: f64, shortest: &mut Shortest) {
+    let half: f64 = 0.5 * PI;
+    for (formula, turns, reflected) in [
+        (lp_rm_sm_lm as Formula, [L, R, S, L], [R, L, S, R]),
+        (lp_rm_sm_rm as Formula, [L, R, S, R], [R, L, S, L]),
+    ] {
+        if let Some((t, u, v)) = formula(x, y, phi) {
+            shortest.offer(&turns, &[t, -half, u, v]);
+        }
+        if let Some((t, u, v)) = formula(-x, y, -phi) {
+            shortest.offer(&turns, &[-t, half, -u, -v]);
+        }
+        if let Some((t, u, v)) = formula(x, -y, -phi) {
+            shortest.offer(&reflected, &[t, -half, u, v]);
+        }
+        if let Some((t, u, v)) = formula(-x, -y, phi) {
+            shortest.offer(&reflected, &[-t, half, -u, -v]);
+        }
+    }
+
+    // backwards
+    let xb: f64 = x * phi.cos() + y * phi.sin();
+    let yb: f64 = x * phi.sin() - y * phi.cos();
+    for (formula, turns, reflected) in [
+        (lp_rm_sm_lm as Formula, [L, S, R, L], [R, S, L, R]),
+        (lp_rm_sm_rm as Formula, [R, S, R, L], [L, S, L, R]),
+    ] {
+        if let Some((t, u, v)) = formula(xb, yb, phi) {
+            shortest.offer(&turns, &[v, u, -half, t]);
+        }
+        if let Some((t, u, v)) = formula(-xb, yb, -phi) {
+            shortest.offer(&turns, &[-v, -u, half, -t]);
+        }
+        if let Some((t, u, v)) = formula(xb, -yb, -phi) {
+            shortest.offer(&reflected, &[v, u, -half, t]);
+        }
+        if let Some((t, u, v)) = formula(-xb, -yb, phi) {
+            shortest.offer(&reflected, &[-v, -u, half, -t]);
+        }
+    }
+}
+
+/// Formula 8.11 of Reeds and Shepp, with the typo of the paper fixed.
+fn lp_rm_s_lm_rp(x: f64, y: f64, phi: f64) -> Option<(f64, f64, f64)> {
+    let xi: f64 = x + phi.sin();
+    let eta: f64 = y - 1f64 - phi.cos();
+    let (rho, _) = polar(xi, eta);
+    if rho < 2f64 {
+        return None;
+    }
+    let u: f64 = 4f64 - (rho * rho - 4f64).sqrt();
+    if u > ZERO {
+        return None;
+    }
+    let t: f64 = mod2pi(((4f64 - u) * xi - 2f64 * eta).atan2(-2f64 * xi + (u - 4f64) * eta));
+    let v: f64 = mod2pi(t - phi);
+    (t >= -ZERO && v >= -ZERO).then_some((t, u, v))
+}
+
+fn ccscc(x: f64, y: f64, phi: f64, shortest: &mut Shortest) {
+    let half: f64 = 0.5 * PI;
+    let (lrslr, rlsrl): ([Turn; 5], [Turn; 5]) = ([L, R, S, L, R], [R, L, S, R, L]);
+    if let Some((t, u, v)) = lp_rm_s_lm_rp(x, y, phi) {
+        shortest.offer(&lrslr, &[t, -half, u, -half, v]);
+    }
+    if let Some((t, u, v)) = lp_rm_s_lm_rp(-x, y, -phi) {
+        shortest.offer(&lrslr, &[-t, half, -u, half, -v]);
+    }
+    if let Some((t, u, v)) = lp_rm_s_lm_rp(x, -y, -phi) {
+        shortest.offer(&rlsrl, &[t, -half, u, -half, v]);
+    }
+    if let Some((t, u, v)) = lp_rm_s_lm_rp(-x, -y, phi) {
+        shortest.offer(&rlsrl, &[-t, half, -u, half, -v]);
+    }
+}
+
+/// One of the formulas of Reeds and Shepp. Returns the lengths (t, u, v) of the pieces, if the curve exists.
+type Formula = fn(f64, f64, f64) -> Option<(f64, f64, f64)>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::{angle_difference, DubinsStateSpace};
+
+    /// Pairs of states in all directions, with different distances and headings.
+    fn get_pairs() -> Vec<(Point<3>, Point<3>)> {
+        let mut pairs: Vec<(Point<3>, Point<3>)> = Vec::new();
+        for (x, y) in [
+            (0.0, 0.0),
+            (3.0, 0.5),
+            (-0.4, 0.3),
+            (-2.0, -5.0),
+            (0.1, 1.9),
+        ] {
+            for theta1 in [-3.0, -1.2, 0.0, 0.7, 2.5] {
+                for theta2 in [-2.2, 0.0, 1.5, 3.1] {
+                    pairs.push((
+                        Point::from_coordinates([0.5, -0.5, theta1]),
+                        Point::from_coordinates([0.5 + x, -0.5 + y, theta2]),
+                    ));
+                }
+            }
+        }
+        pairs
+    }
+
+    fn is_close(a: &Point<3>, b: &Point<3>) -> bool {
+        let [x1, y1, theta1] = a.get_coordinates();
+        let [x2, y2, theta2] = b.get_coordinates();
+        (x1 - x2).abs() < 1e-6
+            && (y1 - y2).abs() < 1e-6
+            && angle_difference(theta1, theta2).abs() < 1e-6
+    }
+
+    // Test that the car drives backwards on a straight line.
+    #[test]
+    fn test_reverse() {
+        let space: ReedsSheppStateSpace = ReedsSheppStateSpace::new(2.0);
+        let from: Point<3> = Point::from_coordinates([0.0, 0.0, 0.0]);
+        let to: Point<3> = Point::from_coordinates([-3.0, 0.0, 0.0]);
+        assert!((space.distance(&from, &to) - 3.0).abs() < 1e-9);
+        assert!(is_close(
+            &space.interpolate(&from, &to, 0.5),
+            &Point::from_coordinates([-1.5, 0.0, 0.0])
+        ));
+    }
+
+    #[test]
+    fn test_half_circle() {
+        let space: ReedsSheppStateSpace = ReedsSheppStateSpace::new(2.0);
+        let from: Point<3> = Point::from_coordinates([0.0, 0.0, 0.0]);
+        let to: Point<3> = Point::from_coordinates([0.0, 4.0, PI]);
+        assert!((space.distance(&from, &to) - 2.0 * PI).abs() < 1e-9);
+    }
+
+    // Test that the curves end at the goal, are symmetric and lie between the straight line and the Dubins curve.
+    #[test]
+    fn test_reaches_goal() {
+        let space: Box<dyn StateSpace<3>> = ReedsSheppStateSpace::new_box(1.5);
+        let dubins: DubinsStateSpace = DubinsStateSpace::new(1.5);
+        assert!(space.is_symmetric());
+        for (from, to) in get_pairs() {
+            let distance: f64 = space.distance(&from, &to);
+            let [x1, y1, _] = from.get_coordinates();
+            let [x2, y2, _] = to.get_coordinates();
+            assert!(distance >= ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt() - 1e-9);
+            assert!(distance <= dubins.distance(&from, &to) + 1e-9);
+            assert!((distance - space.distance(&to, &from)).abs() < 1e-6);
+            assert!(is_close(&space.interpolate(&from, &to, 0.0), &from));
+            assert!(is_close(&space.interpolate(&from, &to, 1.0), &to));
+        }
+    }
+
+    #[test]
+    fn test_segments() {
+        let space: ReedsSheppStateSpace = ReedsSheppStateSpace::default();
+        let from: Point<3> = Point::from_coordinates([0.0, 0.0, 0.0]);
+        let to: Point<3> = Point::from_coordinates([0.0, 1.0, 0.0]);
+        let segments: Vec<(Point<3>, Point<3>)> = space.get_segments(&from, &to);
+        assert!(segments.len() as f64 >= space.distance(&from, &to) / space.segment_length);
+        assert_eq!(segments[0].0, from);
+        assert_eq!(segments[segments.len() - 1].1, to);
+    }
+}
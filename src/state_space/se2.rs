@@ -0,0 +1,188 @@
+use std::f64::consts::PI;
+
+use crate::boundaries::Boundaries;
+use crate::space::Point;
+use crate::state_space::{angle_difference, wrap_angle, StateSpace};
+
+/// Position and heading of a ground vehicle. The states are `Point<3>` with the coordinates (x, y, theta).
+/// The heading theta is wrapped into [-PI, PI), and rotations take the short way around.
+///
+/// The distance is sqrt(dx^2 + dy^2 + (rotation_weight * dtheta)^2).
+/// - rotation_weight: Length that is equivalent to a rotation of one radian
+#[derive(Debug, Copy, Clone)]
+pub struct SE2StateSpace {
+    pub rotation_weight: f64,
+}
+
+impl Default for SE2StateSpace {
+    fn default() -> Self {
+        SE2StateSpace {
+            rotation_weight: 1f64,
+        }
+    }
+}
+
+impl SE2StateSpace {
+    pub fn new(rotation_weight: f64) -> Self {
+        SE2StateSpace { rotation_weight }
+    }
+
+    pub fn new_box(rotation_weight: f64) -> Box<dyn StateSpace<3>> {
+        Box::new(SE2StateSpace::new(rotation_weight))
+    }
+
+    /// Returns boundaries for the given area, that allow every heading from -PI to PI.
+    pub fn boundaries(x_lower: f64, x_upper: f64, y_lower: f64, y_upper: f64) -> Boundaries<3> {
+        Boundaries::from_limits([x_lower, y_lower, -PI], [x_upper, y_upper, PI])
+    }
+}
+
+impl StateSpace<3> for SE2StateSpace {
+    fn distance(&self, from: &Point<3>, to: &Point<3>) -> f64 {
+        let [x1, y1, theta1] = from.get_coordinates();
+        let [x2, y2, theta2] = to.get_coordinates();
+        let rotation: f64 = self.rotation_weight * angle_difference(theta1, theta2);
+        ((x2 - x1).powi(2) + (y2 - y1).powi(2) + rotation.powi(2)).sqrt()
+    }
+
+    /// Moves the position on a straight line and rotates in the shorter direction.
+    fn interpolate(&self, from: &Point<3>, to: &Point<3>, t: f64) -> Point<3> {
+        let [x1, y1, theta1] = from.get_coordinates();
+        let [x2, y2, theta2] = to.get_coordinates();
+        Point::from_coordinates([
+            x1 + (x2 - x1) * t,
+            y1 + (y2 - y1) * t,
+            wrap_angle(theta1 + angle_difference(theta1, theta2) * t),
+        ])
+    }
+
+    fn enforce_bounds(&self, state: Point<3>) -> Point<3> {
+        let [x, y, theta] = state.get_coordinates();
+        Point::from_coordinates([x, y, wrap_angle(theta)])
+    }
+
+    /// The heading is scaled by the rotation weight, so the Euclidean distance between the closest images is the distance.
+    fn get_key(&self, state: &Point<3>) -> [f64; 3] {
+        let [x, y, theta] = state.get_coordinates();
+        [x, y, self.rotation_weight * wrap_angle(theta)]
+    }
+
+    /// Shifts the heading by a full turn in both directions.
+    fn get_key_images(&self, key: [f64; 3]) -> Vec<[f64; 3]> {
+        let turn: f64 = self.rotation_weight * 2f64 * PI;
+        vec![
+            key,
+            [key[0], key[1], key[2] - turn],
+            [key[0], key[1], key[2] + turn],
+        ]
+    }
+
+    /// Splits the motion where the heading passes from PI to -PI, so no segment rotates the long way around.
+    fn get_segments(&self, from: &Point<3>, to: &Point<3>) -> Vec<(Point<3>, Point<3>)> {
+        let [x1, y1, theta1] = self.enforce_bounds(*from).get_coordinates();
+        let [x2, y2, theta2] = self.enforce_bounds(*to).get_coordinates();
+        let rotation: f64 = angle_difference(theta1, theta2);
+        if (theta1 + rotation - theta2).abs() < 1e-9 {
+            return vec![(*from, *to)];
+        }
+        let seam: f64 = PI.copysign(rotation);
+        let t: f64 = (seam - theta1) / rotation;
+        let (x, y): (f64, f64) = (x1 + (x2 - x1) * t, y1 + (y2 - y1) * t);
+        vec![
+            (*from, Point::from_coordinates([x, y, seam])),
+            (Point::from_coordinates([x, y, -seam]), *to),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(x: f64, y: f64, theta: f64) -> Point<3> {
+        Point::from_coordinates([x, y, theta])
+    }
+
+    #[test]
+    fn test_distance() {
+        let space: SE2StateSpace = SE2StateSpace::new(2f64);
+        assert_eq!(
+            space.distance(&state(0.0, 0.0, 0.0), &state(3.0, 4.0, 0.0)),
+            5.0
+        );
+        assert!((space.distance(&state(0.0, 0.0, 0.0), &state(0.0, 0.0, 0.5)) - 1.0).abs() < 1e-12);
+        assert_eq!(SE2StateSpace::default().rotation_weight, 1f64);
+    }
+
+    // Test that headings close to PI and -PI are close to each other.
+    #[test]
+    fn test_distance_wraps() {
+        let space: SE2StateSpace = SE2StateSpace::default();
+        let distance: f64 = space.distance(&state(0.0, 0.0, 3.0), &state(0.0, 0.0, -3.0));
+        assert!((distance - (2f64 * PI - 6.0)).abs() < 1e-12);
+        assert_eq!(
+            space.distance(&state(1.0, 2.0, 3.0), &state(0.0, 0.0, -3.0)),
+            space.distance(&state(0.0, 0.0, -3.0), &state(1.0, 2.0, 3.0))
+        );
+    }
+
+    // Test that the heading is interpolated the short way around and stays wrapped.
+    #[test]
+    fn test_interpolate() {
+        let space: SE2StateSpace = SE2StateSpace::default();
+        let from: Point<3> = state(0.0, 0.0, 3.0);
+        let to: Point<3> = state(2.0, 4.0, -3.0);
+        let middle: [f64; 3] = space.interpolate(&from, &to, 0.5).get_coordinates();
+        assert_eq!(middle[0], 1.0);
+        assert_eq!(middle[1], 2.0);
+        assert!((middle[2] - (-PI)).abs() < 1e-12);
+        assert_eq!(space.interpolate(&from, &to, 0.0), from);
+        assert!(space.distance(&space.interpolate(&from, &to, 1.0), &to) < 1e-12);
+    }
+
+    #[test]
+    fn test_enforce_bounds() {
+        let space: SE2StateSpace = SE2StateSpace::default();
+        let wrapped: Point<3> = space.enforce_bounds(state(1.0, 2.0, 2f64 * PI + 0.5));
+        assert!(space.distance(&wrapped, &state(1.0, 2.0, 0.5)) < 1e-12);
+        assert!(!SE2StateSpace::boundaries(0.0, 1.0, 0.0, 1.0).is_node_inside(&wrapped));
+        assert!(SE2StateSpace::boundaries(0.0, 3.0, 0.0, 3.0).is_node_inside(&wrapped));
+    }
+
+    // Test that one of the key images is exactly as far away as the states.
+    #[test]
+    fn test_key_images() {
+        let space: SE2StateSpace = SE2StateSpace::new(0.5);
+        let a: Point<3> = state(0.0, 1.0, 3.0);
+        let b: Point<3> = state(1.0, 0.0, -3.0);
+        let key: [f64; 3] = space.get_key(&a);
+        let closest: f64 = space
+            .get_key_images(space.get_key(&b))
+            .iter()
+            .map(|image| {
+                Point::from_coordinates(*image).euclidean_distance(&Point::from_coordinates(key))
+            })
+            .fold(f64::INFINITY, f64::min);
+        assert!((closest - space.distance(&a, &b)).abs() < 1e-12);
+    }
+
+    // Test that a motion across the heading PI is split into two segments, and other motions are not.
+    #[test]
+    fn test_segments() {
+        let space: SE2StateSpace = SE2StateSpace::default();
+        let from: Point<3> = state(0.0, 0.0, 0.0);
+        let to: Point<3> = state(2.0, 0.0, 1.0);
+        assert_eq!(space.get_segments(&from, &to), vec![(from, to)]);
+
+        let from: Point<3> = state(0.0, 0.0, PI - 0.1);
+        let to: Point<3> = state(2.0, 0.0, -PI + 0.1);
+        let segments: Vec<(Point<3>, Point<3>)> = space.get_segments(&from, &to);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].0, from);
+        assert_eq!(segments[1].1, to);
+        let [x, _, theta] = segments[0].1.get_coordinates();
+        assert!((x - 1.0).abs() < 1e-9);
+        assert_eq!(theta, PI);
+        assert_eq!(segments[1].0.get_coordinates()[2], -PI);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::collision_checker::CollisionChecker;
+use crate::space::Point;
+
+/// Reports a collision for every node and edge.
+pub struct BlockedCollisionChecker {}
+
+impl CollisionChecker for BlockedCollisionChecker {
+    fn init(&self) -> bool {
+        true
+    }
+
+    fn is_node_colliding(&self, _node: &Point) -> bool {
+        true
+    }
+
+    fn is_edge_colliding(&self, _begin: &Point, _end: &Point) -> bool {
+        true
+    }
+}
+
+/// Blocks the square between (1, 1) and (2, 2).
+pub struct BoxCollisionChecker {}
+
+impl CollisionChecker for BoxCollisionChecker {
+    fn init(&self) -> bool {
+        true
+    }
+
+    fn is_node_colliding(&self, node: &Point) -> bool {
+        node.get_x() >= 1.0 && node.get_x() <= 2.0 && node.get_y() >= 1.0 && node.get_y() <= 2.0
+    }
+
+    fn is_edge_colliding(&self, begin: &Point, end: &Point) -> bool {
+        (0..=50).any(|i| self.is_node_colliding(&begin.interpolate(end, i as f64 / 50.0)))
+    }
+}
+
+/// Blocks -2.5 <= x <= 2.5, so a circular x axis between -PI and PI can only be crossed at PI.
+pub struct SeamCollisionChecker {}
+
+impl CollisionChecker for SeamCollisionChecker {
+    fn init(&self) -> bool {
+        true
+    }
+
+    fn is_node_colliding(&self, node: &Point) -> bool {
+        node.get_x().abs() <= 2.5
+    }
+
+    fn is_edge_colliding(&self, begin: &Point, end: &Point) -> bool {
+        (0..=20).any(|i| self.is_node_colliding(&begin.interpolate(end, i as f64 / 20.0)))
+    }
+}
@@ -3,9 +3,9 @@ use std::marker::PhantomData;
 use geo::{Contains, Intersects};
 use mpl::{
     boundaries::Boundaries,
-    collision_checker::CollisionChecker,
+    collision_checker::{CollisionChecker, OccupancyGridCollisionChecker, PolygonCollisionChecker},
     optimizer::{DefaultOptimizer, Optimizer},
-    planner::{base_planner::Planner, prm::PRM, prm_star::PRMstar},
+    planner::{base_planner::Planner, prm::PRM, prm_star::PRMstar, rrt_star::RRTstar},
     problem::ProblemDefinition,
     setup::PlanningSetup,
     space::Point,
@@ -24,14 +24,38 @@ fn test_prmstar_scenario() {
         boundaries: bounds,
         ready: false,
     };
-    setup.setup();
-    setup.solve();
+    setup.setup().unwrap();
+    setup.solve().unwrap();
     let cost = setup.planner.get_solution_cost();
 
     assert!(cost > 2f64);
     assert!(cost < 10f64);
 }
 
+#[test]
+fn test_seeded_setup_is_reproducible() {
+    let solve = |planner: Box<dyn Planner>| -> (f64, Vec<Point>) {
+        let mut setup: PlanningSetup = PlanningSetup {
+            planner,
+            problem: ProblemDefinition::new(Point::new(0f64, 0f64), Point::new(3f64, 3f64)),
+            boundaries: Boundaries::new_seeded(0f64, 3f64, 0f64, 3f64, 7),
+            ready: false,
+        };
+        setup.setup().unwrap();
+        setup.solve().unwrap();
+        (setup.get_statistics(), setup.problem.solution)
+    };
+
+    assert_eq!(
+        solve(Box::<PRMstar>::default()),
+        solve(Box::<PRMstar>::default())
+    );
+    assert_eq!(
+        solve(Box::<RRTstar>::default()),
+        solve(Box::<RRTstar>::default())
+    );
+}
+
 #[test]
 fn test_prm_naiv_scenario() {
     let bounds: Boundaries = Boundaries::new(0f64, 3f64, 0f64, 3f64);
@@ -46,14 +70,44 @@ fn test_prm_naiv_scenario() {
         boundaries: bounds,
         ready: false,
     };
-    setup.setup();
-    setup.solve();
+    setup.setup().unwrap();
+    setup.solve().unwrap();
     let cost = setup.planner.get_solution_cost();
 
     assert!(cost > 2f64);
     assert!(cost < 10f64);
 }
 
+#[test]
+fn test_termination_on_setup() {
+    use mpl::planner::rrt_star::RRTstar;
+    use mpl::planner::termination::{CostThreshold, Or, TerminationReason, Timeout};
+    use std::time::Duration;
+
+    let bounds: Boundaries = Boundaries::new(0f64, 3f64, 0f64, 3f64);
+    let mut planner = Box::<RRTstar>::default();
+    planner.set_termination_criteria(Or::new_box(
+        Timeout::new_box(Duration::from_secs(10)),
+        CostThreshold::new_box(5f64),
+    ));
+    let pdef: ProblemDefinition =
+        ProblemDefinition::new(Point::new(0f64, 0f64), Point::new(3f64, 3f64));
+    let mut setup: PlanningSetup = PlanningSetup {
+        planner,
+        problem: pdef,
+        boundaries: bounds,
+        ready: false,
+    };
+    setup.setup().unwrap();
+    setup.solve().unwrap();
+
+    assert_eq!(
+        setup.planner.get_termination_reason(),
+        Some(TerminationReason::CostThreshold)
+    );
+    assert!(setup.planner.get_solution_cost() < 5f64);
+}
+
 #[test]
 #[should_panic]
 fn test_rrt_naiv_scenario() {
@@ -113,11 +167,597 @@ fn test_geo_collision() {
         boundaries: bounds,
         ready: false,
     };
-    setup.setup();
-    setup.solve();
+    setup.setup().unwrap();
+    setup.solve().unwrap();
     let cost: f64 = setup.planner.get_solution_cost();
     println!("{}", cost);
 
     assert!(cost > 2.0f64);
     assert!(cost < f64::MAX);
 }
+
+#[test]
+fn test_polygon_collision_checker() {
+    use geo::polygon;
+
+    let obstacles = vec![
+        polygon![(x: 1., y: 1.), (x: 2., y: 1.), (x: 2., y: 2.), (x: 1., y: 2.)],
+        polygon![(x: 0.5, y: 2.5), (x: 1., y: 2.5), (x: 1., y: 3.), (x: 0.5, y: 3.)],
+    ];
+    let mut planner: Box<PRMstar> = Box::default();
+    planner.set_collision_checker(PolygonCollisionChecker::new_box(obstacles));
+
+    let mut setup: PlanningSetup = PlanningSetup {
+        planner,
+        problem: ProblemDefinition::new(Point::new(0f64, 0f64), Point::new(3f64, 3f64)),
+        boundaries: Boundaries::new_seeded(0f64, 3f64, 0f64, 3f64, 3),
+        ready: false,
+    };
+    setup.setup().unwrap();
+    setup.solve().unwrap();
+    let cost: f64 = setup.get_statistics();
+
+    assert!(cost > 2f64 * 5f64.sqrt());
+    assert!(cost < f64::MAX);
+    let collision_checker = setup.planner.get_collision_checker();
+    for edge in setup.problem.solution.windows(2) {
+        assert!(!collision_checker.is_edge_colliding(&edge[0], &edge[1]));
+    }
+}
+
+#[test]
+fn test_occupancy_grid_collision_checker() {
+    use mpl::planner::rrt_connect::RRTConnect;
+
+    // 30 x 30 cells of 0.1 with a wall at x = 1.5, which has a gap at the top.
+    let mut pgm: Vec<u8> = b"P5\n30 30\n255\n".to_vec();
+    for row in 0..30 {
+        for column in 0..30 {
+            let wall: bool = column == 15 && row >= 5;
+            pgm.push(if wall { 0 } else { 254 });
+        }
+    }
+    let cc = OccupancyGridCollisionChecker::from_pgm(&pgm, 0.1, Point::new(0f64, 0f64)).unwrap();
+    let boundaries: Boundaries = cc.get_boundaries();
+
+    let mut planner: Box<RRTConnect> = Box::default();
+    planner.set_collision_checker(Box::new(cc));
+    let mut setup: PlanningSetup = PlanningSetup {
+        planner,
+        problem: ProblemDefinition::new(Point::new(0.5, 0.5), Point::new(2.5, 0.5)),
+        boundaries,
+        ready: false,
+    };
+    setup.setup().unwrap();
+    setup.solve().unwrap();
+
+    let collision_checker = setup.planner.get_collision_checker();
+    assert!(setup.problem.solution.iter().any(|p| p.get_y() > 2.5));
+    for edge in setup.problem.solution.windows(2) {
+        assert!(!collision_checker.is_edge_colliding(&edge[0], &edge[1]));
+    }
+}
+
+#[test]
+fn test_space_time_rrt_with_dynamic_obstacles() {
+    use geo::polygon;
+    use mpl::collision_checker::{DynamicCollisionChecker, DynamicObstacle, Keyframe};
+    use mpl::planner::space_time_rrt::SpaceTimeRRT;
+
+    // Vehicle of 1 x 1 that drives through the aisle y in [0, 3] from the bottom to the top and back.
+    let vehicle = DynamicObstacle::new(
+        polygon![(x: -0.5, y: -0.5), (x: 0.5, y: -0.5), (x: 0.5, y: 0.5), (x: -0.5, y: 0.5)],
+        vec![
+            Keyframe::new(0f64, Point::new(1.5, 0.5), 0f64),
+            Keyframe::new(5f64, Point::new(1.5, 2.5), 0f64),
+            Keyframe::new(10f64, Point::new(1.5, 0.5), 0f64),
+        ],
+    )
+    .unwrap();
+    let static_obstacles = PolygonCollisionChecker::new_box(Vec::new());
+    let cc: DynamicCollisionChecker = DynamicCollisionChecker::new(static_obstacles, vec![vehicle]);
+    let boundaries: Boundaries = Boundaries::new_seeded(0f64, 3f64, 0f64, 3f64, 11);
+
+    let mut planner: Box<SpaceTimeRRT> = Box::new(SpaceTimeRRT::new(boundaries.clone(), cc));
+    planner.config.time_horizon = 10f64;
+    planner.config.max_size = 500;
+    let mut setup: PlanningSetup = PlanningSetup {
+        planner,
+        problem: ProblemDefinition::new(Point::new(0.2, 1.5), Point::new(2.8, 1.5)),
+        boundaries,
+        ready: false,
+    };
+    setup.setup().unwrap();
+    setup.solve().unwrap();
+
+    assert!(setup.get_statistics() >= 2.6);
+    assert_eq!(setup.problem.solution.first(), Some(&Point::new(0.2, 1.5)));
+    assert_eq!(setup.problem.solution.last(), Some(&Point::new(2.8, 1.5)));
+}
+
+/// Spherical obstacle in 3D, e.g. a tree a drone has to fly around.
+struct SphereCollisionChecker {
+    center: Point<3>,
+    radius: f64,
+}
+
+impl CollisionChecker<3> for SphereCollisionChecker {
+    fn init(&self) -> bool {
+        true
+    }
+
+    fn is_node_colliding(&self, node: &Point<3>) -> bool {
+        node.euclidean_distance(&self.center) <= self.radius
+    }
+
+    /// Projects the center onto the segment and checks the distance to the closest point.
+    fn is_edge_colliding(&self, begin: &Point<3>, end: &Point<3>) -> bool {
+        let (a, b, c) = (
+            begin.get_coordinates(),
+            end.get_coordinates(),
+            self.center.get_coordinates(),
+        );
+        let dot = |u: [f64; 3], v: [f64; 3]| u[0] * v[0] + u[1] * v[1] + u[2] * v[2];
+        let direction: [f64; 3] = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+        let length_2: f64 = dot(direction, direction);
+        let t: f64 = if length_2 > 0f64 {
+            (dot([c[0] - a[0], c[1] - a[1], c[2] - a[2]], direction) / length_2).clamp(0f64, 1f64)
+        } else {
+            0f64
+        };
+        self.is_node_colliding(&begin.interpolate(end, t))
+    }
+}
+
+#[test]
+fn test_planning_in_3d() {
+    use mpl::planner::termination::SolutionFound;
+
+    let start: Point<3> = Point::from_coordinates([0f64, 0f64, 0f64]);
+    let goal: Point<3> = Point::from_coordinates([3f64, 3f64, 3f64]);
+    let planners: Vec<Box<dyn Planner<3>>> = vec![
+        Box::<PRM<3>>::default(),
+        Box::new(PRMstar::new(
+            start,
+            goal,
+            Boundaries::default(),
+            DefaultOptimizer::new_box(),
+            mpl::collision_checker::NaiveCollisionChecker::new_box(),
+        )),
+    ];
+    for mut planner in planners {
+        planner.set_collision_checker(Box::new(SphereCollisionChecker {
+            center: Point::from_coordinates([1.5, 1.5, 1.5]),
+            radius: 1f64,
+        }));
+        planner.set_termination_criteria(SolutionFound::new_box());
+        let mut setup: PlanningSetup<3> = PlanningSetup {
+            planner,
+            problem: ProblemDefinition::new(start, goal),
+            boundaries: Boundaries::from_limits([0f64; 3], [3f64; 3]),
+            ready: false,
+        };
+        setup.boundaries.set_seed(3);
+        setup.setup().unwrap();
+        setup.solve().unwrap();
+
+        let solution: &Vec<Point<3>> = &setup.problem.solution;
+        assert_eq!(solution.first(), Some(&start));
+        assert_eq!(solution.last(), Some(&goal));
+        assert!(solution
+            .iter()
+            .all(|point| point.euclidean_distance(&Point::from_coordinates([1.5; 3])) > 1f64));
+    }
+}
+
+/// Vehicle that has to face west, i.e. its heading has to stay within 2.5 of PI.
+/// Edges are straight in the coordinates (x, y, theta), so an edge is free, if both of its ends are on the same side of the seam at PI.
+struct FacingWestCollisionChecker {}
+
+impl CollisionChecker<3> for FacingWestCollisionChecker {
+    fn init(&self) -> bool {
+        true
+    }
+
+    fn is_node_colliding(&self, node: &Point<3>) -> bool {
+        node.get_coordinates()[2].abs() < 2.5
+    }
+
+    fn is_edge_colliding(&self, begin: &Point<3>, end: &Point<3>) -> bool {
+        self.is_node_colliding(begin)
+            || self.is_node_colliding(end)
+            || begin.get_coordinates()[2].signum() != end.get_coordinates()[2].signum()
+    }
+}
+
+// Test that the heading wraps around at PI, because turning the long way around is in collision.
+#[test]
+fn test_planning_in_se2() {
+    use mpl::optimizer::StateSpaceOptimizer;
+    use mpl::planner::rrt_connect::RRTConnect;
+    use mpl::planner::termination::SolutionFound;
+    use mpl::state_space::SE2StateSpace;
+
+    let start: Point<3> = Point::from_coordinates([0.5, 0.5, 3f64]);
+    let goal: Point<3> = Point::from_coordinates([2.5, 2.5, -3f64]);
+    let mut prm: PRM<3> = PRM::default();
+    prm.set_state_space(SE2StateSpace::new_box(0.5));
+    let mut prm_star: PRMstar<3> = PRMstar::new(
+        start,
+        goal,
+        Boundaries::default(),
+        StateSpaceOptimizer::new_box(SE2StateSpace::new_box(0.5)),
+        Box::new(FacingWestCollisionChecker {}),
+    );
+    prm_star.set_state_space(SE2StateSpace::new_box(0.5));
+    let mut rrt_star: RRTstar<3> = RRTstar::new(
+        Boundaries::default(),
+        StateSpaceOptimizer::new_box(SE2StateSpace::new_box(0.5)),
+        Box::new(FacingWestCollisionChecker {}),
+    );
+    rrt_star.set_state_space(SE2StateSpace::new_box(0.5));
+    let mut rrt_connect: RRTConnect<3> = RRTConnect::default();
+    rrt_connect.set_state_space(SE2StateSpace::new_box(0.5));
+    let planners: Vec<Box<dyn Planner<3>>> = vec![
+        Box::new(prm),
+        Box::new(prm_star),
+        Box::new(rrt_star),
+        Box::new(rrt_connect),
+    ];
+
+    for mut planner in planners {
+        planner.set_collision_checker(Box::new(FacingWestCollisionChecker {}));
+        planner.set_termination_criteria(SolutionFound::new_box());
+        let mut setup: PlanningSetup<3> = PlanningSetup {
+            planner,
+            problem: ProblemDefinition::new(start, goal),
+            boundaries: SE2StateSpace::boundaries(0f64, 3f64, 0f64, 3f64),
+            ready: false,
+        };
+        setup.boundaries.set_seed(5);
+        setup.setup().unwrap();
+        setup.solve().unwrap();
+
+        let solution: &Vec<Point<3>> = &setup.problem.solution;
+        assert_eq!(solution.first(), Some(&start));
+        assert_eq!(solution.last(), Some(&goal));
+        assert!(solution
+            .iter()
+            .all(|state| state.get_coordinates()[2].abs() >= 2.5));
+        assert!(
+            solution
+                .windows(2)
+                .any(|pair| pair[0].get_coordinates()[2] > 0f64
+                    && pair[1].get_coordinates()[2] < 0f64)
+        );
+    }
+}
+
+/// Keeps the position of the car inside a square of 10 x 10.
+struct ParkingLotCollisionChecker {}
+
+impl CollisionChecker<3> for ParkingLotCollisionChecker {
+    fn init(&self) -> bool {
+        true
+    }
+
+    fn is_node_colliding(&self, node: &Point<3>) -> bool {
+        let [x, y, _] = node.get_coordinates();
+        !(0f64..=10f64).contains(&x) || !(0f64..=10f64).contains(&y)
+    }
+
+    fn is_edge_colliding(&self, begin: &Point<3>, end: &Point<3>) -> bool {
+        self.is_node_colliding(begin) || self.is_node_colliding(end)
+    }
+}
+
+// Test that the car turns around with curves in every planner, and the edges are followed in their direction.
+#[test]
+fn test_planning_with_car_curves() {
+    use mpl::optimizer::StateSpaceOptimizer;
+    use mpl::planner::rrt::RRT;
+    use mpl::planner::rrt_connect::RRTConnect;
+    use mpl::planner::termination::SolutionFound;
+    use mpl::sampler::{GoalBiasedSampler, UniformSampler};
+    use mpl::state_space::{DubinsStateSpace, ReedsSheppStateSpace, SE2StateSpace, StateSpace};
+
+    let start: Point<3> = Point::from_coordinates([3f64, 5f64, 0f64]);
+    let goal: Point<3> = Point::from_coordinates([7f64, 5f64, 3f64]);
+    let state_spaces: Vec<fn() -> Box<dyn StateSpace<3>>> =
+        vec![|| DubinsStateSpace::new_box(1f64), || {
+            ReedsSheppStateSpace::new_box(1f64)
+        }];
+
+    for state_space in state_spaces {
+        let mut rrt: RRT<3> = RRT::default();
+        rrt.set_state_space(state_space());
+        let mut prm: PRM<3> = PRM::default();
+        prm.set_state_space(state_space());
+        let mut prm_star: PRMstar<3> = PRMstar::new(
+            start,
+            goal,
+            Boundaries::default(),
+            StateSpaceOptimizer::new_box(state_space()),
+            Box::new(ParkingLotCollisionChecker {}),
+        );
+        prm_star.set_state_space(state_space());
+        let mut rrt_star: RRTstar<3> = RRTstar::new(
+            Boundaries::default(),
+            StateSpaceOptimizer::new_box(state_space()),
+            Box::new(ParkingLotCollisionChecker {}),
+        );
+        rrt_star.set_state_space(state_space());
+        rrt_star.set_sampler(GoalBiasedSampler::new_box(
+            goal,
+            0.1,
+            UniformSampler::new_box(),
+        ));
+        let mut rrt_connect: RRTConnect<3> = RRTConnect::default();
+        rrt_connect.set_state_space(state_space());
+        let planners: Vec<Box<dyn Planner<3>>> = vec![
+            Box::new(rrt),
+            Box::new(prm),
+            Box::new(prm_star),
+            Box::new(rrt_star),
+            Box::new(rrt_connect),
+        ];
+
+        for mut planner in planners {
+            planner.set_collision_checker(Box::new(ParkingLotCollisionChecker {}));
+            planner.set_termination_criteria(SolutionFound::new_box());
+            let mut setup: PlanningSetup<3> = PlanningSetup {
+                planner,
+                problem: ProblemDefinition::new(start, goal),
+                boundaries: SE2StateSpace::boundaries(0f64, 10f64, 0f64, 10f64),
+                ready: false,
+            };
+            setup.boundaries.set_seed(3);
+            setup.setup().unwrap();
+            setup.solve().unwrap();
+
+            let solution: &Vec<Point<3>> = &setup.problem.solution;
+            assert_eq!(solution.first(), Some(&start));
+            assert_eq!(solution.last(), Some(&goal));
+            let curve: Vec<Point<3>> = state_space().interpolate_path(solution, 20);
+            assert!(curve
+                .iter()
+                .all(|state| !ParkingLotCollisionChecker {}.is_node_colliding(state)));
+
+            let length: f64 = solution
+                .windows(2)
+                .map(|pair| state_space().distance(&pair[0], &pair[1]))
+                .sum();
+            assert!((setup.planner.get_solution_cost() - length).abs() < 1e-6);
+        }
+    }
+}
+
+/// Gate at 4 <= x <= 6, that the mobile manipulator only passes with the first joint of its arm raised to at least 1.
+struct GateCollisionChecker {}
+
+impl CollisionChecker<5> for GateCollisionChecker {
+    fn init(&self) -> bool {
+        true
+    }
+
+    fn is_node_colliding(&self, node: &Point<5>) -> bool {
+        let [x, _, _, joint, _] = node.get_coordinates();
+        (4f64..=6f64).contains(&x) && joint < 1f64
+    }
+
+    fn is_edge_colliding(&self, begin: &Point<5>, end: &Point<5>) -> bool {
+        (0..=20).any(|step| self.is_node_colliding(&begin.interpolate(end, step as f64 / 20f64)))
+    }
+}
+
+// Test that the planners work unchanged on a base in SE(2) with an arm of two joints.
+#[test]
+fn test_planning_mobile_manipulator() {
+    use mpl::optimizer::StateSpaceOptimizer;
+    use mpl::planner::rrt::RRT;
+    use mpl::planner::rrt_connect::RRTConnect;
+    use mpl::planner::termination::SolutionFound;
+    use mpl::sampler::{GoalBiasedSampler, UniformSampler};
+    use mpl::state_space::{CompoundStateSpace, EuclideanStateSpace, SE2StateSpace};
+
+    fn get_manipulator() -> CompoundStateSpace<5> {
+        let mut space: CompoundStateSpace<5> = CompoundStateSpace::new();
+        space
+            .add_subspace(
+                SE2StateSpace::new_box(0.5),
+                &SE2StateSpace::boundaries(0f64, 10f64, 0f64, 10f64),
+                1f64,
+            )
+            .unwrap();
+        space
+            .add_subspace(
+                EuclideanStateSpace::new_box(),
+                &Boundaries::from_limits([0f64; 2], [2f64; 2]),
+                0.5,
+            )
+            .unwrap();
+        space
+    }
+
+    let start: Point<5> = Point::from_coordinates([1f64, 5f64, 0f64, 0f64, 0f64]);
+    let goal: Point<5> = Point::from_coordinates([9f64, 5f64, 3f64, 0f64, 0f64]);
+    let mut rrt: RRT<5> = RRT::default();
+    rrt.set_state_space(Box::new(get_manipulator()));
+    let mut prm_star: PRMstar<5> = PRMstar::new(
+        start,
+        goal,
+        Boundaries::default(),
+        StateSpaceOptimizer::new_box(Box::new(get_manipulator())),
+        Box::new(GateCollisionChecker {}),
+    );
+    prm_star.set_state_space(Box::new(get_manipulator()));
+    let mut rrt_star: RRTstar<5> = RRTstar::new(
+        Boundaries::default(),
+        StateSpaceOptimizer::new_box(Box::new(get_manipulator())),
+        Box::new(GateCollisionChecker {}),
+    );
+    rrt_star.set_state_space(Box::new(get_manipulator()));
+    rrt_star.set_sampler(GoalBiasedSampler::new_box(
+        goal,
+        0.1,
+        UniformSampler::new_box(),
+    ));
+    let mut rrt_connect: RRTConnect<5> = RRTConnect::default();
+    rrt_connect.set_state_space(Box::new(get_manipulator()));
+    let planners: Vec<Box<dyn Planner<5>>> = vec![
+        Box::new(rrt),
+        Box::new(prm_star),
+        Box::new(rrt_star),
+        Box::new(rrt_connect),
+    ];
+
+    for mut planner in planners {
+        planner.set_collision_checker(Box::new(GateCollisionChecker {}));
+        planner.set_termination_criteria(SolutionFound::new_box());
+        let mut setup: PlanningSetup<5> = PlanningSetup {
+            planner,
+            problem: ProblemDefinition::new(start, goal),
+            boundaries: get_manipulator().get_boundaries(),
+            ready: false,
+        };
+        setup.boundaries.set_seed(11);
+        setup.setup().unwrap();
+        setup.solve().unwrap();
+
+        let solution: &Vec<Point<5>> = &setup.problem.solution;
+        assert_eq!(solution.first(), Some(&start));
+        assert_eq!(solution.last(), Some(&goal));
+        assert!(solution
+            .iter()
+            .any(|state| state.get_coordinates()[3] >= 1f64));
+    }
+}
+
+/// Two continuous revolute joints. The first joint is blocked for -2.3 <= q1 <= 2.3, so it can only turn across PI.
+struct RevoluteCollisionChecker {}
+
+impl CollisionChecker for RevoluteCollisionChecker {
+    fn init(&self) -> bool {
+        true
+    }
+
+    fn is_node_colliding(&self, node: &Point) -> bool {
+        node.get_x().abs() <= 2.3
+    }
+
+    fn is_edge_colliding(&self, begin: &Point, end: &Point) -> bool {
+        (0..=20).any(|step| self.is_node_colliding(&begin.interpolate(end, step as f64 / 20f64)))
+    }
+}
+
+// Test that the planners turn revolute joints across the seam, because the long way around is blocked.
+#[test]
+fn test_planning_revolute_joints() {
+    use mpl::boundaries::Topology;
+    use mpl::optimizer::StateSpaceOptimizer;
+    use mpl::planner::rrt::RRT;
+    use mpl::planner::rrt_connect::RRTConnect;
+    use mpl::planner::termination::SolutionFound;
+    use mpl::state_space::{JointStateSpace, StateSpace};
+    use std::f64::consts::PI;
+
+    let mut boundaries: Boundaries = Boundaries::new(-PI, PI, -PI, PI);
+    boundaries.set_topology([Topology::Circular; 2]);
+    let start: Point = Point::new(2.5, 3f64);
+    let goal: Point = Point::new(-2.5, -3f64);
+    let mut rrt: RRT = RRT::default();
+    rrt.set_state_space(JointStateSpace::new_box(&boundaries));
+    let mut prm: PRM = PRM::default();
+    prm.set_state_space(JointStateSpace::new_box(&boundaries));
+    let mut rrt_star: RRTstar = RRTstar::new(
+        Boundaries::default(),
+        StateSpaceOptimizer::new_box(JointStateSpace::new_box(&boundaries)),
+        Box::new(RevoluteCollisionChecker {}),
+    );
+    rrt_star.set_state_space(JointStateSpace::new_box(&boundaries));
+    let mut rrt_connect: RRTConnect = RRTConnect::default();
+    rrt_connect.set_state_space(JointStateSpace::new_box(&boundaries));
+    let planners: Vec<Box<dyn Planner>> = vec![
+        Box::new(rrt),
+        Box::new(prm),
+        Box::new(rrt_star),
+        Box::new(rrt_connect),
+    ];
+
+    for mut planner in planners {
+        planner.set_collision_checker(Box::new(RevoluteCollisionChecker {}));
+        planner.set_termination_criteria(SolutionFound::new_box());
+        let mut setup: PlanningSetup = PlanningSetup {
+            planner,
+            problem: ProblemDefinition::new(start, goal),
+            boundaries: boundaries.clone(),
+            ready: false,
+        };
+        setup.boundaries.set_seed(8);
+        setup.setup().unwrap();
+        setup.solve().unwrap();
+
+        let solution: &Vec<Point> = &setup.problem.solution;
+        assert_eq!(solution.first(), Some(&start));
+        assert_eq!(solution.last(), Some(&goal));
+        let space: JointStateSpace = JointStateSpace::new(&boundaries);
+        let length: f64 = solution
+            .windows(2)
+            .map(|pair| space.distance(&pair[0], &pair[1]))
+            .sum();
+        assert!(length < 2f64 * PI);
+        assert!(solution
+            .iter()
+            .all(|state| !RevoluteCollisionChecker {}.is_node_colliding(state)));
+    }
+}
+
+// Test that a rectangular robot turns before it drives through a corridor, that is narrower than its length.
+#[test]
+fn test_planning_footprint_in_se2() {
+    use geo::polygon;
+    use mpl::collision_checker::{Footprint, FootprintCollisionChecker};
+    use mpl::planner::rrt_connect::RRTConnect;
+    use mpl::state_space::{SE2StateSpace, StateSpace};
+
+    let obstacles: PolygonCollisionChecker = PolygonCollisionChecker::new(vec![
+        polygon![(x: 0., y: 1.), (x: 1.3, y: 1.), (x: 1.3, y: 2.), (x: 0., y: 2.)],
+        polygon![(x: 1.7, y: 1.), (x: 3., y: 1.), (x: 3., y: 2.), (x: 1.7, y: 2.)],
+    ]);
+    let footprint: Footprint = Footprint::Polygon {
+        polygon: polygon![(x: -0.5, y: -0.1), (x: 0.5, y: -0.1), (x: 0.5, y: 0.1), (x: -0.5, y: 0.1)],
+    };
+    let start: Point<3> = Point::from_coordinates([1.5, 0.3, 0f64]);
+    let goal: Point<3> = Point::from_coordinates([1.5, 2.7, 0f64]);
+    let mut rrt_connect: RRTConnect<3> = RRTConnect::default();
+    rrt_connect.set_state_space(SE2StateSpace::new_box(0.5));
+    rrt_connect.config.max_size = 100_000;
+    let mut setup: PlanningSetup<3> = PlanningSetup {
+        planner: Box::new(rrt_connect),
+        problem: ProblemDefinition::new(start, goal),
+        boundaries: SE2StateSpace::boundaries(0f64, 3f64, 0f64, 3f64),
+        ready: false,
+    };
+    setup
+        .planner
+        .set_collision_checker(FootprintCollisionChecker::new_box(
+            footprint.clone(),
+            obstacles.clone(),
+        ));
+    setup.boundaries.set_seed(4);
+    setup.setup().unwrap();
+    setup.solve().unwrap();
+
+    let cc: FootprintCollisionChecker = FootprintCollisionChecker::new(footprint, obstacles);
+    let solution: &Vec<Point<3>> = &setup.problem.solution;
+    assert_eq!(solution.first(), Some(&start));
+    assert_eq!(solution.last(), Some(&goal));
+    let space: SE2StateSpace = SE2StateSpace::new(0.5);
+    for pair in solution.windows(2) {
+        assert!(!space.is_motion_colliding(&cc, &pair[0], &pair[1]));
+    }
+    assert!(solution.iter().any(|pose| {
+        let [_, y, theta] = pose.get_coordinates();
+        (1f64..=2f64).contains(&y) && (theta.abs() - std::f64::consts::FRAC_PI_2).abs() < 0.2
+    }));
+}
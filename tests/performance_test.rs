@@ -12,7 +12,7 @@ fn test_performance_prm() {
     let mut planner: Box<PRM> = Box::default();
     planner.config.max_size = 10usize;
     let problem = ProblemDefinition::new(start, goal);
-    let boundaries = Boundaries::new(0f64, 3f64, 0f64, 3f64);
+    let boundaries = Boundaries::new_seeded(0f64, 3f64, 0f64, 3f64, 42);
 
     let mut setup: PlanningSetup = PlanningSetup {
         planner,
@@ -20,11 +20,11 @@ fn test_performance_prm() {
         boundaries,
         ready: false,
     };
-    setup.setup();
+    setup.setup().unwrap();
 
     println!("#### mpl ####");
     let start = Instant::now();
-    setup.solve();
+    setup.solve().unwrap();
     let duration = start.elapsed();
     println!("Time elapsed in expensive_function() is: {duration:?}");
     let cost1: f64 = setup.get_statistics();
@@ -32,20 +32,16 @@ fn test_performance_prm() {
     let mut planner2: Box<PRM> = Box::default();
     planner2.config.max_size = 1000usize;
     setup.planner = planner2;
-    setup.setup();
+    setup.setup().unwrap();
     println!("#### mpl ####");
     let start2 = Instant::now();
-    setup.solve();
+    setup.solve().unwrap();
     let duration2 = start2.elapsed();
     println!("Time elapsed in expensive_function() is: {duration2:?}");
     let cost2: f64 = setup.get_statistics();
 
-    let time_increase = if duration2 > 3 * duration {
-        true
-    } else {
-        false
-    };
-    assert!(time_increase);
-    let cost_improvement: bool = if cost2 < 0.5 * cost1 { true } else { false };
+    // The cost is the length of the path. The larger roadmap is not worse and close to the straight line.
+    let optimum: f64 = Point::new(1f64, 1f64).euclidean_distance(&Point::new(2f64, 2f64));
+    let cost_improvement: bool = cost2 <= cost1 && cost2 < 1.05 * optimum;
     assert!(cost_improvement)
 }